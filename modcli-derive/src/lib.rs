@@ -0,0 +1,389 @@
+//! `#[derive(ModCommand)]` for mod-cli.
+//!
+//! Generates `modcli::derive::ModCommand` for a struct with named fields: the
+//! command's metadata from `#[command(...)]` and doc comments, a `CommandSpec`
+//! from the fields, and parsing of `ParsedArgs` back into the struct. Use it
+//! through the `derive` feature of `mod-cli`; see `modcli::derive`.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, ToTokens};
+use syn::meta::ParseNestedMeta;
+use syn::punctuated::Punctuated;
+use syn::{
+    parse_macro_input, Attribute, Data, DeriveInput, Expr, Fields, GenericArgument, Lit, LitChar,
+    LitStr, PathArguments, Token, Type,
+};
+
+#[proc_macro_derive(ModCommand, attributes(command, arg))]
+pub fn derive_mod_command(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+// `#[command(...)]` on the struct.
+#[derive(Default)]
+struct CommandAttrs {
+    name: Option<String>,
+    aliases: Vec<String>,
+    help: Option<String>,
+    hidden: bool,
+    caps: Vec<String>,
+    category: Option<String>,
+}
+
+// `#[arg(...)]` on a field.
+#[derive(Default)]
+struct ArgAttrs {
+    name: Option<String>,
+    positional: bool,
+    count: bool,
+    short: Option<char>,
+    long: Option<String>,
+    help: Option<String>,
+    default: Option<String>,
+    value_name: Option<String>,
+    choices: Vec<String>,
+}
+
+// How a field's type maps onto an argument.
+enum Shape<'a> {
+    Flag,
+    Count,
+    Optional(&'a Type),
+    Many(&'a Type),
+    Required(&'a Type),
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let ident = &input.ident;
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "ModCommand does not support generic structs",
+        ));
+    }
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(named) => named.named.iter().collect(),
+            Fields::Unit => Vec::new(),
+            Fields::Unnamed(_) => {
+                return Err(syn::Error::new_spanned(
+                    ident,
+                    "ModCommand needs named fields or a unit struct",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "ModCommand can only be derived for structs",
+            ))
+        }
+    };
+
+    let attrs = command_attrs(&input.attrs)?;
+    let (summary, details) = doc_help(&input.attrs);
+    let name = attrs
+        .name
+        .unwrap_or_else(|| kebab(ident.to_string().trim_end_matches("Command")));
+    let help = option_str(attrs.help.or(summary));
+    let long_help = option_str(details);
+    let category = option_str(attrs.category);
+    let aliases = &attrs.aliases;
+    let caps = &attrs.caps;
+    let hidden = attrs.hidden;
+
+    let mut specs = Vec::new();
+    let mut values = Vec::new();
+    for field in fields {
+        let field_ident = field.ident.as_ref().expect("named field");
+        let arg = arg_attrs(&field.attrs)?;
+        let arg_name = arg.name.clone().unwrap_or_else(|| {
+            field_ident
+                .to_string()
+                .trim_start_matches("r#")
+                .replace('_', "-")
+        });
+        let shape = shape(&field.ty, arg.count);
+        if arg.default.is_some() && !matches!(shape, Shape::Required(_)) {
+            return Err(syn::Error::new_spanned(
+                &field.ty,
+                "`default` only applies to fields that are not bool, Option or Vec",
+            ));
+        }
+        if arg.positional && matches!(shape, Shape::Flag | Shape::Count) {
+            return Err(syn::Error::new_spanned(
+                &field.ty,
+                "flags cannot be positional",
+            ));
+        }
+        let help = arg.help.clone().or_else(|| doc_help(&field.attrs).0);
+        specs.push(arg_spec(&arg_name, &arg, &shape, help));
+        let value = match shape {
+            Shape::Flag => quote!(args.flag(#arg_name)),
+            Shape::Count => quote!(::modcli::derive::__count(args, #arg_name)?),
+            Shape::Optional(_) => quote!(::modcli::derive::__optional(args, #arg_name)?),
+            Shape::Many(_) => quote!(::modcli::derive::__many(args, #arg_name)?),
+            Shape::Required(_) => quote!(::modcli::derive::__required(args, #arg_name)?),
+        };
+        values.push(quote!(#field_ident: #value));
+    }
+
+    Ok(quote! {
+        impl ::modcli::derive::ModCommand for #ident {
+            fn name() -> &'static str {
+                #name
+            }
+            fn aliases() -> &'static [&'static str] {
+                &[#(#aliases),*]
+            }
+            fn help() -> ::core::option::Option<&'static str> {
+                #help
+            }
+            fn long_help() -> ::core::option::Option<&'static str> {
+                #long_help
+            }
+            fn category() -> ::core::option::Option<&'static str> {
+                #category
+            }
+            fn hidden() -> bool {
+                #hidden
+            }
+            fn required_caps() -> &'static [&'static str] {
+                &[#(#caps),*]
+            }
+            fn spec() -> ::modcli::spec::CommandSpec {
+                ::modcli::spec::CommandSpec::new() #(.arg(#specs))*
+            }
+            fn from_args(
+                args: &::modcli::spec::ParsedArgs,
+            ) -> ::core::result::Result<Self, ::modcli::error::ModCliError> {
+                let _ = args;
+                ::core::result::Result::Ok(Self { #(#values),* })
+            }
+        }
+    })
+}
+
+fn arg_spec(name: &str, arg: &ArgAttrs, shape: &Shape, help: Option<String>) -> TokenStream2 {
+    let mut spec = match shape {
+        Shape::Flag => quote!(::modcli::spec::ArgSpec::flag(#name)),
+        Shape::Count => quote!(::modcli::spec::ArgSpec::flag(#name).repeatable()),
+        _ if arg.positional => quote!(::modcli::spec::ArgSpec::positional(#name)),
+        _ => quote!(::modcli::spec::ArgSpec::option(#name)),
+    };
+    let value_ty = match shape {
+        Shape::Optional(ty) | Shape::Many(ty) | Shape::Required(ty) => Some(*ty),
+        Shape::Flag | Shape::Count => None,
+    };
+    if let Some(ty) = value_ty.and_then(value_type) {
+        spec.extend(quote!(.value_type(::modcli::spec::ValueType::#ty)));
+    }
+    if let Shape::Many(_) = shape {
+        spec.extend(quote!(.repeatable()));
+    }
+    if let Shape::Required(_) = shape {
+        if arg.default.is_none() {
+            spec.extend(quote!(.required()));
+        }
+    }
+    if let Some(ref long) = arg.long {
+        spec.extend(quote!(.long(#long)));
+    }
+    if let Some(short) = arg.short {
+        spec.extend(quote!(.short(#short)));
+    }
+    if let Some(ref default) = arg.default {
+        spec.extend(quote!(.default(#default)));
+    }
+    if let Some(ref value_name) = arg.value_name {
+        spec.extend(quote!(.value_name(#value_name)));
+    }
+    if !arg.choices.is_empty() {
+        let choices = &arg.choices;
+        spec.extend(quote!(.choices(&[#(#choices),*])));
+    }
+    if let Some(help) = help {
+        spec.extend(quote!(.help(#help)));
+    }
+    spec
+}
+
+fn command_attrs(attrs: &[Attribute]) -> syn::Result<CommandAttrs> {
+    let mut out = CommandAttrs::default();
+    for attr in attrs.iter().filter(|a| a.path().is_ident("command")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                out.name = Some(string(&meta)?);
+            } else if meta.path.is_ident("aliases") {
+                out.aliases = list(&meta)?;
+            } else if meta.path.is_ident("help") {
+                out.help = Some(string(&meta)?);
+            } else if meta.path.is_ident("hidden") {
+                out.hidden = true;
+            } else if meta.path.is_ident("caps") {
+                out.caps = list(&meta)?;
+            } else if meta.path.is_ident("category") {
+                out.category = Some(string(&meta)?);
+            } else {
+                return Err(meta.error("unknown `command` attribute"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(out)
+}
+
+fn arg_attrs(attrs: &[Attribute]) -> syn::Result<ArgAttrs> {
+    let mut out = ArgAttrs::default();
+    for attr in attrs.iter().filter(|a| a.path().is_ident("arg")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                out.name = Some(string(&meta)?);
+            } else if meta.path.is_ident("positional") {
+                out.positional = true;
+            } else if meta.path.is_ident("count") {
+                out.count = true;
+            } else if meta.path.is_ident("short") {
+                out.short = Some(meta.value()?.parse::<LitChar>()?.value());
+            } else if meta.path.is_ident("long") {
+                out.long = Some(string(&meta)?);
+            } else if meta.path.is_ident("help") {
+                out.help = Some(string(&meta)?);
+            } else if meta.path.is_ident("default") {
+                out.default = Some(string(&meta)?);
+            } else if meta.path.is_ident("value_name") {
+                out.value_name = Some(string(&meta)?);
+            } else if meta.path.is_ident("choices") {
+                out.choices = list(&meta)?;
+            } else {
+                return Err(meta.error("unknown `arg` attribute"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(out)
+}
+
+// `key = "value"`
+fn string(meta: &ParseNestedMeta) -> syn::Result<String> {
+    Ok(meta.value()?.parse::<LitStr>()?.value())
+}
+
+// `key("a", "b")`
+fn list(meta: &ParseNestedMeta) -> syn::Result<Vec<String>> {
+    let content;
+    syn::parenthesized!(content in meta.input);
+    let items = Punctuated::<LitStr, Token![,]>::parse_terminated(&content)?;
+    Ok(items.iter().map(LitStr::value).collect())
+}
+
+// First doc paragraph (joined into one line) and the rest, if any.
+fn doc_help(attrs: &[Attribute]) -> (Option<String>, Option<String>) {
+    let lines: Vec<String> = attrs
+        .iter()
+        .filter(|a| a.path().is_ident("doc"))
+        .filter_map(|a| match &a.meta.require_name_value().ok()?.value {
+            Expr::Lit(lit) => match &lit.lit {
+                Lit::Str(s) => Some(s.value().trim().to_string()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+    let split = lines
+        .iter()
+        .position(|l| l.is_empty())
+        .unwrap_or(lines.len());
+    let first = lines[..split].join(" ");
+    let rest = lines[split..].join("\n").trim().to_string();
+    let non_empty = |s: String| (!s.is_empty()).then_some(s);
+    (non_empty(first), non_empty(rest))
+}
+
+fn option_str(value: Option<String>) -> TokenStream2 {
+    match value {
+        Some(s) => quote!(::core::option::Option::Some(#s)),
+        None => quote!(::core::option::Option::None),
+    }
+}
+
+fn shape(ty: &Type, count: bool) -> Shape<'_> {
+    if count {
+        return Shape::Count;
+    }
+    if let Some(inner) = generic_arg(ty, "Option") {
+        return Shape::Optional(inner);
+    }
+    if let Some(inner) = generic_arg(ty, "Vec") {
+        return Shape::Many(inner);
+    }
+    if last_ident(ty).as_deref() == Some("bool") {
+        return Shape::Flag;
+    }
+    Shape::Required(ty)
+}
+
+// `T` in `Wrapper<T>`.
+fn generic_arg<'a>(ty: &'a Type, wrapper: &str) -> Option<&'a Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != wrapper {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        GenericArgument::Type(inner) if args.args.len() == 1 => Some(inner),
+        _ => None,
+    }
+}
+
+fn last_ident(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Path(path) => path.path.segments.last().map(|s| s.ident.to_string()),
+        _ => None,
+    }
+}
+
+// `ValueType` variant checked at parse time; `None` for plain strings.
+fn value_type(ty: &Type) -> Option<TokenStream2> {
+    let variant = match last_ident(ty)?.as_str() {
+        // Only types that fit the parser's `i64` check; wider ones are left
+        // to `FromStr` when the field is read.
+        "i8" | "i16" | "i32" | "i64" | "isize" | "u8" | "u16" | "u32" => "Int",
+        "f32" | "f64" => "Float",
+        "PathBuf" => "Path",
+        _ => return None,
+    };
+    Some(syn::Ident::new(variant, proc_macro2::Span::call_site()).into_token_stream())
+}
+
+// `DeployApp` -> `deploy-app`, `HTTPServer` -> `http-server`
+fn kebab(ident: &str) -> String {
+    let chars: Vec<char> = ident.chars().collect();
+    let mut out = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            // A word starts after a lowercase letter or digit, or at the last
+            // capital of a run that is followed by lowercase (`HTTPServer`).
+            let after_lower = i > 0 && !chars[i - 1].is_uppercase();
+            let ends_run = i > 0
+                && chars[i - 1].is_uppercase()
+                && chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            if after_lower || ends_run {
+                out.push('-');
+            }
+            out.extend(c.to_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
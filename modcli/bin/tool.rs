@@ -21,5 +21,5 @@ fn main() {
     }
 
     // Now safely run the CLI with args
-    cli.run(args);
+    cli.run_and_exit(args);
 }
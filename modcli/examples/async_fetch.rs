@@ -1,38 +1,45 @@
-// Run with: cargo run --example async_fetch --features async,tokio-runtime -- <url>
+// Run with: cargo run --example async_fetch --features tokio-runtime -- fetch <url>
 
-#[cfg(feature = "async")]
+#[cfg(feature = "tokio-runtime")]
 use modcli::command::AsyncCommand;
-#[cfg(feature = "async")]
+#[cfg(feature = "tokio-runtime")]
 use modcli::error::ModCliError;
-#[cfg(feature = "async")]
-use modcli::loader::CommandRegistry;
-#[cfg(feature = "async")]
+#[cfg(feature = "tokio-runtime")]
+use modcli::ModCli;
+#[cfg(feature = "tokio-runtime")]
 use std::future::Future;
-#[cfg(feature = "async")]
+#[cfg(feature = "tokio-runtime")]
 use std::pin::Pin;
-#[cfg(feature = "async")]
+#[cfg(feature = "tokio-runtime")]
 use std::time::Duration;
 
-#[cfg(feature = "async")]
+#[cfg(feature = "tokio-runtime")]
 struct Fetch;
 
-#[cfg(feature = "async")]
+#[cfg(feature = "tokio-runtime")]
 impl AsyncCommand for Fetch {
     fn name(&self) -> &str {
         "fetch"
     }
+    fn help(&self) -> Option<&str> {
+        Some("Fetch a URL (simulated)")
+    }
+    fn timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(5))
+    }
     fn execute_async<'a>(
         &'a self,
         args: &'a [String],
     ) -> Pin<Box<dyn Future<Output = Result<(), ModCliError>> + Send + 'a>> {
         Box::pin(async move {
             let url = args
-                .get(0)
+                .first()
                 .cloned()
                 .unwrap_or_else(|| "https://example.com".to_string());
 
             println!("Starting fetch: {url}");
             // Simulate async network work; replace with reqwest or your client of choice.
+            // Ctrl-C cancels the command; the 5s timeout applies as well.
             tokio::time::sleep(Duration::from_millis(500)).await;
             println!("Done: {url}");
             Ok(())
@@ -40,23 +47,17 @@ impl AsyncCommand for Fetch {
     }
 }
 
-#[cfg(all(feature = "async", feature = "tokio-runtime"))]
-#[tokio::main]
-async fn main() {
-    let mut reg = CommandRegistry::new();
-    reg.register_async(Box::new(Fetch));
+#[cfg(feature = "tokio-runtime")]
+fn main() -> std::process::ExitCode {
+    let mut cli = ModCli::new();
+    cli.registry.register_async(Box::new(Fetch));
 
-    // Invoke: cargo run --example async_fetch --features async,tokio-runtime -- <url>
+    // Invoke: cargo run --example async_fetch --features tokio-runtime -- fetch <url>
     let args: Vec<String> = std::env::args().skip(1).collect();
-    let cmd = "fetch";
-    if args.is_empty() {
-        reg.execute_async(cmd, &[]).await;
-    } else {
-        reg.execute_async(cmd, &args).await;
-    }
+    cli.run_blocking(args).into()
 }
 
-#[cfg(not(all(feature = "async", feature = "tokio-runtime")))]
+#[cfg(not(feature = "tokio-runtime"))]
 fn main() {
-    eprintln!("This example requires `--features async,tokio-runtime`");
+    eprintln!("This example requires `--features tokio-runtime`");
 }
@@ -1,21 +1,8 @@
 use modcli::command::Command;
+use modcli::tree::Group;
 use modcli::ModCli;
 use std::env;
 
-// Top-level group marker: "ops". Typically a no-op; serves as a namespace.
-struct OpsGroup;
-impl Command for OpsGroup {
-    fn name(&self) -> &str {
-        "ops"
-    }
-    fn help(&self) -> Option<&str> {
-        Some("Parent group; see `help ops`")
-    }
-    fn execute(&self, _args: &[String]) {
-        println!("Use: help ops  |  ops <child> ...");
-    }
-}
-
 // Child requiring admin role: "ops:restart"
 struct Restart;
 impl Command for Restart {
@@ -65,8 +52,9 @@ fn main() {
         cli.registry.set_caps(list);
     }
 
-    // Register group and children
-    cli.registry.register(Box::new(OpsGroup));
+    // Register group metadata and children; `ops` alone lists visible children
+    cli.registry
+        .register_group(Group::new("ops").help("Operations commands; see `help ops`"));
     cli.registry.register(Box::new(Restart));
     cli.registry.register(Box::new(Private));
 
@@ -1,30 +1,40 @@
+use modcli::man::ManOptions;
+use modcli::ModCli;
 use std::env;
-use std::time::{SystemTime, UNIX_EPOCH};
 
+// Generates documentation from the registry. Register your own commands (and
+// grant capabilities) before generating so the pages match your tool.
+//
+//   gen_man                    main man page (section 1)
+//   gen_man --command <name>   page for a single command
+//   gen_man --markdown         Markdown command reference
 fn main() {
     let name = env::var("CARGO_PKG_NAME").unwrap_or_else(|_| "modcli".into());
     let version = env::var("CARGO_PKG_VERSION").unwrap_or_else(|_| "0.0.0".into());
     let about = env::var("CARGO_PKG_DESCRIPTION").unwrap_or_else(|_| "Modern CLI framework".into());
-    let now = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_secs();
 
-    // Minimal groff man page (section 1)
-    println!(".TH {} 1 {} \\\"{}\\\"", name.to_uppercase(), now, version);
-    println!(".SH NAME");
-    println!("{name} \\− {about}");
-    println!(".SH SYNOPSIS");
-    println!(".B {name}");
-    println!("[\\-\\-version] [\\-\\-modcli] <command> [args...]\\n");
-    println!(".SH DESCRIPTION");
-    println!("{name} is a high\\-performance CLI framework.");
-    println!("Commands are provided by your application via a registry.");
-    println!(".SH OPTIONS");
-    println!(".TP");
-    println!("\\fB\\-\\-version\\fR  Print framework version");
-    println!(".TP");
-    println!("\\fB\\-\\-modcli\\fR  Show framework info");
-    println!(".SH SEE ALSO");
-    println!("Project: https://github.com/jamesgober/mod-cli");
+    let cli = ModCli::new();
+    let opts = ManOptions::new(&name)
+        .version(&version)
+        .about(&about)
+        .description("Commands are provided by your application via a registry.");
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        None => print!("{}", cli.registry.render_man(&opts)),
+        Some("--markdown") => print!("{}", cli.registry.render_markdown_reference()),
+        Some("--command") if args.len() == 2 => {
+            match cli.registry.render_man_command(&args[1], &opts) {
+                Some(page) => print!("{page}"),
+                None => {
+                    eprintln!("unknown command: {}", args[1]);
+                    std::process::exit(1);
+                }
+            }
+        }
+        _ => {
+            eprintln!("usage: gen_man [--markdown | --command <name>]");
+            std::process::exit(2);
+        }
+    }
 }
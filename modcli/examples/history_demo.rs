@@ -6,7 +6,7 @@ fn main() {
     let args: Vec<String> = std::env::args().skip(1).collect();
     let custom_path = args
         .iter()
-        .find_map(|a| a.strip_prefix("--history=").map(|s| PathBuf::from(s)));
+        .find_map(|a| a.strip_prefix("--history=").map(PathBuf::from));
 
     // Load history (empty if none)
     let mut entries = history::load(custom_path.as_deref());
@@ -5,13 +5,14 @@ use modcli::output::progress::{ProgressBar, ProgressStyle};
 fn main() {
     print::line("Custom ProgressStyle with label and color:");
 
-    let mut style = ProgressStyle::default();
-    style.fill = '█';
-    style.start_cap = '⟦';
-    style.end_cap = '⟧';
-    style.done_label = "All set!";
-    style.show_percent = true;
-    style.color = Some(Color::Green);
+    let style = ProgressStyle {
+        fill: '█',
+        start_cap: '⟦',
+        end_cap: '⟧',
+        done_label: "All set!",
+        show_percent: true,
+        color: Some(Color::Green),
+    };
 
     let mut bar = ProgressBar::new(40, style);
     bar.set_label("Uploading");
@@ -69,12 +69,10 @@ fn main() {
                 KeyCode::BackTab => {
                     focus = if focus == 0 { 3 } else { focus - 1 };
                 }
-                KeyCode::Enter => {
-                    if focus == 3 {
-                        break;
-                    }
-                    // noop per-field (we edit live)
+                KeyCode::Enter if focus == 3 => {
+                    break;
                 }
+                // noop per-field (we edit live)
                 KeyCode::Char(c) => {
                     match focus {
                         0 => {
@@ -90,28 +88,20 @@ fn main() {
                                 threads = (threads - 1).max(1);
                             }
                         }
-                        2 => {
-                            if c == ' ' {
-                                enable_cache = !enable_cache;
-                            }
+                        2 if c == ' ' => {
+                            enable_cache = !enable_cache;
                         }
                         _ => {}
                     }
                 }
-                KeyCode::Backspace => {
-                    if focus == 0 {
-                        username.pop();
-                    }
+                KeyCode::Backspace if focus == 0 => {
+                    username.pop();
                 }
-                KeyCode::Left => {
-                    if focus == 1 {
-                        threads = (threads - 1).max(1);
-                    }
+                KeyCode::Left if focus == 1 => {
+                    threads = (threads - 1).max(1);
                 }
-                KeyCode::Right => {
-                    if focus == 1 {
-                        threads = (threads + 1).min(512);
-                    }
+                KeyCode::Right if focus == 1 => {
+                    threads = (threads + 1).min(512);
                 }
                 // '+' and '-' handled in KeyCode::Char above
                 _ => {}
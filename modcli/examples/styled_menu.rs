@@ -55,10 +55,8 @@ fn main() {
                 KeyCode::Up => {
                     cursor_idx = cursor_idx.saturating_sub(1);
                 }
-                KeyCode::Down => {
-                    if cursor_idx + 1 < items.len() {
-                        cursor_idx += 1;
-                    }
+                KeyCode::Down if cursor_idx + 1 < items.len() => {
+                    cursor_idx += 1;
                 }
                 KeyCode::PageUp => {
                     if cursor_idx >= page_size {
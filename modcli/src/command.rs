@@ -1,4 +1,7 @@
+use crate::context::Context;
+use crate::deprecation::Deprecation;
 use crate::error::ModCliError;
+use crate::exit::ExitCode;
 /// Defines the trait for commands to implement.
 ///
 /// # Example
@@ -21,8 +24,11 @@ use crate::error::ModCliError;
 /// cli.run(args);
 /// ```
 use crate::loader::CommandRegistry;
+use crate::spec::{CommandSpec, ParsedArgs};
 
-pub trait Command {
+/// `Send + Sync` so the registry can hand commands to other threads
+/// (`ModCli::run_async` runs sync commands on tokio's blocking pool).
+pub trait Command: Send + Sync {
     fn name(&self) -> &str;
 
     fn aliases(&self) -> &[&str] {
@@ -33,6 +39,36 @@ pub trait Command {
         None
     }
 
+    /// Longer description (Markdown) shown on the command's help page.
+    fn long_help(&self) -> Option<&str> {
+        None
+    }
+
+    /// Usage line for the help page; defaults to one derived from `spec()`.
+    fn usage(&self) -> Option<&str> {
+        None
+    }
+
+    /// Example invocations with a short description each.
+    fn examples(&self) -> &[(&str, &str)] {
+        &[]
+    }
+
+    /// Related commands listed under SEE ALSO.
+    fn see_also(&self) -> &[&str] {
+        &[]
+    }
+
+    /// Heading this command is listed under in help (e.g. "Deployment").
+    fn category(&self) -> Option<&str> {
+        None
+    }
+
+    /// Marks the command deprecated: the first run warns, help annotates it.
+    fn deprecated(&self) -> Option<Deprecation> {
+        None
+    }
+
     fn hidden(&self) -> bool {
         false
     }
@@ -44,10 +80,24 @@ pub trait Command {
         &[]
     }
 
+    /// Optional declarative argument schema. When present, the registry parses
+    /// and validates arguments against it before `validate()` and hands the
+    /// result to `execute_parsed()`. Default: no schema.
+    fn spec(&self) -> Option<CommandSpec> {
+        None
+    }
+
     fn validate(&self, _args: &[String]) -> Result<(), ModCliError> {
         Ok(())
     }
 
+    /// Flags that print this command's help instead of running it
+    /// (`deploy --help`). Return `&[]` to receive them as arguments; flags
+    /// declared in `spec()` are never intercepted.
+    fn help_flags(&self) -> &[&str] {
+        &["--help", "-h"]
+    }
+
     fn execute(&self, args: &[String]);
 
     /// Execute with access to the registry context. Default delegates to `execute`.
@@ -55,17 +105,169 @@ pub trait Command {
     fn execute_with(&self, args: &[String], _registry: &CommandRegistry) {
         self.execute(args)
     }
+
+    /// Execute with arguments parsed against `spec()`. Default delegates to
+    /// `execute_with` using the raw arguments.
+    fn execute_parsed(&self, parsed: &ParsedArgs, registry: &CommandRegistry) {
+        self.execute_with(parsed.raw(), registry)
+    }
+
+    /// Fallible entry point used by the registry, with the invocation `Context`
+    /// (path, parsed args, capabilities, output, app state, cancellation).
+    /// Return an error or a non-zero `ExitCode` to signal failure.
+    /// Default runs `execute_parsed` and succeeds.
+    fn run(&self, ctx: &Context) -> Result<ExitCode, ModCliError> {
+        self.execute_parsed(ctx.args(), ctx.registry());
+        Ok(ExitCode::SUCCESS)
+    }
+
+    /// The async command behind a `register_async` entry (used by the registry).
+    #[cfg(feature = "async")]
+    #[doc(hidden)]
+    fn as_async(&self) -> Option<&dyn AsyncCommand> {
+        None
+    }
 }
 
 // Optional async commands (object-safe via boxed future)
+/// Async counterpart of `Command` (feature: `async`).
+///
+/// Async commands share the registry with sync commands: they are routed,
+/// listed in help, filtered by visibility and checked by the authorization
+/// policy the same way. Run them with `CommandRegistry::execute_async`.
 #[cfg(feature = "async")]
 pub trait AsyncCommand: Send + Sync {
     fn name(&self) -> &str;
     fn aliases(&self) -> &[&str] {
         &[]
     }
+    fn help(&self) -> Option<&str> {
+        None
+    }
+    /// Help page content; see the `Command` methods of the same names.
+    fn long_help(&self) -> Option<&str> {
+        None
+    }
+    fn usage(&self) -> Option<&str> {
+        None
+    }
+    fn examples(&self) -> &[(&str, &str)] {
+        &[]
+    }
+    fn see_also(&self) -> &[&str] {
+        &[]
+    }
+    fn category(&self) -> Option<&str> {
+        None
+    }
+    fn deprecated(&self) -> Option<Deprecation> {
+        None
+    }
+    fn hidden(&self) -> bool {
+        false
+    }
+    /// Capability requirements for visibility/authorization (see `Command::required_caps`).
+    fn required_caps(&self) -> &[&str] {
+        &[]
+    }
+    /// Optional argument schema; arguments are checked against it before
+    /// `validate()` and the result is in `run_async`'s `Context`.
+    /// `execute_async` still receives the raw arguments.
+    fn spec(&self) -> Option<CommandSpec> {
+        None
+    }
+    fn validate(&self, _args: &[String]) -> Result<(), ModCliError> {
+        Ok(())
+    }
+    /// Flags that print this command's help (see `Command::help_flags`).
+    fn help_flags(&self) -> &[&str] {
+        &["--help", "-h"]
+    }
+    /// Maximum run time; exceeding it drops the command's future and returns
+    /// `ModCliError::Timeout` (enforced with the `tokio-runtime` feature).
+    /// Sync commands have no equivalent, since their thread can't be stopped.
+    fn timeout(&self) -> Option<std::time::Duration> {
+        None
+    }
     fn execute_async<'a>(
         &'a self,
         args: &'a [String],
     ) -> core::pin::Pin<Box<dyn core::future::Future<Output = Result<(), ModCliError>> + Send + 'a>>;
+
+    /// Entry point used by the registry, with the invocation `Context`; the
+    /// async counterpart of `Command::run`. Default awaits `execute_async`
+    /// with the raw arguments and succeeds when it returns `Ok`.
+    fn run_async<'a>(&'a self, ctx: &'a Context<'_>) -> CommandFuture<'a> {
+        Box::pin(async move {
+            self.execute_async(ctx.args().raw())
+                .await
+                .map(|_| ExitCode::SUCCESS)
+        })
+    }
+}
+
+/// Future returned by `AsyncCommand::run_async`.
+#[cfg(feature = "async")]
+pub type CommandFuture<'a> = core::pin::Pin<
+    Box<dyn core::future::Future<Output = Result<ExitCode, ModCliError>> + Send + 'a>,
+>;
+
+// Presents an async command as a `Command` so it lives in the same registry.
+#[cfg(feature = "async")]
+pub(crate) struct AsyncEntry(pub(crate) Box<dyn AsyncCommand>);
+
+#[cfg(feature = "async")]
+impl Command for AsyncEntry {
+    fn name(&self) -> &str {
+        self.0.name()
+    }
+    fn aliases(&self) -> &[&str] {
+        self.0.aliases()
+    }
+    fn help(&self) -> Option<&str> {
+        self.0.help()
+    }
+    fn long_help(&self) -> Option<&str> {
+        self.0.long_help()
+    }
+    fn usage(&self) -> Option<&str> {
+        self.0.usage()
+    }
+    fn examples(&self) -> &[(&str, &str)] {
+        self.0.examples()
+    }
+    fn see_also(&self) -> &[&str] {
+        self.0.see_also()
+    }
+    fn category(&self) -> Option<&str> {
+        self.0.category()
+    }
+    fn deprecated(&self) -> Option<Deprecation> {
+        self.0.deprecated()
+    }
+    fn hidden(&self) -> bool {
+        self.0.hidden()
+    }
+    fn required_caps(&self) -> &[&str] {
+        self.0.required_caps()
+    }
+    fn spec(&self) -> Option<CommandSpec> {
+        self.0.spec()
+    }
+    fn validate(&self, args: &[String]) -> Result<(), ModCliError> {
+        self.0.validate(args)
+    }
+    fn help_flags(&self) -> &[&str] {
+        self.0.help_flags()
+    }
+    fn execute(&self, _args: &[String]) {}
+    fn run(&self, ctx: &Context) -> Result<ExitCode, ModCliError> {
+        Err(ModCliError::InvalidUsage(format!(
+            "`{}` is an async command; run it with execute_async",
+            ctx.path().replace(':', " ")
+        )))
+    }
+    fn as_async(&self) -> Option<&dyn AsyncCommand> {
+        Some(self.0.as_ref())
+    }
 }
@@ -1,5 +1,6 @@
 use crate::command::Command;
 use crate::error::ModCliError;
+use crate::output::print;
 
 pub struct HelloCommand;
 
@@ -24,9 +25,9 @@ impl Command for HelloCommand {
 
     fn execute(&self, args: &[String]) {
         if let Some(name) = args.first() {
-            println!("Hello, {name}!");
+            print::line(&format!("Hello, {name}!"));
         } else {
-            println!("Hello!");
+            print::line("Hello!");
         }
     }
 }
@@ -1,9 +1,8 @@
 use crate::command::Command;
+use crate::context::Context;
 use crate::error::ModCliError;
-use crate::loader::CommandRegistry;
-use crate::output::hook;
-use crate::output::markdown;
-use crate::output::messages;
+use crate::exit::ExitCode;
+use crate::help::HelpRenderer;
 
 /// Built-in help command (execution handled by registry internally)
 pub struct HelpCommand;
@@ -33,76 +32,39 @@ impl Command for HelpCommand {
         Some("Displays help information")
     }
 
-    fn validate(&self, args: &[String]) -> Result<(), ModCliError> {
-        if args.len() > 1 {
-            Err(ModCliError::InvalidUsage(
-                "Too many arguments. Usage: help [command]".into(),
-            ))
-        } else {
-            Ok(())
-        }
-    }
-
     fn execute(&self, _args: &[String]) {}
 
-    fn execute_with(&self, args: &[String], registry: &CommandRegistry) {
-        // validate() already ensures args.len() <= 1
-        if args.len() == 1 {
-            let query = &args[0];
-            // If a direct command matches and is visible, show its help
-            if let Some(target) = registry.get(query) {
-                if registry.is_visible(target) {
-                    let name_line = target.name().to_string();
-                    println!("{name_line}");
-                    let body = target.help().unwrap_or("No description.");
-                    let rendered = markdown::render_markdown(body);
-                    print!("{rendered}");
-                } else {
-                    println!("No help available for '{query}'");
-                }
-                return;
-            }
-
-            // Namespace help: list children of `query:` that are visible
-            let ns = format!("{query}:");
-            let mut any = false;
-            let ns_header_fallback = format!("Help ({query}):");
-            let header = messages::message_or_default("help.ns_header", &ns_header_fallback);
-            println!("{header}");
-            for command in registry.all() {
-                let name = command.name();
-                if name.starts_with(&ns) && registry.is_visible(command.as_ref()) {
-                    println!(
-                        "  {:<20} {}",
-                        name,
-                        markdown::render_markdown(command.help().unwrap_or("No description"))
-                    );
-                    any = true;
-                }
-            }
-            if !any {
-                let unknown =
-                    format!("[{query}]. Type `help` or `--help` for a list of available commands.");
-                hook::unknown(&unknown);
-            }
-            return;
+    fn run(&self, ctx: &Context) -> Result<ExitCode, ModCliError> {
+        let registry = ctx.registry();
+        let args = ctx.args().raw();
+        let renderer = HelpRenderer::new();
+        // Top level: every visible node, then global options
+        if args.is_empty() {
+            ctx.out().write(&renderer.listing(registry, ""));
+            return Ok(ExitCode::SUCCESS);
         }
 
-        let header = messages::message_or_default("help.header", "Help:");
-        println!("{header}");
-        for command in registry.all() {
-            let name = command.name();
-            let top_level = !name.contains(':');
-            if top_level && registry.is_visible(command.as_ref()) {
-                println!(
-                    "  {:<12} {}",
-                    name,
-                    markdown::render_markdown(command.help().unwrap_or("No description"))
-                );
-            }
-        }
-        if let Some(footer) = messages::get_message("help.footer") {
-            println!("{footer}");
+        // `help a b c` shows help for the node at path `a:b:c`
+        let query = args.join(" ");
+        let resolved = registry.resolve(args).filter(|(_, n)| *n == args.len());
+        let Some((path, _)) = resolved else {
+            return Err(ModCliError::UnknownCommand {
+                name: query,
+                suggestions: registry.suggest(args),
+            });
+        };
+        let page = if registry.get(&path).is_some() {
+            renderer.command(registry, &path)
+        } else if registry.is_group_visible(&path) {
+            // Group node: list its visible children
+            Some(renderer.listing(registry, &path))
+        } else {
+            None
+        };
+        match page {
+            Some(page) => ctx.out().write(&page),
+            None => ctx.out().line(&format!("No help available for '{query}'")),
         }
+        Ok(ExitCode::SUCCESS)
     }
 }
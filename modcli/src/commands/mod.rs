@@ -2,8 +2,10 @@ pub mod framework;
 pub mod hello;
 pub mod help;
 pub mod ping;
+pub mod shell;
 
 pub use framework::FrameworkCommand;
 pub use hello::HelloCommand;
 pub use help::HelpCommand;
 pub use ping::PingCommand;
+pub use shell::ShellCommand;
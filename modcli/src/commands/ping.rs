@@ -1,5 +1,6 @@
 use crate::command::Command;
 use crate::error::ModCliError;
+use crate::output::print;
 
 pub struct PingCommand;
 
@@ -23,6 +24,6 @@ impl Command for PingCommand {
     }
 
     fn execute(&self, _args: &[String]) {
-        println!("Pong!");
+        print::line("Pong!");
     }
 }
@@ -1,12 +1,45 @@
 use crate::command::Command;
-use crate::config::CliConfig;
-use crate::console::run_shell;
 use crate::error::ModCliError;
+use crate::loader::CommandRegistry;
+use crate::output::hook;
+use crate::shell::Shell;
 
-pub struct ShellCommand;
+/// Built-in `shell` command that starts an interactive session over the registry.
+///
+/// Register your own instance to customize the prompt and messages:
+/// ```no_run
+/// use modcli::commands::ShellCommand;
+/// use modcli::shell::Shell;
+/// use modcli::ModCli;
+///
+/// let mut cli = ModCli::new();
+/// cli.registry
+///     .register(Box::new(ShellCommand::with_shell(Shell::new().prompt("tool> "))));
+/// ```
+pub struct ShellCommand {
+    shell: Shell,
+}
+
+impl Default for ShellCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ShellCommand {
+    pub fn new() -> Self {
+        Self {
+            shell: Shell::new(),
+        }
+    }
+
+    pub fn with_shell(shell: Shell) -> Self {
+        Self { shell }
+    }
+}
 
 impl Command for ShellCommand {
-    fn name(&self) -> &'static str {
+    fn name(&self) -> &str {
         "shell"
     }
 
@@ -16,14 +49,19 @@ impl Command for ShellCommand {
 
     fn validate(&self, args: &[String]) -> Result<(), ModCliError> {
         if !args.is_empty() {
-            Err(ModCliError::InvalidUsage("Shell does not accept any arguments.".into()))
+            Err(ModCliError::InvalidUsage(
+                "Shell does not accept any arguments.".into(),
+            ))
         } else {
             Ok(())
         }
     }
 
-    fn execute(&self, _args: &[String]) {
-        let config = CliConfig::load(None);
-        let _ = run_shell(config);
+    fn execute(&self, _args: &[String]) {}
+
+    fn execute_with(&self, _args: &[String], registry: &CommandRegistry) {
+        if let Err(e) = self.shell.run(registry) {
+            hook::error(&format!("shell error: {e}"));
+        }
     }
 }
@@ -0,0 +1,381 @@
+//! Shell completion script generation from the command registry.
+//!
+//! Scripts are generated from what the registry actually contains: visible
+//! command names, nested `a:b:c` paths (completed as words, `a b c`), group and
+//! command aliases at their own level and, when a command declares a `spec()`,
+//! its flags.
+//! Hidden and capability-gated commands are filtered through
+//! `CommandRegistry::is_visible`, so grant capabilities before generating.
+//!
+//! # Example
+//! ```no_run
+//! use modcli::completions::Shell;
+//! use modcli::ModCli;
+//!
+//! let cli = ModCli::new();
+//! print!("{}", cli.registry.generate_completions(Shell::Bash, "tool"));
+//! ```
+
+use crate::error::ModCliError;
+use crate::help::summary;
+use crate::loader::CommandRegistry;
+use crate::spec::ArgKind;
+use crate::tree::{alias_path, display_path};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// Target shell for completion scripts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+    PowerShell,
+}
+
+impl Shell {
+    /// All supported shells.
+    pub fn all() -> [Shell; 4] {
+        [Shell::Bash, Shell::Zsh, Shell::Fish, Shell::PowerShell]
+    }
+
+    /// Conventional file name for the generated script (e.g. `_tool` for zsh).
+    pub fn file_name(&self, bin_name: &str) -> String {
+        match self {
+            Shell::Bash => format!("{bin_name}.bash"),
+            Shell::Zsh => format!("_{bin_name}"),
+            Shell::Fish => format!("{bin_name}.fish"),
+            Shell::PowerShell => format!("_{bin_name}.ps1"),
+        }
+    }
+}
+
+impl FromStr for Shell {
+    type Err = ModCliError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "bash" => Ok(Shell::Bash),
+            "zsh" => Ok(Shell::Zsh),
+            "fish" => Ok(Shell::Fish),
+            "powershell" | "pwsh" => Ok(Shell::PowerShell),
+            other => Err(ModCliError::InvalidUsage(format!(
+                "unsupported shell: {other} (expected bash, zsh, fish or powershell)"
+            ))),
+        }
+    }
+}
+
+/// Completion candidates keyed by the space-separated command path typed so far.
+/// The empty path holds the top-level words.
+type Tree = BTreeMap<String, BTreeMap<String, String>>;
+
+impl CommandRegistry {
+    /// Generate a completion script for `shell`, registered for `bin_name`.
+    pub fn generate_completions(&self, shell: Shell, bin_name: &str) -> String {
+        let tree = self.completion_tree();
+        let func = bin_name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect::<String>();
+        match shell {
+            Shell::Bash => bash(&tree, bin_name, &func),
+            Shell::Zsh => zsh(&tree, bin_name, &func),
+            Shell::Fish => fish(&tree, bin_name, &func),
+            Shell::PowerShell => powershell(&tree, bin_name),
+        }
+    }
+
+    fn completion_tree(&self) -> Tree {
+        let mut tree: Tree = BTreeMap::new();
+        tree.entry(String::new()).or_default();
+        // (alias path, target path), both space-separated
+        let mut aliases: Vec<(String, String)> = Vec::new();
+
+        for cmd in self.all() {
+            if !self.is_visible(cmd.as_ref()) {
+                continue;
+            }
+            let about = summary(cmd.help()).unwrap_or_default();
+            let segments: Vec<&str> = cmd.name().split(':').collect();
+
+            // Register each segment under its parent path; groups get their own help text.
+            let mut parent = String::new();
+            for (i, seg) in segments.iter().enumerate() {
+                let leaf = i + 1 == segments.len();
+                let group_about = if leaf {
+                    None
+                } else {
+                    let path = segments[..=i].join(":");
+                    self.group(&path).and_then(|g| summary(g.help.as_deref()))
+                };
+                let words = tree.entry(parent.clone()).or_default();
+                let desc = words.entry(seg.to_string()).or_default();
+                if leaf {
+                    *desc = about.clone();
+                } else if let Some(g) = group_about {
+                    *desc = g;
+                }
+                if !parent.is_empty() {
+                    parent.push(' ');
+                }
+                parent.push_str(seg);
+            }
+
+            let mut flags: BTreeMap<String, String> = BTreeMap::new();
+            if let Some(spec) = cmd.spec() {
+                for arg in spec.args().iter().filter(|a| a.kind != ArgKind::Positional) {
+                    let help = arg.help.clone().unwrap_or_default();
+                    if let Some(ref l) = arg.long {
+                        flags.insert(format!("--{l}"), help.clone());
+                    }
+                    if let Some(s) = arg.short {
+                        flags.insert(format!("-{s}"), help);
+                    }
+                }
+            }
+            tree.entry(parent.clone()).or_default().extend(flags);
+
+            for alias in cmd.aliases() {
+                if !alias.starts_with('-') {
+                    aliases.push((display_path(&alias_path(cmd.name(), alias)), parent.clone()));
+                }
+            }
+        }
+        for group in self.groups().filter(|g| self.is_group_visible(&g.name)) {
+            for alias in &group.aliases {
+                aliases.push((
+                    display_path(&alias_path(&group.name, alias)),
+                    display_path(&group.name),
+                ));
+            }
+        }
+
+        // Aliases complete at their own level and carry the target's subtree
+        for (alias, target) in aliases {
+            let (parent, seg) = alias.rsplit_once(' ').unwrap_or(("", &alias));
+            let (tparent, tseg) = target.rsplit_once(' ').unwrap_or(("", &target));
+            let desc = tree
+                .get(tparent)
+                .and_then(|w| w.get(tseg))
+                .cloned()
+                .unwrap_or_default();
+            tree.entry(parent.to_string())
+                .or_default()
+                .insert(seg.to_string(), desc);
+            let copies: Vec<(String, BTreeMap<String, String>)> = tree
+                .iter()
+                .filter_map(|(path, words)| {
+                    let rest = if *path == target {
+                        Some("")
+                    } else {
+                        path.strip_prefix(&target).filter(|r| r.starts_with(' '))
+                    }?;
+                    Some((format!("{alias}{rest}"), words.clone()))
+                })
+                .collect();
+            for (path, words) in copies {
+                tree.entry(path).or_default().extend(words);
+            }
+        }
+
+        // `help <command>` completes top-level names
+        if tree[""].contains_key("help") {
+            let top: BTreeMap<String, String> = tree[""]
+                .iter()
+                .filter(|(k, _)| k.as_str() != "help")
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect();
+            tree.entry("help".into()).or_default().extend(top);
+        }
+        tree
+    }
+}
+
+// First non-empty line of the help text, with simple Markdown markers removed.
+fn sh_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+fn bash(tree: &Tree, bin: &str, func: &str) -> String {
+    let mut out = String::new();
+    out.push_str(&format!("# bash completion for {bin}\n"));
+    out.push_str(&format!("_{func}_words() {{\n    case \"$1\" in\n"));
+    for (path, words) in tree {
+        let list: Vec<&str> = words.keys().map(|s| s.as_str()).collect();
+        out.push_str(&format!(
+            "        {}) echo {} ;;\n",
+            sh_quote(path),
+            sh_quote(&list.join(" "))
+        ));
+    }
+    out.push_str("        *) return 1 ;;\n    esac\n}\n\n");
+    out.push_str(&format!(
+        r#"_{func}() {{
+    local cur="${{COMP_WORDS[COMP_CWORD]}}" cmdpath="" next w i
+    for ((i = 1; i < COMP_CWORD; i++)); do
+        w="${{COMP_WORDS[i]}}"
+        [[ "$w" == -* ]] && continue
+        next="${{cmdpath:+$cmdpath }}$w"
+        _{func}_words "$next" >/dev/null || break
+        cmdpath="$next"
+    done
+    COMPREPLY=( $(compgen -W "$(_{func}_words "$cmdpath")" -- "$cur") )
+}}
+complete -F _{func} {bin}
+"#
+    ));
+    out
+}
+
+fn zsh(tree: &Tree, bin: &str, func: &str) -> String {
+    let mut out = String::new();
+    out.push_str(&format!("#compdef {bin}\n# zsh completion for {bin}\n\n"));
+    out.push_str(&format!("_{func}_items() {{\n    case \"$1\" in\n"));
+    for (path, words) in tree {
+        let items: Vec<String> = words
+            .iter()
+            .map(|(w, d)| {
+                let w = w.replace(':', r"\:");
+                if d.is_empty() {
+                    sh_quote(&w)
+                } else {
+                    sh_quote(&format!("{w}:{d}"))
+                }
+            })
+            .collect();
+        out.push_str(&format!(
+            "        {}) reply=({}) ;;\n",
+            sh_quote(path),
+            items.join(" ")
+        ));
+    }
+    out.push_str("        *) return 1 ;;\n    esac\n}\n\n");
+    out.push_str(&format!(
+        r#"_{func}() {{
+    local cmdpath="" next w i
+    local -a reply
+    for ((i = 2; i < CURRENT; i++)); do
+        w="${{words[i]}}"
+        [[ "$w" == -* ]] && continue
+        next="${{cmdpath:+$cmdpath }}$w"
+        _{func}_items "$next" || break
+        cmdpath="$next"
+    done
+    _{func}_items "$cmdpath"
+    _describe -t commands '{bin} command' reply
+}}
+
+if [ "$funcstack[1]" = "_{func}" ]; then
+    _{func} "$@"
+else
+    compdef _{func} {bin}
+fi
+"#
+    ));
+    out
+}
+
+fn fish(tree: &Tree, bin: &str, func: &str) -> String {
+    let mut out = String::new();
+    let known: Vec<String> = tree
+        .keys()
+        .filter(|k| !k.is_empty())
+        .map(|k| fish_quote(k))
+        .collect();
+    out.push_str(&format!(
+        r#"# fish completion for {bin}
+function __{func}_path
+    set -l known {known}
+    set -l cmdpath ''
+    for t in (commandline -opc)[2..-1]
+        string match -q -- '-*' $t; and continue
+        set -l next (string trim -- "$cmdpath $t")
+        contains -- $next $known; or break
+        set cmdpath $next
+    end
+    echo $cmdpath
+end
+
+function __{func}_at
+    test (__{func}_path) = "$argv"
+end
+
+complete -c {bin} -f
+"#,
+        known = known.join(" ")
+    ));
+    for (path, words) in tree {
+        let cond = fish_quote(format!("__{func}_at {path}").trim_end());
+        for (w, d) in words {
+            let mut line = if let Some(long) = w.strip_prefix("--") {
+                format!("complete -c {bin} -n {cond} -l {long}")
+            } else if let Some(short) = w.strip_prefix('-') {
+                format!("complete -c {bin} -n {cond} -s {short}")
+            } else {
+                format!("complete -c {bin} -n {cond} -a {}", fish_quote(w))
+            };
+            if !d.is_empty() {
+                line.push_str(&format!(" -d {}", fish_quote(d)));
+            }
+            out.push_str(&line);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+fn fish_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\\', r"\\").replace('\'', r"\'"))
+}
+
+fn ps_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "''"))
+}
+
+fn powershell(tree: &Tree, bin: &str) -> String {
+    let mut out = String::new();
+    out.push_str(&format!("# PowerShell completion for {bin}\n"));
+    out.push_str(&format!(
+        "Register-ArgumentCompleter -Native -CommandName {} -ScriptBlock {{\n",
+        ps_quote(bin)
+    ));
+    out.push_str("    param($wordToComplete, $commandAst, $cursorPosition)\n");
+    out.push_str("    $items = @{\n");
+    for (path, words) in tree {
+        let list: Vec<String> = words
+            .iter()
+            .map(|(w, d)| {
+                let tip = if d.is_empty() { w } else { d };
+                format!(
+                    "[pscustomobject]@{{ n = {}; d = {} }}",
+                    ps_quote(w),
+                    ps_quote(tip)
+                )
+            })
+            .collect();
+        out.push_str(&format!(
+            "        {} = @({})\n",
+            ps_quote(path),
+            list.join(", ")
+        ));
+    }
+    out.push_str("    }\n");
+    out.push_str(
+        r#"    $path = ''
+    foreach ($el in $commandAst.CommandElements | Select-Object -Skip 1) {
+        if ($el.Extent.EndOffset -ge $cursorPosition) { break }
+        $w = $el.ToString()
+        if ($w.StartsWith('-')) { continue }
+        $next = ($path + ' ' + $w).Trim()
+        if (-not $items.ContainsKey($next)) { break }
+        $path = $next
+    }
+    $items[$path] | Where-Object { $_.n -like "$wordToComplete*" } | ForEach-Object {
+        [System.Management.Automation.CompletionResult]::new($_.n, $_.n, 'ParameterValue', $_.d)
+    }
+}
+"#,
+    );
+    out
+}
@@ -0,0 +1,336 @@
+//! Layered configuration loading (feature `config`).
+//!
+//! `ConfigLoader` merges, from lowest to highest precedence:
+//!
+//! 1. defaults (`defaults(&value)`),
+//! 2. config files found in the XDG config directories
+//!    (`$XDG_CONFIG_DIRS/<app>/config.{toml,json}`, then
+//!    `$XDG_CONFIG_HOME/<app>/config.{toml,json}`, falling back to `~/.config`),
+//! 3. an explicit file (`file(path)` or `--config <path>`),
+//! 4. `APPNAME_*` environment variables, where `__` separates nested keys
+//!    (`TOOL_SERVER__PORT=8080` sets `server.port`),
+//! 5. command-line overrides (`--set key.path=value` or `set(key, value)`).
+//!
+//! Objects merge key by key; any other value replaces the one below it.
+//! Environment and flag values are read as JSON scalars when they parse as
+//! one (`8080`, `true`, `[1,2]`) and as strings otherwise.
+//!
+//! The `modcli` key holds framework settings (`FrameworkConfig`: prefix,
+//! theme, banner, messages, shell). `ModCli::configure` loads the layers,
+//! applies that section and returns the application's own typed config.
+//!
+//! # Example
+//! ```no_run
+//! use modcli::config::ConfigLoader;
+//! use modcli::ModCli;
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct AppConfig {
+//!     endpoint: String,
+//!     #[serde(default)]
+//!     retries: u32,
+//! }
+//!
+//! let mut args: Vec<String> = std::env::args().skip(1).collect();
+//! let mut cli = ModCli::new();
+//! let loader = ConfigLoader::new("tool")
+//!     .defaults(&serde_json::json!({ "endpoint": "https://localhost" }))
+//!     .args(&mut args);
+//! let config: AppConfig = cli.configure(loader).expect("valid config");
+//! cli.run_and_exit(args);
+//! ```
+
+use crate::error::ModCliError;
+use crate::shell::Shell;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// File names looked up inside each `<config dir>/<app>/` directory.
+const FILE_NAMES: [&str; 2] = ["config.toml", "config.json"];
+
+/// Builder that collects configuration layers and merges them on `load`.
+#[derive(Debug)]
+pub struct ConfigLoader {
+    app: String,
+    env_prefix: Option<String>,
+    defaults: Value,
+    search: bool,
+    files: Vec<PathBuf>,
+    overrides: Vec<(String, Value)>,
+    error: Option<String>,
+}
+
+impl ConfigLoader {
+    /// Loader for `app`: searches `<config dir>/<app>/` and reads `APP_*`
+    /// environment variables (upper-cased, `-` replaced with `_`).
+    pub fn new(app: &str) -> Self {
+        Self {
+            app: app.to_string(),
+            env_prefix: Some(format!("{}_", app.to_uppercase().replace('-', "_"))),
+            defaults: Value::Object(Map::new()),
+            search: true,
+            files: Vec::new(),
+            overrides: Vec::new(),
+            error: None,
+        }
+    }
+
+    /// Lowest-precedence values, usually the application's default config.
+    pub fn defaults<T: Serialize + ?Sized>(mut self, value: &T) -> Self {
+        match serde_json::to_value(value) {
+            Ok(v) => self.defaults = v,
+            Err(e) => self.fail(format!("defaults: {e}")),
+        }
+        self
+    }
+
+    /// Explicit config file; it must exist. Later files take precedence.
+    pub fn file<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.files.push(path.into());
+        self
+    }
+
+    /// Enable or disable the XDG directory search (enabled by default).
+    pub fn search(mut self, enabled: bool) -> Self {
+        self.search = enabled;
+        self
+    }
+
+    /// Prefix for environment overrides, e.g. `MYTOOL_`.
+    pub fn env_prefix(mut self, prefix: &str) -> Self {
+        self.env_prefix = Some(prefix.to_string());
+        self
+    }
+
+    /// Ignore environment variables.
+    pub fn no_env(mut self) -> Self {
+        self.env_prefix = None;
+        self
+    }
+
+    /// Highest-precedence override for a dotted key path (`server.port`).
+    pub fn set<T: Serialize + ?Sized>(mut self, key: &str, value: &T) -> Self {
+        match serde_json::to_value(value) {
+            Ok(v) => self.overrides.push((key.to_string(), v)),
+            Err(e) => self.fail(format!("{key}: {e}")),
+        }
+        self
+    }
+
+    /// Remove `--config <path>` and `--set key=value` (repeatable, `=` forms
+    /// accepted) from `args` and record them. Only the options before the
+    /// first non-option word (normally the command) are scanned, so a
+    /// command's own `--config` is left alone; other options there need the
+    /// `--name=value` form. Malformed flags are reported by `load`.
+    pub fn args(mut self, args: &mut Vec<String>) -> Self {
+        let mut i = 0;
+        while i < args.len() {
+            let arg = args[i].as_str();
+            if arg == "--" || !arg.starts_with('-') {
+                break;
+            }
+            let (flag, inline) = match arg.split_once('=') {
+                Some((f, v)) if f == "--config" || f == "--set" => (f, Some(v.to_string())),
+                _ if arg == "--config" || arg == "--set" => (arg, None),
+                _ => {
+                    i += 1;
+                    continue;
+                }
+            };
+            let flag = flag.to_string();
+            let value = match inline {
+                Some(v) => {
+                    args.remove(i);
+                    v
+                }
+                None => {
+                    if i + 1 >= args.len() {
+                        self.fail(format!("{flag} requires a value"));
+                        args.remove(i);
+                        continue;
+                    }
+                    args.drain(i..i + 2).nth(1).unwrap_or_default()
+                }
+            };
+            if flag == "--config" {
+                self.files.push(PathBuf::from(value));
+            } else if let Some((key, raw)) = value.split_once('=') {
+                self.overrides
+                    .push((key.trim().to_string(), parse_scalar(raw)));
+            } else {
+                self.fail(format!("--set expects key=value, got `{value}`"));
+            }
+        }
+        self
+    }
+
+    /// Config files the XDG search would read, lowest precedence first.
+    /// Only existing files are returned.
+    pub fn search_paths(&self) -> Vec<PathBuf> {
+        let mut dirs: Vec<PathBuf> = Vec::new();
+        let system = std::env::var("XDG_CONFIG_DIRS")
+            .ok()
+            .filter(|v| !v.is_empty())
+            .unwrap_or_else(|| "/etc/xdg".into());
+        // XDG_CONFIG_DIRS lists the most important directory first
+        dirs.extend(system.split(':').rev().map(PathBuf::from));
+        match std::env::var_os("XDG_CONFIG_HOME").filter(|v| !v.is_empty()) {
+            Some(home) => dirs.push(PathBuf::from(home)),
+            None => {
+                if let Some(home) = std::env::var_os("HOME").filter(|v| !v.is_empty()) {
+                    dirs.push(Path::new(&home).join(".config"));
+                }
+            }
+        }
+        dirs.into_iter()
+            .flat_map(|d| FILE_NAMES.iter().map(move |f| d.join(&self.app).join(f)))
+            .filter(|p| p.is_file())
+            .collect()
+    }
+
+    /// Merge all layers into a single JSON value.
+    pub fn load_value(&self) -> Result<Value, ModCliError> {
+        if let Some(e) = &self.error {
+            return Err(ModCliError::Config(e.clone()));
+        }
+        let mut merged = self.defaults.clone();
+        if self.search {
+            for path in self.search_paths() {
+                merge(&mut merged, read_file(&path)?);
+            }
+        }
+        for path in &self.files {
+            merge(&mut merged, read_file(path)?);
+        }
+        if let Some(prefix) = &self.env_prefix {
+            let mut vars: Vec<(String, String)> = std::env::vars()
+                .filter(|(k, _)| k.starts_with(prefix.as_str()) && k.len() > prefix.len())
+                .collect();
+            vars.sort();
+            for (key, raw) in vars {
+                let path = key[prefix.len()..].to_lowercase().replace("__", ".");
+                merge(&mut merged, nested(&path, parse_scalar(&raw)));
+            }
+        }
+        for (key, value) in &self.overrides {
+            merge(&mut merged, nested(key, value.clone()));
+        }
+        Ok(merged)
+    }
+
+    /// Merge all layers and deserialize them into `T`.
+    pub fn load<T: DeserializeOwned>(&self) -> Result<T, ModCliError> {
+        let value = self.load_value()?;
+        serde_json::from_value(value).map_err(|e| ModCliError::Config(e.to_string()))
+    }
+
+    fn fail(&mut self, msg: String) {
+        self.error.get_or_insert(msg);
+    }
+}
+
+/// Framework settings read from the `modcli` key (see `examples/config.json`).
+/// `ModCli::apply_config` applies all of them except `shell`, which the
+/// application builds with `shell()` and runs itself.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+pub struct FrameworkConfig {
+    pub prefix: Option<String>,
+    /// Startup banner text, printed once on the first run.
+    pub banner: Option<String>,
+    /// Built-in theme name (see `output::themes::apply_theme`).
+    pub theme: Option<String>,
+    /// Message catalog overrides (see `output::messages::set_message`).
+    pub messages: BTreeMap<String, String>,
+    pub shell: Option<ShellConfig>,
+}
+
+/// Interactive shell settings under `modcli.shell`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+pub struct ShellConfig {
+    pub prompt: Option<String>,
+    pub welcome: Vec<String>,
+    pub goodbye: Vec<String>,
+}
+
+impl FrameworkConfig {
+    /// Read the `modcli` section of a merged config value (empty when absent).
+    pub fn from_value(value: &Value) -> Result<Self, ModCliError> {
+        match value.get("modcli") {
+            None | Some(Value::Null) => Ok(Self::default()),
+            Some(section) => serde_json::from_value(section.clone())
+                .map_err(|e| ModCliError::Config(format!("modcli: {e}"))),
+        }
+    }
+
+    /// Shell configured from `modcli.shell`, if present.
+    pub fn shell(&self) -> Option<Shell> {
+        let cfg = self.shell.as_ref()?;
+        let mut shell = Shell::new();
+        if let Some(prompt) = &cfg.prompt {
+            shell = shell.prompt(prompt);
+        }
+        if !cfg.welcome.is_empty() {
+            shell = shell.welcome(&cfg.welcome.join("\n"));
+        }
+        if !cfg.goodbye.is_empty() {
+            shell = shell.goodbye(&cfg.goodbye.join("\n"));
+        }
+        Some(shell)
+    }
+}
+
+// Parse a file as TOML or JSON based on its extension.
+fn read_file(path: &Path) -> Result<Value, ModCliError> {
+    let data = std::fs::read_to_string(path)
+        .map_err(|e| ModCliError::Config(format!("{}: {e}", path.display())))?;
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_ascii_lowercase();
+    let parsed = match ext.as_str() {
+        "toml" => toml::from_str::<Value>(&data).map_err(|e| e.to_string()),
+        "json" => serde_json::from_str::<Value>(&data).map_err(|e| e.to_string()),
+        _ => Err("unsupported format (expected .toml or .json)".to_string()),
+    };
+    parsed.map_err(|e| ModCliError::Config(format!("{}: {e}", path.display())))
+}
+
+// Deep-merge `upper` into `base`: objects by key, everything else replaced.
+fn merge(base: &mut Value, upper: Value) {
+    match (base, upper) {
+        (Value::Object(base), Value::Object(upper)) => {
+            for (k, v) in upper {
+                match base.get_mut(&k) {
+                    Some(slot) => merge(slot, v),
+                    None => {
+                        base.insert(k, v);
+                    }
+                }
+            }
+        }
+        (slot, upper) => *slot = upper,
+    }
+}
+
+// Build `{"a":{"b":value}}` from the dotted path `a.b`.
+fn nested(path: &str, value: Value) -> Value {
+    path.rsplit('.').fold(value, |acc, key| {
+        let mut map = Map::new();
+        map.insert(key.to_string(), acc);
+        Value::Object(map)
+    })
+}
+
+fn parse_scalar(raw: &str) -> Value {
+    match serde_json::from_str::<Value>(raw) {
+        Ok(v @ (Value::Bool(_) | Value::Number(_) | Value::Array(_) | Value::Null)) => v,
+        _ => Value::String(raw.to_string()),
+    }
+}
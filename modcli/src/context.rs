@@ -0,0 +1,138 @@
+//! Execution context passed to `Command::run`.
+//!
+//! A `Context` carries the resolved command path, the raw invocation, the
+//! parsed arguments, global options, granted capabilities, the registry's output handle,
+//! application state registered with `CommandRegistry::insert_state`, the
+//! cancellation token and, with `structured-output`, `emit()` for values.
+//!
+//! # Example
+//! ```no_run
+//! use modcli::command::Command;
+//! use modcli::context::Context;
+//! use modcli::error::ModCliError;
+//! use modcli::exit::ExitCode;
+//! use modcli::ModCli;
+//!
+//! struct AppState {
+//!     endpoint: String,
+//! }
+//!
+//! struct Status;
+//!
+//! impl Command for Status {
+//!     fn name(&self) -> &str { "status" }
+//!     fn execute(&self, _args: &[String]) {}
+//!     fn run(&self, ctx: &Context) -> Result<ExitCode, ModCliError> {
+//!         let state = ctx.get::<AppState>().expect("state registered");
+//!         ctx.out().line(&format!("{}: ok", state.endpoint));
+//!         Ok(ExitCode::SUCCESS)
+//!     }
+//! }
+//!
+//! let mut cli = ModCli::new();
+//! cli.registry.insert_state(AppState { endpoint: "https://api".into() });
+//! cli.registry.register(Box::new(Status));
+//! cli.main();
+//! ```
+
+use crate::cancel::CancellationToken;
+#[cfg(feature = "structured-output")]
+use crate::error::ModCliError;
+use crate::loader::CommandRegistry;
+use crate::output::handle::Output;
+use crate::output::mode::{self, OutputMode};
+use crate::spec::ParsedArgs;
+use std::any::Any;
+use std::collections::HashSet;
+
+/// Per-invocation view of the registry handed to a command.
+pub struct Context<'a> {
+    registry: &'a CommandRegistry,
+    path: String,
+    invocation: Vec<String>,
+    args: ParsedArgs,
+}
+
+impl<'a> Context<'a> {
+    /// Build a context for the command at `path` (`:`-separated).
+    /// `invocation` is the raw command word plus its arguments.
+    pub fn new(
+        registry: &'a CommandRegistry,
+        path: &str,
+        invocation: Vec<String>,
+        args: ParsedArgs,
+    ) -> Self {
+        Self {
+            registry,
+            path: path.to_string(),
+            invocation,
+            args,
+        }
+    }
+
+    pub fn registry(&self) -> &'a CommandRegistry {
+        self.registry
+    }
+
+    /// Resolved command path, e.g. `cluster:node:drain`.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Raw invocation as typed: the command word followed by all arguments.
+    pub fn invocation(&self) -> &[String] {
+        &self.invocation
+    }
+
+    /// Arguments for this command (parsed against `spec()` when declared).
+    pub fn args(&self) -> &ParsedArgs {
+        &self.args
+    }
+
+    /// Global options given anywhere on the command line (see `globals`).
+    pub fn globals(&self) -> &'a ParsedArgs {
+        self.registry.globals()
+    }
+
+    /// Capabilities granted on the registry.
+    pub fn caps(&self) -> &'a HashSet<String> {
+        self.registry.caps()
+    }
+
+    pub fn has_cap(&self, cap: &str) -> bool {
+        self.registry.has_cap(cap)
+    }
+
+    /// Output handle for this invocation (see `CommandRegistry::set_output`).
+    pub fn out(&self) -> &'a Output {
+        self.registry.output()
+    }
+
+    /// Application state registered with `CommandRegistry::insert_state`.
+    pub fn get<T: Any + Send + Sync>(&self) -> Option<&'a T> {
+        self.registry.state::<T>()
+    }
+
+    pub fn cancellation(&self) -> &'a CancellationToken {
+        self.registry.cancellation()
+    }
+
+    /// True once cancellation was requested; long-running commands should stop.
+    pub fn is_cancelled(&self) -> bool {
+        self.registry.cancellation().is_cancelled()
+    }
+
+    /// Current output mode (`--output`).
+    pub fn output_mode(&self) -> OutputMode {
+        mode::output_mode()
+    }
+
+    /// Write a serializable value to `out()` in the current output mode: a table
+    /// or `key  value` lines for humans, or JSON/NDJSON/YAML.
+    #[cfg(feature = "structured-output")]
+    pub fn emit<T: serde::Serialize + ?Sized>(&self, value: &T) -> Result<(), ModCliError> {
+        let text = crate::output::emit::render(value, mode::output_mode())?;
+        self.out().write(&text);
+        Ok(())
+    }
+}
@@ -1,5 +1,6 @@
 /*
 use crate::command::Command;
+use crate::output::print;
 
 /// An example custom hardcoded command.
 pub struct CustomCommand;
@@ -18,9 +19,9 @@ impl Command for CustomCommand {
     }
 
     fn execute(&self, args: &[String]) {
-        println!("You executed a custom command!");
+        print::line("You executed a custom command!");
         if !args.is_empty() {
-            println!("With args: {:?}", args);
+            print::line(&format!("With args: {:?}", args));
         }
     }
 }
@@ -0,0 +1,86 @@
+//! Deprecated commands, aliases and renames.
+//!
+//! Mark a command with `Command::deprecated()`, an alias with
+//! `CommandRegistry::deprecate_alias`, or move a command to a new name with
+//! `CommandRegistry::rename`, which keeps the old name working as a
+//! forwarding alias. The first use of a deprecated name in a registry emits
+//! one warning through `output::hook::deprecated`; later uses are silent.
+//!
+//! Help annotates deprecated commands and aliases, or leaves them out with
+//! `CommandRegistry::set_list_deprecated(false)`.
+//!
+//! # Example
+//! ```
+//! use modcli::deprecation::Deprecation;
+//! use modcli::loader::CommandRegistry;
+//!
+//! let mut reg = CommandRegistry::new();
+//! // `tool deploy` keeps working and forwards to `tool release deploy`
+//! reg.rename("deploy", "release:deploy", Deprecation::new().removed_in("2.0"));
+//!
+//! let dep = reg.deprecation("deploy").unwrap();
+//! assert_eq!(
+//!     dep.message("deploy"),
+//!     "`deploy` is deprecated and will be removed in 2.0; use `release deploy` instead."
+//! );
+//! ```
+
+use crate::tree::display_path;
+
+/// Why and how a command or alias is deprecated.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Deprecation {
+    /// Command to use instead (`:` path or words).
+    pub replacement: Option<String>,
+    /// Version in which the name goes away.
+    pub removal: Option<String>,
+    /// Extra explanation appended to the warning.
+    pub note: Option<String>,
+}
+
+impl Deprecation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn replaced_by(mut self, replacement: &str) -> Self {
+        self.replacement = Some(replacement.to_string());
+        self
+    }
+
+    pub fn removed_in(mut self, version: &str) -> Self {
+        self.removal = Some(version.to_string());
+        self
+    }
+
+    pub fn note(mut self, note: &str) -> Self {
+        self.note = Some(note.to_string());
+        self
+    }
+
+    /// Warning for `name` (a `:` path or typed words).
+    pub fn message(&self, name: &str) -> String {
+        let mut msg = format!("`{}` is deprecated", display_path(name));
+        if let Some(ref version) = self.removal {
+            msg.push_str(&format!(" and will be removed in {version}"));
+        }
+        if let Some(ref replacement) = self.replacement {
+            msg.push_str(&format!("; use `{}` instead", display_path(replacement)));
+        }
+        msg.push('.');
+        if let Some(ref note) = self.note {
+            msg.push(' ');
+            msg.push_str(note);
+        }
+        msg
+    }
+
+    /// Short annotation for help listings: `deprecated` or
+    /// `deprecated, use <replacement>`.
+    pub fn label(&self) -> String {
+        match self.replacement {
+            Some(ref r) => format!("deprecated, use {}", display_path(r)),
+            None => "deprecated".to_string(),
+        }
+    }
+}
@@ -0,0 +1,173 @@
+//! Commands derived from a struct (feature `derive`).
+//!
+//! `#[derive(ModCommand)]` turns a struct's fields into the command's
+//! arguments and generates its metadata; implementing `Run` supplies the
+//! behavior. `ModCommand::command()` wraps the pair as a `Command` for
+//! `CommandRegistry::register`, which parses arguments into a fresh struct on
+//! every invocation.
+//!
+//! Struct attributes, `#[command(...)]`:
+//! - `name = "deploy"` (default: the struct name in kebab case without a
+//!   `Command` suffix), `aliases("dp", ...)`, `help = "..."` (default: the
+//!   first paragraph of the doc comment; later paragraphs become `long_help`),
+//!   `hidden`, `caps("admin", ...)`, `category = "..."`
+//!
+//! Fields become options named after the field (`dry_run` -> `--dry-run`):
+//! - `bool` is a flag; `#[arg(count)]` on an integer counts repeated flags
+//! - `Option<T>` is optional, `Vec<T>` repeatable, any other `T: FromStr`
+//!   required unless it has a `default`
+//! - integer, float and `PathBuf` fields are type-checked by the parser
+//!
+//! Field attributes, `#[arg(...)]`: `positional`, `short = 'p'`,
+//! `long = "..."`, `name = "..."`, `help = "..."` (default: doc comment),
+//! `default = "..."`, `value_name = "..."`, `choices("a", "b", ...)`, `count`.
+//!
+//! # Example
+//! ```
+//! use modcli::context::Context;
+//! use modcli::derive::{ModCommand, Run};
+//! use modcli::error::ModCliError;
+//! use modcli::exit::ExitCode;
+//! use modcli::loader::CommandRegistry;
+//!
+//! /// Deploy a build
+//! #[derive(ModCommand)]
+//! #[command(aliases("dp"), category = "Deployment")]
+//! struct Deploy {
+//!     /// Environment to deploy to
+//!     #[arg(positional, choices("staging", "prod"))]
+//!     target: String,
+//!     #[arg(short = 'p', default = "8080")]
+//!     port: u16,
+//!     dry_run: bool,
+//! }
+//!
+//! impl Run for Deploy {
+//!     fn run(self, ctx: &Context) -> Result<ExitCode, ModCliError> {
+//!         ctx.out().line(&format!("{}:{} dry={}", self.target, self.port, self.dry_run));
+//!         Ok(ExitCode::SUCCESS)
+//!     }
+//! }
+//!
+//! let mut reg = CommandRegistry::new();
+//! reg.register(Deploy::command());
+//! reg.try_run("dp", &["prod".into(), "--dry-run".into()]).unwrap();
+//! ```
+
+use crate::command::Command;
+use crate::context::Context;
+use crate::error::ModCliError;
+use crate::exit::ExitCode;
+use crate::spec::{CommandSpec, ParsedArgs};
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+pub use modcli_derive::ModCommand;
+
+/// Command metadata, argument schema and parsing; generated by
+/// `#[derive(ModCommand)]`.
+pub trait ModCommand: Sized + 'static {
+    fn name() -> &'static str;
+    fn aliases() -> &'static [&'static str] {
+        &[]
+    }
+    fn help() -> Option<&'static str> {
+        None
+    }
+    fn long_help() -> Option<&'static str> {
+        None
+    }
+    fn category() -> Option<&'static str> {
+        None
+    }
+    fn hidden() -> bool {
+        false
+    }
+    fn required_caps() -> &'static [&'static str] {
+        &[]
+    }
+    fn spec() -> CommandSpec;
+    /// Build the struct from arguments already parsed against `spec()`.
+    fn from_args(args: &ParsedArgs) -> Result<Self, ModCliError>;
+
+    /// The command, ready for `CommandRegistry::register`.
+    fn command() -> Box<dyn Command>
+    where
+        Self: Run,
+    {
+        Box::new(Derived::<Self>(PhantomData))
+    }
+}
+
+/// Behavior of a derived command, run with the parsed struct.
+pub trait Run {
+    fn run(self, ctx: &Context) -> Result<ExitCode, ModCliError>;
+}
+
+// `Command` for a derived struct; parses a new `T` per invocation.
+struct Derived<T>(PhantomData<fn() -> T>);
+
+impl<T: ModCommand + Run> Command for Derived<T> {
+    fn name(&self) -> &str {
+        T::name()
+    }
+    fn aliases(&self) -> &[&str] {
+        T::aliases()
+    }
+    fn help(&self) -> Option<&str> {
+        T::help()
+    }
+    fn long_help(&self) -> Option<&str> {
+        T::long_help()
+    }
+    fn category(&self) -> Option<&str> {
+        T::category()
+    }
+    fn hidden(&self) -> bool {
+        T::hidden()
+    }
+    fn required_caps(&self) -> &[&str] {
+        T::required_caps()
+    }
+    fn spec(&self) -> Option<CommandSpec> {
+        Some(T::spec())
+    }
+    // Dispatch goes through `run`.
+    fn execute(&self, _args: &[String]) {}
+    fn run(&self, ctx: &Context) -> Result<ExitCode, ModCliError> {
+        T::from_args(ctx.args())?.run(ctx)
+    }
+}
+
+fn invalid(name: &str, raw: &str) -> ModCliError {
+    ModCliError::InvalidUsage(format!("invalid value '{raw}' for {name}"))
+}
+
+#[doc(hidden)]
+pub fn __required<T: FromStr>(args: &ParsedArgs, name: &str) -> Result<T, ModCliError> {
+    let raw = args
+        .get_str(name)
+        .ok_or_else(|| ModCliError::InvalidUsage(format!("missing required argument {name}")))?;
+    raw.parse().map_err(|_| invalid(name, raw))
+}
+
+#[doc(hidden)]
+pub fn __optional<T: FromStr>(args: &ParsedArgs, name: &str) -> Result<Option<T>, ModCliError> {
+    args.get_str(name)
+        .map(|raw| raw.parse().map_err(|_| invalid(name, raw)))
+        .transpose()
+}
+
+#[doc(hidden)]
+pub fn __many<T: FromStr>(args: &ParsedArgs, name: &str) -> Result<Vec<T>, ModCliError> {
+    args.get_all(name)
+        .iter()
+        .map(|raw| raw.parse().map_err(|_| invalid(name, raw)))
+        .collect()
+}
+
+#[doc(hidden)]
+pub fn __count<T: TryFrom<usize>>(args: &ParsedArgs, name: &str) -> Result<T, ModCliError> {
+    let n = args.count(name);
+    T::try_from(n).map_err(|_| invalid(name, &n.to_string()))
+}
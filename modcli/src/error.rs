@@ -21,13 +21,43 @@ pub enum ModCliError {
     #[error("validation failed: {0}")]
     Validation(String),
 
-    #[error("unknown command: {0}")]
-    UnknownCommand(String),
+    /// The authorization policy (or missing capabilities) refused the command.
+    #[error("unauthorized: {0}")]
+    Unauthorized(String),
+
+    /// Command not found; `suggestions` holds close visible matches (may be empty).
+    #[error("unknown command: {name}")]
+    UnknownCommand {
+        name: String,
+        suggestions: Vec<String>,
+    },
+
+    /// An async command exceeded its `AsyncCommand::timeout`.
+    #[error("command `{command}` timed out after {after:?}")]
+    Timeout {
+        command: String,
+        after: std::time::Duration,
+    },
+
+    /// The registry's cancellation token fired (Ctrl-C) while an async
+    /// command was running; its future was dropped.
+    #[error("command `{command}` was interrupted")]
+    Interrupted { command: String },
+
+    /// A name or alias collides with one already registered (see
+    /// `CommandRegistry::try_register` and `check`).
+    #[error("registration conflict: {0}")]
+    Conflict(crate::tree::Conflict),
 
     #[cfg(feature = "theme-config")]
     #[error("config parse error: {0}")]
     ConfigParse(#[from] serde_json::Error),
 
+    /// Configuration could not be read, parsed or deserialized (feature `config`).
+    #[cfg(feature = "config")]
+    #[error("config error: {0}")]
+    Config(String),
+
     #[error("error: {0}")]
     Other(String),
 }
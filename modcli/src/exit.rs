@@ -0,0 +1,134 @@
+//! Process exit codes and the error-to-exit-code table.
+//!
+//! Commands report status by overriding `Command::run` and returning
+//! `Ok(ExitCode)` or an error. `ModCli::run_and_exit` maps errors through an
+//! `ExitCodes` table (sysexits-style by default) so scripts can rely on the
+//! process exit status.
+//!
+//! # Example
+//! ```no_run
+//! use modcli::exit::ExitCodes;
+//! use modcli::ModCli;
+//!
+//! let mut cli = ModCli::new();
+//! cli.set_exit_codes(ExitCodes { unknown_command: 2, ..ExitCodes::default() });
+//! cli.main();
+//! ```
+
+use crate::error::ModCliError;
+
+/// Exit status of a command (`0` = success).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ExitCode(pub u8);
+
+impl ExitCode {
+    pub const SUCCESS: ExitCode = ExitCode(0);
+    pub const FAILURE: ExitCode = ExitCode(1);
+    /// Command line usage error (`EX_USAGE`).
+    pub const USAGE: ExitCode = ExitCode(64);
+    /// Input data was incorrect (`EX_DATAERR`).
+    pub const DATAERR: ExitCode = ExitCode(65);
+    /// Input file missing or unreadable (`EX_NOINPUT`).
+    pub const NOINPUT: ExitCode = ExitCode(66);
+    /// A required service is unavailable (`EX_UNAVAILABLE`).
+    pub const UNAVAILABLE: ExitCode = ExitCode(69);
+    /// Internal software error (`EX_SOFTWARE`).
+    pub const SOFTWARE: ExitCode = ExitCode(70);
+    /// Input/output error (`EX_IOERR`).
+    pub const IOERR: ExitCode = ExitCode(74);
+    /// Permission denied (`EX_NOPERM`).
+    pub const NOPERM: ExitCode = ExitCode(77);
+    /// Configuration error (`EX_CONFIG`).
+    pub const CONFIG: ExitCode = ExitCode(78);
+    /// Command timed out (`timeout(1)` convention).
+    pub const TIMEOUT: ExitCode = ExitCode(124);
+    /// Command not found (shell convention).
+    pub const NOT_FOUND: ExitCode = ExitCode(127);
+    /// Interrupted by Ctrl-C (`128 + SIGINT`).
+    pub const INTERRUPTED: ExitCode = ExitCode(130);
+
+    pub fn code(self) -> u8 {
+        self.0
+    }
+
+    pub fn is_success(self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl From<u8> for ExitCode {
+    fn from(code: u8) -> Self {
+        ExitCode(code)
+    }
+}
+
+impl From<ExitCode> for std::process::ExitCode {
+    fn from(code: ExitCode) -> Self {
+        std::process::ExitCode::from(code.0)
+    }
+}
+
+/// Exit code for each `ModCliError` kind.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ExitCodes {
+    /// `InvalidUsage` (bad arguments).
+    pub usage: u8,
+    /// `Validation`.
+    pub validation: u8,
+    /// `Unauthorized`.
+    pub unauthorized: u8,
+    /// `UnknownCommand`.
+    pub unknown_command: u8,
+    /// `Io`.
+    pub io: u8,
+    /// `MissingShellConfig`, `ConfigParse` and `Config`.
+    pub config: u8,
+    /// `PluginLoad`.
+    pub unavailable: u8,
+    /// `Timeout`.
+    pub timeout: u8,
+    /// `Interrupted`.
+    pub interrupted: u8,
+    /// `Conflict` and `Other`.
+    pub other: u8,
+}
+
+impl Default for ExitCodes {
+    /// sysexits(3) codes, with `127` for unknown commands.
+    fn default() -> Self {
+        Self {
+            usage: ExitCode::USAGE.0,
+            validation: ExitCode::DATAERR.0,
+            unauthorized: ExitCode::NOPERM.0,
+            unknown_command: ExitCode::NOT_FOUND.0,
+            timeout: ExitCode::TIMEOUT.0,
+            interrupted: ExitCode::INTERRUPTED.0,
+            io: ExitCode::IOERR.0,
+            config: ExitCode::CONFIG.0,
+            unavailable: ExitCode::UNAVAILABLE.0,
+            other: ExitCode::FAILURE.0,
+        }
+    }
+}
+
+impl ExitCodes {
+    /// Map an error to its exit code.
+    pub fn code_for(&self, err: &ModCliError) -> ExitCode {
+        ExitCode(match err {
+            ModCliError::InvalidUsage(_) => self.usage,
+            ModCliError::Validation(_) => self.validation,
+            ModCliError::Unauthorized(_) => self.unauthorized,
+            ModCliError::UnknownCommand { .. } => self.unknown_command,
+            ModCliError::Io(_) => self.io,
+            ModCliError::MissingShellConfig => self.config,
+            #[cfg(feature = "theme-config")]
+            ModCliError::ConfigParse(_) => self.config,
+            #[cfg(feature = "config")]
+            ModCliError::Config(_) => self.config,
+            ModCliError::PluginLoad(_) => self.unavailable,
+            ModCliError::Timeout { .. } => self.timeout,
+            ModCliError::Interrupted { .. } => self.interrupted,
+            ModCliError::Conflict(_) | ModCliError::Other(_) => self.other,
+        })
+    }
+}
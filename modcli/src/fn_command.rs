@@ -0,0 +1,225 @@
+//! Commands built inline from closures.
+//!
+//! `CommandRegistry::command(name)` starts a `CommandBuilder`; `run` (or
+//! `run_async` with the `async` feature) registers a command backed by the
+//! closure. Handy for small tools and tests that don't need a struct.
+//!
+//! # Example
+//! ```
+//! use modcli::exit::ExitCode;
+//! use modcli::loader::CommandRegistry;
+//! use modcli::spec::ArgSpec;
+//!
+//! let mut reg = CommandRegistry::new();
+//! reg.command("greet")
+//!     .alias("hi")
+//!     .help("Greets someone")
+//!     .arg(ArgSpec::positional("who").default("world"))
+//!     .run(|ctx| {
+//!         ctx.out().line(&format!("Hello, {}!", ctx.args().get_str("who").unwrap_or("")));
+//!         Ok(ExitCode::SUCCESS)
+//!     });
+//! reg.try_run("hi", &[]).unwrap();
+//! ```
+
+use crate::command::Command;
+#[cfg(feature = "async")]
+use crate::command::{AsyncCommand, CommandFuture};
+use crate::context::Context;
+use crate::error::ModCliError;
+use crate::exit::ExitCode;
+use crate::loader::CommandRegistry;
+use crate::spec::{ArgSpec, CommandSpec};
+
+type RunFn = dyn Fn(&Context) -> Result<ExitCode, ModCliError> + Send + Sync;
+
+#[cfg(feature = "async")]
+type AsyncRunFn = dyn for<'a> Fn(&'a Context<'a>) -> CommandFuture<'a> + Send + Sync;
+
+// Metadata shared by sync and async closure commands.
+#[derive(Default)]
+struct Meta {
+    name: String,
+    aliases: Vec<&'static str>,
+    help: Option<String>,
+    long_help: Option<String>,
+    category: Option<String>,
+    hidden: bool,
+    caps: Vec<&'static str>,
+    spec: Option<CommandSpec>,
+}
+
+/// Builder returned by `CommandRegistry::command`.
+pub struct CommandBuilder<'r> {
+    registry: &'r mut CommandRegistry,
+    meta: Meta,
+}
+
+impl<'r> CommandBuilder<'r> {
+    pub(crate) fn new(registry: &'r mut CommandRegistry, name: &str) -> Self {
+        Self {
+            registry,
+            meta: Meta {
+                name: name.to_string(),
+                ..Meta::default()
+            },
+        }
+    }
+
+    pub fn alias(mut self, alias: &'static str) -> Self {
+        self.meta.aliases.push(alias);
+        self
+    }
+
+    pub fn aliases(mut self, aliases: &[&'static str]) -> Self {
+        self.meta.aliases.extend_from_slice(aliases);
+        self
+    }
+
+    pub fn help(mut self, help: &str) -> Self {
+        self.meta.help = Some(help.to_string());
+        self
+    }
+
+    /// Longer description (Markdown) for the help page.
+    pub fn long_help(mut self, text: &str) -> Self {
+        self.meta.long_help = Some(text.to_string());
+        self
+    }
+
+    pub fn category(mut self, category: &str) -> Self {
+        self.meta.category = Some(category.to_string());
+        self
+    }
+
+    pub fn hidden(mut self) -> Self {
+        self.meta.hidden = true;
+        self
+    }
+
+    /// Capabilities required to see and run the command.
+    pub fn caps(mut self, caps: &[&'static str]) -> Self {
+        self.meta.caps.extend_from_slice(caps);
+        self
+    }
+
+    /// Add an argument to the command's spec.
+    pub fn arg(mut self, arg: ArgSpec) -> Self {
+        let spec = self.meta.spec.take().unwrap_or_default();
+        self.meta.spec = Some(spec.arg(arg));
+        self
+    }
+
+    /// Replace the command's spec.
+    pub fn spec(mut self, spec: CommandSpec) -> Self {
+        self.meta.spec = Some(spec);
+        self
+    }
+
+    /// Register the command with `f` as its `Command::run`.
+    pub fn run<F>(self, f: F)
+    where
+        F: Fn(&Context) -> Result<ExitCode, ModCliError> + Send + Sync + 'static,
+    {
+        self.registry.register(Box::new(FnCommand {
+            meta: self.meta,
+            run: Box::new(f),
+        }));
+    }
+
+    /// Register an async command (feature: `async`) with `f` as its
+    /// `AsyncCommand::run_async`: it gets the `Context` like `run` and
+    /// returns a boxed future, e.g. `|ctx| Box::pin(async move { .. })`.
+    #[cfg(feature = "async")]
+    pub fn run_async<F>(self, f: F)
+    where
+        F: for<'a> Fn(&'a Context<'a>) -> CommandFuture<'a> + Send + Sync + 'static,
+    {
+        self.registry.register_async(Box::new(AsyncFnCommand {
+            meta: self.meta,
+            run: Box::new(f),
+        }));
+    }
+}
+
+struct FnCommand {
+    meta: Meta,
+    run: Box<RunFn>,
+}
+
+impl Command for FnCommand {
+    fn name(&self) -> &str {
+        &self.meta.name
+    }
+    fn aliases(&self) -> &[&str] {
+        &self.meta.aliases
+    }
+    fn help(&self) -> Option<&str> {
+        self.meta.help.as_deref()
+    }
+    fn long_help(&self) -> Option<&str> {
+        self.meta.long_help.as_deref()
+    }
+    fn category(&self) -> Option<&str> {
+        self.meta.category.as_deref()
+    }
+    fn hidden(&self) -> bool {
+        self.meta.hidden
+    }
+    fn required_caps(&self) -> &[&str] {
+        &self.meta.caps
+    }
+    fn spec(&self) -> Option<CommandSpec> {
+        self.meta.spec.clone()
+    }
+    // Dispatch goes through `run`.
+    fn execute(&self, _args: &[String]) {}
+    fn run(&self, ctx: &Context) -> Result<ExitCode, ModCliError> {
+        (self.run)(ctx)
+    }
+}
+
+#[cfg(feature = "async")]
+struct AsyncFnCommand {
+    meta: Meta,
+    run: Box<AsyncRunFn>,
+}
+
+#[cfg(feature = "async")]
+impl AsyncCommand for AsyncFnCommand {
+    fn name(&self) -> &str {
+        &self.meta.name
+    }
+    fn aliases(&self) -> &[&str] {
+        &self.meta.aliases
+    }
+    fn help(&self) -> Option<&str> {
+        self.meta.help.as_deref()
+    }
+    fn long_help(&self) -> Option<&str> {
+        self.meta.long_help.as_deref()
+    }
+    fn category(&self) -> Option<&str> {
+        self.meta.category.as_deref()
+    }
+    fn hidden(&self) -> bool {
+        self.meta.hidden
+    }
+    fn required_caps(&self) -> &[&str] {
+        &self.meta.caps
+    }
+    fn spec(&self) -> Option<CommandSpec> {
+        self.meta.spec.clone()
+    }
+    // Dispatch goes through `run_async`.
+    fn execute_async<'a>(
+        &'a self,
+        _args: &'a [String],
+    ) -> core::pin::Pin<Box<dyn core::future::Future<Output = Result<(), ModCliError>> + Send + 'a>>
+    {
+        Box::pin(async { Ok(()) })
+    }
+    fn run_async<'a>(&'a self, ctx: &'a Context<'_>) -> CommandFuture<'a> {
+        (self.run)(ctx)
+    }
+}
@@ -0,0 +1,296 @@
+//! Global options accepted before or after the command name.
+//!
+//! `ModCli::run` (and `run_with_status` / `run_async`) removes declared
+//! global options from anywhere before a `--` separator, parses them with the
+//! same rules as `spec::CommandSpec`, applies the built-in ones and stores the
+//! result on the registry, where every command reads it via `ctx.globals()`.
+//! `tool -v deploy prod` and `tool deploy prod -v` are equivalent.
+//!
+//! A registry starts without global options. Opt into the standard set with
+//! `ModCli::with_standard_globals()` (or `GlobalOptions::standard()`):
+//! - `-v, --verbose` (repeatable) and `-q, --quiet`: see `verbosity()`; quiet
+//!   hides info, status, success and debug messages from `output::hook`
+//! - `--color <auto|always|never>`: force colored output on or off; without
+//!   it the choice made with `color_policy::set_choice` stands
+//! - `--output <mode>`: see `output::mode`
+//! - `--config <path>` (feature `config`): exposed as-is (see
+//!   `config::ConfigLoader::file`)
+//!
+//! Before the command word, global options always win. After it, an option
+//! the resolved command's own `spec()` declares stays with the command, so a
+//! grep-style `-v` or an `--output <file>` reaches it untouched.
+//!
+//! # Example
+//! ```
+//! use modcli::globals::GlobalOptions;
+//! use modcli::spec::ArgSpec;
+//!
+//! let globals = GlobalOptions::standard().arg(ArgSpec::option("profile").short('p'));
+//! let mut args: Vec<String> = ["-v", "deploy", "prod", "--profile=ci", "-vq"]
+//!     .iter()
+//!     .map(|s| s.to_string())
+//!     .collect();
+//! let parsed = globals.extract(&mut args).unwrap();
+//! assert_eq!(args, vec!["deploy", "prod"]);
+//! assert_eq!(parsed.count("verbose"), 2);
+//! assert!(parsed.flag("quiet"));
+//! assert_eq!(parsed.get_str("profile"), Some("ci"));
+//! assert_eq!(parsed.get_str("color"), None);
+//! ```
+
+use crate::error::ModCliError;
+use crate::output::color_policy::{self, ColorChoice};
+use crate::output::mode::{self, OutputMode};
+use crate::spec::{ArgKind, ArgSpec, CommandSpec, ParsedArgs};
+use crate::testing;
+use std::sync::atomic::{AtomicI8, Ordering};
+
+static VERBOSITY: AtomicI8 = AtomicI8::new(0);
+
+/// Declared global options.
+#[derive(Clone, Debug, Default)]
+pub struct GlobalOptions {
+    spec: CommandSpec,
+}
+
+impl GlobalOptions {
+    /// No global options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `--verbose`, `--quiet`, `--color`, `--output` and, with the `config`
+    /// feature, `--config`.
+    pub fn standard() -> Self {
+        let options = Self::new()
+            .arg(
+                ArgSpec::flag("verbose")
+                    .short('v')
+                    .repeatable()
+                    .help("More output (repeat for more)"),
+            )
+            .arg(
+                ArgSpec::flag("quiet")
+                    .short('q')
+                    .help("Only print warnings and errors"),
+            )
+            .arg(
+                ArgSpec::option("color")
+                    .value_name("WHEN")
+                    .choices(&["auto", "always", "never"])
+                    .help("Colorize output"),
+            )
+            .arg(
+                ArgSpec::option("output")
+                    .value_name("MODE")
+                    .help("Output mode: human, json, ndjson or yaml"),
+            );
+        #[cfg(feature = "config")]
+        let options = options.arg(
+            ArgSpec::option("config")
+                .value_name("PATH")
+                .help("Configuration file"),
+        );
+        options
+    }
+
+    /// Add a global option or flag. Positionals are ignored.
+    pub fn arg(mut self, arg: ArgSpec) -> Self {
+        self.add(arg);
+        self
+    }
+
+    /// Add a global option in place; replaces one with the same name.
+    pub fn add(&mut self, arg: ArgSpec) {
+        if arg.kind == ArgKind::Positional {
+            return;
+        }
+        let rest = self
+            .spec
+            .args()
+            .iter()
+            .filter(|a| a.name != arg.name)
+            .cloned();
+        self.spec = rest.fold(CommandSpec::new(), CommandSpec::arg).arg(arg);
+    }
+
+    /// Remove a global option by name (e.g. to free `-v` for commands).
+    pub fn remove(&mut self, name: &str) {
+        let rest = self.spec.args().iter().filter(|a| a.name != name).cloned();
+        self.spec = rest.fold(CommandSpec::new(), CommandSpec::arg);
+    }
+
+    /// The options as a spec (for help and usage lines).
+    pub fn spec(&self) -> &CommandSpec {
+        &self.spec
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.spec.args().is_empty()
+    }
+
+    /// Remove global options from `args` (up to a `--` separator) and parse
+    /// them. Short flags may be clustered (`-vq`) when every letter is a
+    /// global flag; anything else is left for the command.
+    pub fn extract(&self, args: &mut Vec<String>) -> Result<ParsedArgs, ModCliError> {
+        self.extract_for(args, |_| None)
+    }
+
+    /// Like `extract`, but options declared by the command's own spec are
+    /// left in place after the command word (the first argument that isn't
+    /// a global option). `command_spec` is called once, with the arguments
+    /// from the command word on.
+    pub fn extract_for<F>(
+        &self,
+        args: &mut Vec<String>,
+        command_spec: F,
+    ) -> Result<ParsedArgs, ModCliError>
+    where
+        F: FnOnce(&[String]) -> Option<CommandSpec>,
+    {
+        let mut command_spec = Some(command_spec);
+        let mut own: Option<CommandSpec> = None;
+        let mut taken: Vec<String> = Vec::new();
+        let mut i = 0;
+        while i < args.len() {
+            let tok = args[i].as_str();
+            if tok == "--" {
+                break;
+            }
+            if let Some(takes_value) = own.as_ref().and_then(|spec| declared_by(spec, tok)) {
+                i += 1 + usize::from(takes_value);
+                continue;
+            }
+            let width = match self.claims(tok) {
+                Some(takes_value) => 1 + usize::from(takes_value && i + 1 < args.len()),
+                None => 0,
+            };
+            if width == 0 {
+                if let Some(resolve) = command_spec.take() {
+                    own = resolve(&args[i..]);
+                }
+                i += 1;
+                continue;
+            }
+            taken.extend(args.drain(i..i + width));
+        }
+        self.spec.parse(&taken)
+    }
+
+    // Some(takes_next_value) when `tok` is a global option.
+    fn claims(&self, tok: &str) -> Option<bool> {
+        if let Some(body) = tok.strip_prefix("--") {
+            let (key, inline) = split_long(body);
+            let spec = self.spec.find_long(key)?;
+            return Some(spec.kind == ArgKind::Option && !inline);
+        }
+        let body = tok.strip_prefix('-').filter(|b| !b.is_empty())?;
+        let first = body.chars().next()?;
+        let spec = self.spec.find_short(first)?;
+        let rest = &body[first.len_utf8()..];
+        if spec.kind == ArgKind::Option {
+            return Some(rest.is_empty());
+        }
+        let clustered = rest.strip_prefix('=').is_some()
+            || rest.chars().all(|c| {
+                self.spec
+                    .find_short(c)
+                    .is_some_and(|s| s.kind == ArgKind::Flag)
+            });
+        clustered.then_some(false)
+    }
+}
+
+// `key=value` -> (`key`, true); `key` -> (`key`, false).
+fn split_long(body: &str) -> (&str, bool) {
+    match body.split_once('=') {
+        Some((k, _)) => (k, true),
+        None => (body, false),
+    }
+}
+
+// Some(takes_next_value) when a command's `spec` declares `tok`: a long
+// option, or a short cluster containing any of its letters.
+fn declared_by(spec: &CommandSpec, tok: &str) -> Option<bool> {
+    if let Some(body) = tok.strip_prefix("--") {
+        let (key, inline) = split_long(body);
+        let arg = spec.find_long(key)?;
+        return Some(arg.kind == ArgKind::Option && !inline);
+    }
+    let body = tok.strip_prefix('-').filter(|b| !b.is_empty())?;
+    let first = body.chars().next()?;
+    if let Some(arg) = spec.find_short(first) {
+        let rest = &body[first.len_utf8()..];
+        return Some(arg.kind == ArgKind::Option && rest.is_empty());
+    }
+    body.chars()
+        .any(|c| spec.find_short(c).is_some())
+        .then_some(false)
+}
+
+/// Sets the process-wide verbosity (`-1` quiet, `0` normal, `n` for `-v` × n).
+pub fn set_verbosity(level: i8) {
+    if !testing::update_settings(|s| s.verbosity = level) {
+        VERBOSITY.store(level, Ordering::SeqCst);
+    }
+}
+
+/// Process-wide verbosity set from `--quiet` / `--verbose`.
+pub fn verbosity() -> i8 {
+    match testing::settings() {
+        Some(settings) => settings.verbosity,
+        None => VERBOSITY.load(Ordering::SeqCst),
+    }
+}
+
+/// True when `--quiet` was given.
+pub fn is_quiet() -> bool {
+    verbosity() < 0
+}
+
+/// Process-wide settings `apply` changes: color choice, verbosity and
+/// output mode. `ModCli` snapshots them before a run and restores them after
+/// it, so `--output json` or `-q` apply to one invocation only.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Settings {
+    pub(crate) color: ColorChoice,
+    pub(crate) verbosity: i8,
+    pub(crate) output: OutputMode,
+}
+
+impl Settings {
+    pub(crate) fn current() -> Self {
+        Self {
+            color: color_policy::choice(),
+            verbosity: verbosity(),
+            output: mode::output_mode(),
+        }
+    }
+
+    pub(crate) fn restore(self) {
+        color_policy::set_choice(self.color);
+        set_verbosity(self.verbosity);
+        mode::set_output_mode(self.output);
+    }
+}
+
+/// Apply the standard options present in `parsed`: output mode, color
+/// choice and verbosity. Options that weren't given leave the current
+/// setting alone.
+pub fn apply(parsed: &ParsedArgs) -> Result<(), ModCliError> {
+    if let Some(output) = parsed.get_str("output") {
+        mode::set_output_mode(output.parse()?);
+    }
+    match parsed.get_str("color") {
+        Some("auto") => color_policy::set_choice(ColorChoice::Auto),
+        Some("always") => color_policy::set_choice(ColorChoice::Always),
+        Some("never") => color_policy::set_choice(ColorChoice::Never),
+        _ => {}
+    }
+    if parsed.flag("quiet") {
+        set_verbosity(-1);
+    } else if parsed.count("verbose") > 0 {
+        set_verbosity(parsed.count("verbose").min(i8::MAX as usize) as i8);
+    }
+    Ok(())
+}
@@ -0,0 +1,460 @@
+//! Help rendering for commands and command listings.
+//!
+//! `help <command>`, `<command> --help` and bare groups render through
+//! [`HelpRenderer`]. A command page is built from `Command::help`,
+//! `long_help`, `usage` (or `spec()`), `examples`, `aliases` and `see_also`:
+//!
+//! ```text
+//! deploy
+//! Deploy a release
+//!
+//! USAGE:
+//!   deploy <target> [--port <PORT>]
+//!
+//! ARGS:
+//!   <target>             Deployment target
+//!
+//! OPTIONS:
+//!   -p, --port <PORT>    Port [default: 8080]
+//! ```
+//!
+//! Text is wrapped to the terminal width (or `COLUMNS`, default 80).
+//! Listings show aliases next to names and group nodes under their
+//! `Command::category` / `Group::category` headings, in the registry's
+//! `HelpOrder`.
+//!
+//! Section titles are `output::messages` keys, so they can be renamed or
+//! translated: `help.usage`, `help.arguments`, `help.options`,
+//! `help.global_options`, `help.examples`, `help.aliases`, `help.see_also`,
+//! `help.commands`, `help.no_description`, plus the existing `help.header`,
+//! `help.ns_header` and `help.footer`. Titles and names are colored with the
+//! theme's `help_heading` and `help_name` styles when colors are enabled.
+//!
+//! # Example
+//! ```
+//! use modcli::command::Command;
+//! use modcli::help::HelpRenderer;
+//! use modcli::loader::CommandRegistry;
+//!
+//! struct Deploy;
+//!
+//! impl Command for Deploy {
+//!     fn name(&self) -> &str { "deploy" }
+//!     fn help(&self) -> Option<&str> { Some("Deploy a release") }
+//!     fn usage(&self) -> Option<&str> { Some("deploy <target>") }
+//!     fn examples(&self) -> &[(&str, &str)] { &[("deploy prod", "Deploy to production")] }
+//!     fn execute(&self, _args: &[String]) {}
+//! }
+//!
+//! let mut reg = CommandRegistry::new();
+//! reg.register(Box::new(Deploy));
+//! let page = HelpRenderer::new().width(60).color(false).command(&reg, "deploy").unwrap();
+//! assert!(page.contains("USAGE:\n  deploy <target>\n"));
+//! assert!(page.contains("EXAMPLES:\n  deploy prod\n      Deploy to production\n"));
+//! ```
+
+use crate::loader::CommandRegistry;
+use crate::output::{color_policy, markdown, messages, themes};
+use crate::spec::{ArgKind, ArgSpec, CommandSpec};
+use crate::tree::{alias_path, display_path, HelpOrder};
+use console::measure_text_width;
+use crossterm::style::Attribute;
+use terminal_size::{terminal_size, Width};
+
+const INDENT: usize = 2;
+const GAP: usize = 2;
+const MAX_LABEL: usize = 28;
+const MIN_WIDTH: usize = 40;
+
+/// Renders help pages and listings as text.
+#[derive(Clone, Debug)]
+pub struct HelpRenderer {
+    width: usize,
+    color: bool,
+}
+
+impl Default for HelpRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HelpRenderer {
+    /// Renderer for the current terminal: its width and color support.
+    pub fn new() -> Self {
+        Self {
+            width: terminal_width(),
+            color: color_policy::enabled(),
+        }
+    }
+
+    /// Wrap to `width` columns (at least 40).
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = width.max(MIN_WIDTH);
+        self
+    }
+
+    /// Enable or disable styling (headings, names and Markdown).
+    pub fn color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Full help page for the visible command at `path`, or None.
+    pub fn command(&self, registry: &CommandRegistry, path: &str) -> Option<String> {
+        let cmd = registry.get(path).filter(|c| registry.is_visible(*c))?;
+        let display = display_path(cmd.name());
+        let mut out = String::new();
+
+        out.push_str(&self.name(&display));
+        out.push('\n');
+        let summary = cmd
+            .help()
+            .map(str::to_string)
+            .unwrap_or_else(no_description);
+        out.push_str(&self.text(&summary, 0));
+        if let Some(dep) = cmd.deprecated() {
+            out.push_str(&self.text(&dep.message(cmd.name()), 0));
+        }
+
+        let spec = cmd.spec();
+        let usage = match (cmd.usage(), &spec) {
+            (Some(usage), _) => Some(usage.to_string()),
+            (None, Some(spec)) => Some(format!("{display} {}", spec.usage()).trim().to_string()),
+            (None, None) => None,
+        };
+        if let Some(usage) = usage {
+            self.section(&mut out, "help.usage", "USAGE:");
+            out.push_str(&self.text(&usage, INDENT));
+        }
+        if let Some(long) = cmd.long_help() {
+            out.push('\n');
+            out.push_str(&self.text(long, 0));
+        }
+        if let Some(spec) = &spec {
+            self.spec_sections(
+                &mut out,
+                spec,
+                "help.arguments",
+                "ARGS:",
+                "help.options",
+                "OPTIONS:",
+            );
+        }
+        if !cmd.examples().is_empty() {
+            self.section(&mut out, "help.examples", "EXAMPLES:");
+            for (line, about) in cmd.examples() {
+                out.push_str(&self.text(line, INDENT));
+                if !about.is_empty() {
+                    out.push_str(&self.text(about, INDENT * 3));
+                }
+            }
+        }
+        if !cmd.aliases().is_empty() {
+            let aliases: Vec<String> = cmd
+                .aliases()
+                .iter()
+                .filter_map(|a| {
+                    let full = alias_path(cmd.name(), a);
+                    let shown = display_path(&full);
+                    match registry.deprecation(&full) {
+                        None => Some(shown),
+                        Some(_) if registry.list_deprecated() => {
+                            Some(format!("{shown} (deprecated)"))
+                        }
+                        Some(_) => None,
+                    }
+                })
+                .collect();
+            if !aliases.is_empty() {
+                self.section(&mut out, "help.aliases", "ALIASES:");
+                out.push_str(&self.text(&aliases.join(", "), INDENT));
+            }
+        }
+        if !cmd.see_also().is_empty() {
+            self.section(&mut out, "help.see_also", "SEE ALSO:");
+            out.push_str(&self.text(&cmd.see_also().join(", "), INDENT));
+        }
+        if registry.is_group_visible(path) {
+            self.section(&mut out, "help.commands", "COMMANDS:");
+            self.children(&mut out, registry, path);
+        }
+        Some(out)
+    }
+
+    /// Listing of the visible children of `path`; the empty path renders the
+    /// top-level help (with global options), a group path its group help.
+    pub fn listing(&self, registry: &CommandRegistry, path: &str) -> String {
+        let mut out = String::new();
+        if path.is_empty() {
+            out.push_str(&messages::message_or_default("help.header", "Help:"));
+            out.push('\n');
+        } else {
+            let fallback = format!("Help ({}):", display_path(path));
+            out.push_str(&messages::message_or_default("help.ns_header", &fallback));
+            out.push('\n');
+            if let Some(help) = registry.group(path).and_then(|g| g.help.as_deref()) {
+                out.push_str(&self.text(help, 0));
+            }
+        }
+        self.children(&mut out, registry, path);
+
+        let globals = registry.global_options().spec();
+        if path.is_empty() && !globals.args().is_empty() {
+            self.spec_sections(
+                &mut out,
+                globals,
+                "help.arguments",
+                "ARGS:",
+                "help.global_options",
+                "GLOBAL OPTIONS:",
+            );
+        }
+        if path.is_empty() {
+            if let Some(footer) = messages::get_message("help.footer") {
+                out.push('\n');
+                out.push_str(&footer);
+                out.push('\n');
+            }
+        }
+        out
+    }
+
+    // Children of `path`: uncategorized first, then one titled block per
+    // category, all aligned to one column.
+    fn children(&self, out: &mut String, registry: &CommandRegistry, path: &str) {
+        let children = registry.children(path);
+        let mut categories: Vec<Option<&str>> = Vec::new();
+        for child in &children {
+            let category = registry.node_category(child);
+            if !categories.contains(&category) {
+                categories.push(category);
+            }
+        }
+        match registry.help_order() {
+            HelpOrder::Alphabetical => categories.sort(),
+            HelpOrder::Registration => categories.sort_by_key(|c| c.is_some()),
+        }
+
+        let rows: Vec<(Option<&str>, (String, String))> = children
+            .iter()
+            .map(|child| {
+                (
+                    registry.node_category(child),
+                    self.child_row(registry, child),
+                )
+            })
+            .collect();
+        let label_width = label_width(rows.iter().map(|(_, row)| row));
+        for category in categories {
+            if let Some(title) = category {
+                out.push('\n');
+                out.push_str(&self.heading(&format!("{title}:")));
+                out.push('\n');
+            }
+            let block: Vec<(String, String)> = rows
+                .iter()
+                .filter(|(c, _)| *c == category)
+                .map(|(_, row)| row.clone())
+                .collect();
+            out.push_str(&self.aligned_rows(&block, label_width));
+        }
+    }
+
+    // `name, alias ...` label (`...` marks groups) and description.
+    fn child_row(&self, registry: &CommandRegistry, child: &str) -> (String, String) {
+        let seg = child.rsplit(':').next().unwrap_or(child);
+        let mut label = std::iter::once(seg.to_string())
+            .chain(registry.node_aliases(child))
+            .collect::<Vec<_>>()
+            .join(", ");
+        if registry.get(child).is_none() {
+            label.push_str(" ...");
+        }
+        let mut about = registry
+            .node_help(child)
+            .map(str::to_string)
+            .unwrap_or_else(no_description);
+        if let Some(dep) = registry.get(child).and_then(|c| c.deprecated()) {
+            about.push_str(&format!(" [{}]", dep.label()));
+        }
+        (self.name(&label), about)
+    }
+
+    fn spec_sections(
+        &self,
+        out: &mut String,
+        spec: &CommandSpec,
+        args_key: &str,
+        args_title: &str,
+        opts_key: &str,
+        opts_title: &str,
+    ) {
+        let positionals: Vec<_> = spec.positionals().map(|a| self.arg_row(a)).collect();
+        if !positionals.is_empty() {
+            self.section(out, args_key, args_title);
+            out.push_str(&self.rows(&positionals));
+        }
+        let options: Vec<_> = spec.options().map(|a| self.arg_row(a)).collect();
+        if !options.is_empty() {
+            self.section(out, opts_key, opts_title);
+            out.push_str(&self.rows(&options));
+        }
+    }
+
+    fn arg_row(&self, arg: &ArgSpec) -> (String, String) {
+        let mut label = arg.display_name();
+        if let (Some(s), Some(_)) = (arg.short, &arg.long) {
+            label = format!("-{s}, {label}");
+        }
+        if arg.kind == ArgKind::Option {
+            label.push_str(&format!(" <{}>", arg.placeholder()));
+        }
+        let mut about = arg.help.clone().unwrap_or_default();
+        if let Some(ref d) = arg.default {
+            about.push_str(&format!(" [default: {d}]"));
+        }
+        if !arg.choices.is_empty() {
+            about.push_str(&format!(" [possible values: {}]", arg.choices.join(", ")));
+        }
+        (self.name(&label), about.trim().to_string())
+    }
+
+    // Blank line and a section title.
+    fn section(&self, out: &mut String, key: &str, default: &str) {
+        let title = messages::message_or_default(key, default);
+        out.push('\n');
+        out.push_str(&self.heading(&title));
+        out.push('\n');
+    }
+
+    // Two-column rows: labels padded to a shared width, descriptions wrapped
+    // beside them (or below labels that are too long).
+    fn rows(&self, rows: &[(String, String)]) -> String {
+        self.aligned_rows(rows, label_width(rows.iter()))
+    }
+
+    fn aligned_rows(&self, rows: &[(String, String)], label_width: usize) -> String {
+        let column = INDENT + label_width + GAP;
+        let mut out = String::new();
+        for (label, about) in rows {
+            let width = measure_text_width(label);
+            out.push_str(&" ".repeat(INDENT));
+            out.push_str(label);
+            let lines = self.wrap_text(about, self.width.saturating_sub(column));
+            let mut lines = lines.into_iter();
+            if width <= label_width {
+                if let Some(first) = lines.next() {
+                    out.push_str(&" ".repeat(label_width - width + GAP));
+                    out.push_str(&first);
+                }
+            }
+            out.push('\n');
+            for line in lines {
+                out.push_str(&" ".repeat(column));
+                out.push_str(&line);
+                out.push('\n');
+            }
+        }
+        out
+    }
+
+    // Markdown text, wrapped and indented.
+    fn text(&self, text: &str, indent: usize) -> String {
+        let mut out = String::new();
+        for line in self.wrap_text(text, self.width.saturating_sub(indent)) {
+            if !line.is_empty() {
+                out.push_str(&" ".repeat(indent));
+            }
+            out.push_str(&line);
+            out.push('\n');
+        }
+        out
+    }
+
+    fn wrap_text(&self, text: &str, width: usize) -> Vec<String> {
+        let rendered = markdown::render_markdown(text);
+        let rendered = if self.color {
+            rendered
+        } else {
+            console::strip_ansi_codes(&rendered).into_owned()
+        };
+        rendered
+            .lines()
+            .flat_map(|line| wrap(line, width.max(MIN_WIDTH / 2)))
+            .collect()
+    }
+
+    fn heading(&self, text: &str) -> String {
+        if self.color {
+            let color = themes::current_theme().get_log_color("help_heading");
+            color_policy::paint(text, Some(color), None, &[Attribute::Bold])
+        } else {
+            text.to_string()
+        }
+    }
+
+    fn name(&self, text: &str) -> String {
+        if self.color {
+            let color = themes::current_theme().get_log_color("help_name");
+            color_policy::fg(text, color)
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+// Widest label that still fits the label column.
+fn label_width<'a>(rows: impl Iterator<Item = &'a (String, String)>) -> usize {
+    rows.map(|(label, _)| measure_text_width(label))
+        .filter(|w| *w <= MAX_LABEL)
+        .max()
+        .unwrap_or(0)
+}
+
+/// Terminal width in columns: `COLUMNS`, then the terminal size, then 80.
+pub fn terminal_width() -> usize {
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|c| c.parse().ok())
+        .or_else(|| terminal_size().map(|(Width(w), _)| w as usize))
+        .unwrap_or(80)
+        .max(MIN_WIDTH)
+}
+
+/// One-line summary of a help text, as shown in completion menus and man page
+/// NAME sections: the first non-blank line without markdown heading or list
+/// markers, `*` or backticks. `None` when there is no such line.
+pub fn summary(help: Option<&str>) -> Option<String> {
+    let line = help?.lines().map(str::trim).find(|l| !l.is_empty())?;
+    Some(
+        line.trim_start_matches(['#', '-', ' '])
+            .replace(['*', '`'], ""),
+    )
+}
+
+fn no_description() -> String {
+    messages::message_or_default("help.no_description", "No description").into_owned()
+}
+
+// Word-wrap one line (ANSI-aware); leading indentation is kept on every line.
+fn wrap(line: &str, width: usize) -> Vec<String> {
+    let indent: String = line.chars().take_while(|c| *c == ' ').collect();
+    let mut lines = Vec::new();
+    let mut cur = String::new();
+    for word in line.split_whitespace() {
+        let candidate = if cur.is_empty() {
+            format!("{indent}{word}")
+        } else {
+            format!("{cur} {word}")
+        };
+        if measure_text_width(&candidate) > width && !cur.is_empty() {
+            lines.push(std::mem::take(&mut cur));
+            cur = format!("{indent}{word}");
+        } else {
+            cur = candidate;
+        }
+    }
+    lines.push(cur);
+    lines
+}
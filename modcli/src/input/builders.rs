@@ -1,13 +1,15 @@
-use crate::output::hook;
+use crate::output::color_policy::{fg, paint};
+use crate::output::sink::{self, Stream};
 use crate::output::themes::current_theme;
-use crossterm::style::{Color, Stylize};
+use crate::output::{hook, print};
+use crossterm::style::{Attribute, Color};
 use crossterm::{
     cursor,
-    event::{self, Event, KeyCode},
+    event::{self, Event, KeyCode, KeyEvent, KeyModifiers},
     execute, terminal,
 };
 use std::collections::HashSet;
-use std::io::{stdin, stdout, Write};
+use std::io::Write;
 
 // Reduce type complexity for validator closures used by text/number inputs
 type TextValidator = dyn Fn(&str) -> Result<(), String> + Send + Sync;
@@ -59,22 +61,20 @@ impl<'a> TextInput<'a> {
                 .as_deref()
                 .map(|d| format!(" [{d}]"))
                 .unwrap_or_default();
-            print!("{}{} ", self.label, hint);
-            if let Err(e) = stdout().flush() {
-                hook::warn(&format!("flush failed: {e}"));
-            }
+            print::write(&format!("{}{} ", self.label, hint));
+            sink::flush();
 
             let input = if self.mask.is_some() {
                 // Basic masked input without backspace handling for now
                 let mut s = String::new();
-                if let Err(e) = stdin().read_line(&mut s) {
+                if let Err(e) = crate::input::read_line(&mut s) {
                     hook::error(&format!("failed to read: {e}"));
                     return Err("read error".into());
                 }
                 s
             } else {
                 let mut s = String::new();
-                if let Err(e) = stdin().read_line(&mut s) {
+                if let Err(e) = crate::input::read_line(&mut s) {
                     hook::error(&format!("failed to read: {e}"));
                     return Err("read error".into());
                 }
@@ -164,13 +164,11 @@ impl<'a> NumberInput<'a> {
     pub fn get(self) -> Result<f64, String> {
         loop {
             let hint = self.default.map(|d| format!(" [{d}]")).unwrap_or_default();
-            print!("{}{} ", self.label, hint);
-            if let Err(e) = stdout().flush() {
-                hook::warn(&format!("flush failed: {e}"));
-            }
+            print::write(&format!("{}{} ", self.label, hint));
+            sink::flush();
 
             let mut s = String::new();
-            if let Err(e) = stdin().read_line(&mut s) {
+            if let Err(e) = crate::input::read_line(&mut s) {
                 hook::error(&format!("failed to read: {e}"));
                 return Err("read error".into());
             }
@@ -236,12 +234,10 @@ impl<'a> ConfirmInput<'a> {
     }
     pub fn get(self) -> bool {
         let hint = if self.default_yes { "[Y/n]" } else { "[y/N]" };
-        print!("{} {} ", self.label, hint);
-        if let Err(e) = stdout().flush() {
-            hook::warn(&format!("flush failed: {e}"));
-        }
+        print::write(&format!("{} {} ", self.label, hint));
+        sink::flush();
         let mut s = String::new();
-        if let Err(e) = stdin().read_line(&mut s) {
+        if let Err(e) = crate::input::read_line(&mut s) {
             hook::warn(&format!("failed to read: {e}"));
             return self.default_yes;
         }
@@ -275,22 +271,20 @@ impl<'a> SelectInput<'a> {
         self
     }
     pub fn get(self) -> Result<usize, String> {
-        println!("{}", self.label);
+        print::line(self.label);
         for (i, it) in self.items.iter().enumerate() {
-            println!("  {}. {}", i + 1, it);
+            print::line(&format!("  {}. {}", i + 1, it));
         }
-        print!(
+        print::write(&format!(
             "Enter choice [1-{}]{}: ",
             self.items.len(),
             self.initial
                 .map(|i| format!(" (default {})", i + 1))
                 .unwrap_or_default()
-        );
-        if let Err(e) = stdout().flush() {
-            hook::warn(&format!("flush failed: {e}"));
-        }
+        ));
+        sink::flush();
         let mut s = String::new();
-        if let Err(e) = stdin().read_line(&mut s) {
+        if let Err(e) = crate::input::read_line(&mut s) {
             return Err(format!("read error: {e}"));
         }
         let trimmed = s.trim();
@@ -321,17 +315,15 @@ pub struct MultiSelectInput<'a> {
 
 impl<'a> MultiSelectInput<'a> {
     pub fn get(self) -> Result<Vec<usize>, String> {
-        println!("{}", self.label);
+        print::line(self.label);
         for (i, it) in self.items.iter().enumerate() {
-            println!("  {}. {}", i + 1, it);
-        }
-        println!("Enter comma-separated indexes (e.g., 1,3,4) or empty for none:");
-        print!("> ");
-        if let Err(e) = stdout().flush() {
-            hook::warn(&format!("flush failed: {e}"));
+            print::line(&format!("  {}. {}", i + 1, it));
         }
+        print::line("Enter comma-separated indexes (e.g., 1,3,4) or empty for none:");
+        print::write("> ");
+        sink::flush();
         let mut s = String::new();
-        if let Err(e) = stdin().read_line(&mut s) {
+        if let Err(e) = crate::input::read_line(&mut s) {
             return Err(format!("read error: {e}"));
         }
         let trimmed = s.trim();
@@ -373,25 +365,23 @@ impl<'a> ButtonsInput<'a> {
         self
     }
     pub fn get(self) -> usize {
-        println!("{}", self.label);
-        println!(
-            "{}",
-            self.buttons
+        print::line(self.label);
+        print::line(
+            &self
+                .buttons
                 .iter()
                 .map(|(t, k)| format!("[{k}] {t}"))
                 .collect::<Vec<_>>()
-                .join("  ")
+                .join("  "),
         );
         let default_hint = self
             .default
             .and_then(|i| self.buttons.get(i).map(|(_, k)| format!(" (default {k})")))
             .unwrap_or_default();
-        print!("Choose by hotkey{default_hint}: ");
-        if let Err(e) = stdout().flush() {
-            hook::warn(&format!("flush failed: {e}"));
-        }
+        print::write(&format!("Choose by hotkey{default_hint}: "));
+        sink::flush();
         let mut s = String::new();
-        if let Err(e) = stdin().read_line(&mut s) {
+        if let Err(e) = crate::input::read_line(&mut s) {
             hook::warn(&format!("read error: {e}"));
             return self.default.unwrap_or(0);
         }
@@ -435,6 +425,11 @@ pub struct KeyMap {
     pub toggle_char: char, // for multi-select
 }
 
+// Raw mode swallows SIGINT; Ctrl-C arrives as a key press instead.
+fn is_ctrl_c(k: &KeyEvent) -> bool {
+    k.code == KeyCode::Char('c') && k.modifiers.contains(KeyModifiers::CONTROL)
+}
+
 impl Default for KeyMap {
     fn default() -> Self {
         Self {
@@ -471,32 +466,35 @@ impl<'a> RawSelectInput<'a> {
         self
     }
     pub fn get(self) -> Option<usize> {
-        let mut stdout = stdout();
+        let mut out = sink::writer(Stream::Out);
         let mut selected = self.initial;
         if terminal::enable_raw_mode().is_err() {
             return None;
         }
-        let _ = execute!(stdout, terminal::Clear(terminal::ClearType::All));
+        let _ = execute!(out, terminal::Clear(terminal::ClearType::All));
         loop {
-            let _ = execute!(stdout, cursor::MoveTo(0, 0));
-            println!("{}\n", self.label);
+            let _ = execute!(out, cursor::MoveTo(0, 0));
+            print::line(&format!("{}\n", self.label));
             for (i, it) in self.items.iter().enumerate() {
                 if i == selected {
-                    println!("  > {it}");
+                    print::line(&format!("  > {it}"));
                 } else {
-                    println!("    {it}");
+                    print::line(&format!("    {it}"));
                 }
             }
-            let _ = stdout.flush();
+            let _ = out.flush();
             if let Ok(Event::Key(k)) = event::read() {
+                if is_ctrl_c(&k) {
+                    let _ = terminal::disable_raw_mode();
+                    crate::signals::interrupt();
+                    return None;
+                }
                 match k.code {
                     c if c == self.keymap.up => {
                         selected = selected.saturating_sub(1);
                     }
-                    c if c == self.keymap.down => {
-                        if selected + 1 < self.items.len() {
-                            selected += 1;
-                        }
+                    c if c == self.keymap.down && selected + 1 < self.items.len() => {
+                        selected += 1;
                     }
                     c if c == self.keymap.confirm => {
                         let _ = terminal::disable_raw_mode();
@@ -542,37 +540,40 @@ impl<'a> RawMultiSelectInput<'a> {
         self
     }
     pub fn get(self) -> Option<Vec<usize>> {
-        let mut stdout = stdout();
+        let mut out = sink::writer(Stream::Out);
         let mut cursor_idx = self.initial;
         let mut picked: Vec<bool> = vec![false; self.items.len()];
         if terminal::enable_raw_mode().is_err() {
             return None;
         }
-        let _ = execute!(stdout, terminal::Clear(terminal::ClearType::All));
+        let _ = execute!(out, terminal::Clear(terminal::ClearType::All));
         loop {
-            let _ = execute!(stdout, cursor::MoveTo(0, 0));
-            println!(
+            let _ = execute!(out, cursor::MoveTo(0, 0));
+            print::line(&format!(
                 "{}\n(space=toggle, enter=confirm, esc=cancel)\n",
                 self.label
-            );
+            ));
             for (i, it) in self.items.iter().enumerate() {
                 let mark = if picked[i] { "[x]" } else { "[ ]" };
                 if i == cursor_idx {
-                    println!("  > {mark} {it}");
+                    print::line(&format!("  > {mark} {it}"));
                 } else {
-                    println!("    {mark} {it}");
+                    print::line(&format!("    {mark} {it}"));
                 }
             }
-            let _ = stdout.flush();
+            let _ = out.flush();
             if let Ok(Event::Key(k)) = event::read() {
+                if is_ctrl_c(&k) {
+                    let _ = terminal::disable_raw_mode();
+                    crate::signals::interrupt();
+                    return None;
+                }
                 match k.code {
                     c if c == self.keymap.up => {
                         cursor_idx = cursor_idx.saturating_sub(1);
                     }
-                    c if c == self.keymap.down => {
-                        if cursor_idx + 1 < self.items.len() {
-                            cursor_idx += 1;
-                        }
+                    c if c == self.keymap.down && cursor_idx + 1 < self.items.len() => {
+                        cursor_idx += 1;
                     }
                     KeyCode::Char(c) if c == self.keymap.toggle_char => {
                         if let Some(p) = picked.get_mut(cursor_idx) {
@@ -638,11 +639,11 @@ impl<'a> RawPagedSelectInput<'a> {
         if self.items.is_empty() {
             return None;
         }
-        let mut stdout = stdout();
+        let mut out = sink::writer(Stream::Out);
         if terminal::enable_raw_mode().is_err() {
             return None;
         }
-        let _ = execute!(stdout, terminal::Clear(terminal::ClearType::All));
+        let _ = execute!(out, terminal::Clear(terminal::ClearType::All));
         let mut query = String::new();
         loop {
             // Filter
@@ -668,14 +669,14 @@ impl<'a> RawPagedSelectInput<'a> {
             let start = page * self.page_size;
             let end = (start + self.page_size).min(filtered.len());
 
-            let _ = execute!(stdout, cursor::MoveTo(0, 0));
-            println!("{}\n", self.label);
-            println!("Search: {}\n", query.as_str().with(Color::DarkGrey));
-            println!(
+            let _ = execute!(out, cursor::MoveTo(0, 0));
+            print::line(&format!("{}\n", self.label));
+            print::line(&format!("Search: {}\n", fg(&query, Color::DarkGrey)));
+            print::line(&format!(
                 "Page {}/{}\n",
                 if total_pages == 0 { 0 } else { page + 1 },
                 total_pages
-            );
+            ));
             let theme = current_theme();
             let sel_bg = theme.get_log_color("menu_selected_bg");
             let sel_fg = theme.get_log_color("menu_selected_fg");
@@ -683,28 +684,39 @@ impl<'a> RawPagedSelectInput<'a> {
             for (row, fi) in (start..end).enumerate().map(|(row, i)| (row, filtered[i])) {
                 let label = &self.items[fi];
                 if (start + row) == self.cursor {
-                    let line = format!("  > {label}").with(sel_fg).on(sel_bg).bold();
-                    println!("{line}");
+                    let line = paint(
+                        &format!("  > {label}"),
+                        Some(sel_fg),
+                        Some(sel_bg),
+                        &[Attribute::Bold],
+                    );
+                    print::line(&line);
                 } else if row % 2 == 1 {
-                    println!("{}", format!("    {label}").with(stripe_fg));
+                    print::line(&fg(&format!("    {label}"), stripe_fg));
                 } else {
-                    println!("    {label}");
+                    print::line(&format!("    {label}"));
                 }
             }
-            println!(
-                "\nKeys: ↑/↓ PgUp/PgDn Home/End Enter Esc  (type to search, Backspace clears)"
+            print::line(
+                "\nKeys: ↑/↓ PgUp/PgDn Home/End Enter Esc  (type to search, Backspace clears)",
             );
-            let _ = stdout.flush();
+            let _ = out.flush();
 
             if let Ok(Event::Key(k)) = event::read() {
+                if is_ctrl_c(&k) {
+                    let _ = terminal::disable_raw_mode();
+                    crate::signals::interrupt();
+                    return None;
+                }
                 match k.code {
                     c if c == self.keymap.up => {
                         self.cursor = self.cursor.saturating_sub(1);
                     }
-                    c if c == self.keymap.down => {
-                        if !filtered.is_empty() && self.cursor + 1 < filtered.len() {
-                            self.cursor += 1;
-                        }
+                    c if c == self.keymap.down
+                        && !filtered.is_empty()
+                        && self.cursor + 1 < filtered.len() =>
+                    {
+                        self.cursor += 1;
                     }
                     c if c == self.keymap.page_up => {
                         if self.cursor >= self.page_size {
@@ -786,11 +798,11 @@ impl<'a> RawPagedMultiSelectInput<'a> {
         if self.items.is_empty() {
             return Some(Vec::new());
         }
-        let mut stdout = stdout();
+        let mut out = sink::writer(Stream::Out);
         if terminal::enable_raw_mode().is_err() {
             return None;
         }
-        let _ = execute!(stdout, terminal::Clear(terminal::ClearType::All));
+        let _ = execute!(out, terminal::Clear(terminal::ClearType::All));
         let mut query = String::new();
         loop {
             // Filter
@@ -816,14 +828,14 @@ impl<'a> RawPagedMultiSelectInput<'a> {
             let start = page * self.page_size;
             let end = (start + self.page_size).min(filtered.len());
 
-            let _ = execute!(stdout, cursor::MoveTo(0, 0));
-            println!("{}\n(space=toggle)\n", self.label);
-            println!("Search: {}\n", query.as_str().with(Color::DarkGrey));
-            println!(
+            let _ = execute!(out, cursor::MoveTo(0, 0));
+            print::line(&format!("{}\n(space=toggle)\n", self.label));
+            print::line(&format!("Search: {}\n", fg(&query, Color::DarkGrey)));
+            print::line(&format!(
                 "Page {}/{}\n",
                 if total_pages == 0 { 0 } else { page + 1 },
                 total_pages
-            );
+            ));
             let theme = current_theme();
             let sel_bg = theme.get_log_color("menu_selected_bg");
             let sel_fg = theme.get_log_color("menu_selected_fg");
@@ -836,26 +848,37 @@ impl<'a> RawPagedMultiSelectInput<'a> {
                 };
                 let label = &self.items[fi];
                 if (start + row) == self.cursor {
-                    let line = format!("  > {mark} {label}").with(sel_fg).on(sel_bg).bold();
-                    println!("{line}");
+                    let line = paint(
+                        &format!("  > {mark} {label}"),
+                        Some(sel_fg),
+                        Some(sel_bg),
+                        &[Attribute::Bold],
+                    );
+                    print::line(&line);
                 } else if row % 2 == 1 {
-                    println!("{}", format!("    {mark} {label}").with(stripe_fg));
+                    print::line(&fg(&format!("    {mark} {label}"), stripe_fg));
                 } else {
-                    println!("    {mark} {label}");
+                    print::line(&format!("    {mark} {label}"));
                 }
             }
-            println!("\nKeys: ↑/↓ PgUp/PgDn Home/End Space Enter Esc  (type to search, Backspace clears)");
-            let _ = stdout.flush();
+            print::line("\nKeys: ↑/↓ PgUp/PgDn Home/End Space Enter Esc  (type to search, Backspace clears)");
+            let _ = out.flush();
 
             if let Ok(Event::Key(k)) = event::read() {
+                if is_ctrl_c(&k) {
+                    let _ = terminal::disable_raw_mode();
+                    crate::signals::interrupt();
+                    return None;
+                }
                 match k.code {
                     c if c == self.keymap.up => {
                         self.cursor = self.cursor.saturating_sub(1);
                     }
-                    c if c == self.keymap.down => {
-                        if !filtered.is_empty() && self.cursor + 1 < filtered.len() {
-                            self.cursor += 1;
-                        }
+                    c if c == self.keymap.down
+                        && !filtered.is_empty()
+                        && self.cursor + 1 < filtered.len() =>
+                    {
+                        self.cursor += 1;
                     }
                     c if c == self.keymap.page_up => {
                         if self.cursor >= self.page_size {
@@ -1074,17 +1097,17 @@ impl<'a> RawButtonsInput<'a> {
         if self.buttons.is_empty() {
             return None;
         }
-        let mut stdout = stdout();
+        let mut out = sink::writer(Stream::Out);
         if terminal::enable_raw_mode().is_err() {
             return None;
         }
-        let _ = execute!(stdout, terminal::Clear(terminal::ClearType::All));
+        let _ = execute!(out, terminal::Clear(terminal::ClearType::All));
         loop {
             let theme = current_theme();
             let sel_bg = theme.get_log_color("menu_selected_bg");
             let sel_fg = theme.get_log_color("menu_selected_fg");
-            let _ = execute!(stdout, cursor::MoveTo(0, 0));
-            println!("{}\n", self.label);
+            let _ = execute!(out, cursor::MoveTo(0, 0));
+            print::line(&format!("{}\n", self.label));
             // Render buttons as a row
             let mut line = String::new();
             for (i, (title, key)) in self.buttons.iter().enumerate() {
@@ -1094,38 +1117,38 @@ impl<'a> RawButtonsInput<'a> {
                 let styled = if i == self.cursor {
                     // Selected state
                     if is_disabled {
-                        token.as_str().with(Color::DarkGrey).on(sel_bg).to_string()
+                        paint(&token, Some(Color::DarkGrey), Some(sel_bg), &[])
                     } else if is_danger {
-                        token
-                            .as_str()
-                            .with(sel_fg)
-                            .on(Color::Red)
-                            .bold()
-                            .to_string()
+                        paint(&token, Some(sel_fg), Some(Color::Red), &[Attribute::Bold])
                     } else {
-                        token.as_str().with(sel_fg).on(sel_bg).bold().to_string()
+                        paint(&token, Some(sel_fg), Some(sel_bg), &[Attribute::Bold])
                     }
                 } else if is_disabled {
-                    token.as_str().with(Color::DarkGrey).to_string()
+                    fg(&token, Color::DarkGrey)
                 } else if is_danger {
-                    token.as_str().with(Color::Red).to_string()
+                    fg(&token, Color::Red)
                 } else {
                     token.clone()
                 };
                 line.push_str(&format!(" {styled}"));
             }
-            println!("{line}\n");
+            print::line(&format!("{line}\n"));
             // Tooltip/help under the row if provided
             if let Some(Some(help)) = self.helps.get(self.cursor) {
-                println!("{help}\n");
+                print::line(&format!("{help}\n"));
             }
-            println!(
+            print::line(&format!(
                 "Keys: Left/Right to move, Enter select, hotkeys {}, Esc cancel",
                 self.buttons.iter().map(|(_, k)| k).collect::<String>()
-            );
-            let _ = stdout.flush();
+            ));
+            let _ = out.flush();
 
             if let Ok(Event::Key(k)) = event::read() {
+                if is_ctrl_c(&k) {
+                    let _ = terminal::disable_raw_mode();
+                    crate::signals::interrupt();
+                    return None;
+                }
                 match k.code {
                     c if c == self.keymap.left => {
                         // Move left skipping disabled if possible
@@ -1153,7 +1176,10 @@ impl<'a> RawButtonsInput<'a> {
                         // Danger confirmation if enabled
                         if self.confirm_on_danger && self.danger.contains(&self.cursor) {
                             let _ = terminal::disable_raw_mode();
-                            println!("Confirm '{}'? [y/N] ", self.buttons[self.cursor].0);
+                            print::line(&format!(
+                                "Confirm '{}'? [y/N] ",
+                                self.buttons[self.cursor].0
+                            ));
                             use std::io::stdin;
                             let mut buf = String::new();
                             let _ = stdin().read_line(&mut buf);
@@ -1180,7 +1206,7 @@ impl<'a> RawButtonsInput<'a> {
                         {
                             if self.confirm_on_danger && self.danger.contains(&idx) {
                                 let _ = terminal::disable_raw_mode();
-                                println!("Confirm '{}'? [y/N] ", self.buttons[idx].0);
+                                print::line(&format!("Confirm '{}'? [y/N] ", self.buttons[idx].0));
                                 use std::io::stdin;
                                 let mut buf = String::new();
                                 let _ = stdin().read_line(&mut buf);
@@ -1,19 +1,15 @@
-use crate::output::hook;
-use rpassword::read_password;
-use std::io::{stdin, stdout, Write};
+use crate::output::{hook, print, sink};
 
 /// Prompt for plain text input with optional default fallback
 pub fn prompt_text(prompt: &str, default: Option<&str>) -> String {
-    print!(
+    print::write(&format!(
         "{prompt}{} ",
         default.map_or(String::new(), |d| format!(" [{d}]"))
-    );
-    if let Err(e) = stdout().flush() {
-        hook::warn(&format!("flush failed: {e}"));
-    }
+    ));
+    sink::flush();
 
     let mut input = String::new();
-    if let Err(e) = stdin().read_line(&mut input) {
+    if let Err(e) = crate::input::read_line(&mut input) {
         hook::error(&format!("failed to read input: {e}"));
         return default.unwrap_or("").to_string();
     }
@@ -29,13 +25,11 @@ pub fn prompt_text(prompt: &str, default: Option<&str>) -> String {
 /// Prompt for a yes/no confirmation
 pub fn confirm(prompt: &str, default_yes: bool) -> bool {
     let yes_hint = if default_yes { "[Y/n]" } else { "[y/N]" };
-    print!("{prompt} {yes_hint} ");
-    if let Err(e) = stdout().flush() {
-        hook::warn(&format!("flush failed: {e}"));
-    }
+    print::write(&format!("{prompt} {yes_hint} "));
+    sink::flush();
 
     let mut input = String::new();
-    if let Err(e) = stdin().read_line(&mut input) {
+    if let Err(e) = crate::input::read_line(&mut input) {
         hook::error(&format!("failed to read input: {e}"));
         return default_yes;
     }
@@ -51,9 +45,7 @@ pub fn confirm(prompt: &str, default_yes: bool) -> bool {
 
 /// Prompt for a hidden password
 pub fn prompt_password(prompt: &str) -> String {
-    print!("{prompt} ");
-    if let Err(e) = stdout().flush() {
-        hook::warn(&format!("flush failed: {e}"));
-    }
-    read_password().unwrap_or_default()
+    print::write(&format!("{prompt} "));
+    sink::flush();
+    crate::input::read_password().unwrap_or_default()
 }
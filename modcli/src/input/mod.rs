@@ -2,6 +2,8 @@ pub mod builders;
 pub mod input_builder;
 pub mod secure;
 
+use std::io;
+
 // Unified input API re-exports
 pub use crate::output::input::{interactive_menu, prompt_confirm, prompt_password, prompt_text};
 // New builder-style API
@@ -10,3 +12,20 @@ pub use builders::{
     raw_multi_select_paged, raw_select, raw_select_paged, select, text, ConfirmInput, FormBuilder,
     FormValue, NumberInput, TextInput,
 };
+
+// A line from stdin, or the next `testing::TestCli` answer.
+pub(crate) fn read_line(buf: &mut String) -> io::Result<usize> {
+    match crate::testing::scripted_line(buf, true) {
+        Some(result) => result,
+        None => io::stdin().read_line(buf),
+    }
+}
+
+// A password read without echo, or the next `testing::TestCli` answer.
+pub(crate) fn read_password() -> io::Result<String> {
+    let mut buf = String::new();
+    match crate::testing::scripted_line(&mut buf, false) {
+        Some(result) => result.map(|_| buf.trim_end_matches('\n').to_string()),
+        None => rpassword::read_password(),
+    }
+}
@@ -13,8 +13,9 @@
 //! - Styled output, gradients, progress, tables
 //! - Optional internal helper commands
 //!
-//! Note: Runtime plugins and JSON/config loaders have been removed from core for
-//! security and performance. Configure your CLI directly in code.
+//! Note: Runtime plugins have been removed from core for security and
+//! performance. Configure your CLI in code, or opt into layered config files,
+//! environment variables and flags with the `config` feature (see `config`).
 
 use std::sync::{
     atomic::{AtomicBool, Ordering},
@@ -22,16 +23,36 @@ use std::sync::{
 };
 
 pub mod args;
+pub mod cancel;
 pub mod command;
+pub mod completions;
+#[cfg(feature = "config")]
+pub mod config;
+pub mod context;
+pub mod deprecation;
+#[cfg(feature = "derive")]
+pub mod derive;
 pub mod error;
+pub mod exit;
+pub mod fn_command;
+pub mod globals;
+pub mod help;
 pub mod input;
 pub mod loader;
+pub mod man;
+pub mod middleware;
 pub mod output;
 pub mod parser;
 pub mod shell;
+pub mod signals;
+pub mod spec;
+pub mod suggest;
+pub mod testing;
+pub mod tree;
 pub mod validate;
 
 pub use crate::command::Command as CliCustom;
+use crate::exit::{ExitCode, ExitCodes};
 use crate::loader::CommandRegistry;
 
 #[cfg(feature = "internal-commands")]
@@ -51,6 +72,8 @@ pub mod custom;
 /// ```
 pub struct ModCli {
     pub registry: CommandRegistry,
+    exit_codes: ExitCodes,
+    app: Option<(String, String)>,
 }
 
 /// Registers a startup banner from a UTF-8 text file. The contents are read immediately
@@ -60,7 +83,7 @@ pub fn set_startup_banner_from_file(path: &str) -> Result<(), crate::error::ModC
     let data = std::fs::read_to_string(path)?;
     let owned = data.clone();
     set_startup_banner(move || {
-        println!("{owned}\n");
+        crate::output::print::line(&format!("{owned}\n"));
     });
     Ok(())
 }
@@ -73,7 +96,7 @@ macro_rules! banner_text {
     ($text:expr) => {{
         $crate::set_startup_banner(|| {
             $crate::output::print::line($text);
-            println!();
+            $crate::output::print::newline();
         });
     }};
 }
@@ -118,9 +141,27 @@ impl ModCli {
     pub fn new() -> Self {
         Self {
             registry: CommandRegistry::new(),
+            exit_codes: ExitCodes::default(),
+            app: None,
         }
     }
 
+    /// A `ModCli` that accepts the standard global options (`-v`, `-q`,
+    /// `--color`, `--output` and, with the `config` feature, `--config`);
+    /// see `globals`. `new()` starts without global options, so commands
+    /// receive those flags as ordinary arguments.
+    ///
+    /// ```
+    /// let cli = modcli::ModCli::with_standard_globals();
+    /// assert!(!cli.registry.global_options().is_empty());
+    /// ```
+    pub fn with_standard_globals() -> Self {
+        let mut cli = Self::new();
+        cli.registry
+            .set_global_options(crate::globals::GlobalOptions::standard());
+        cli
+    }
+
     /// Sets the command prefix used for prefix routing (e.g., `tool:hello`).
     pub fn set_prefix(&mut self, prefix: &str) {
         self.registry.set_prefix(prefix);
@@ -131,19 +172,295 @@ impl ModCli {
         self.registry.get_prefix()
     }
 
+    /// Sets the application name and version; `tool --version` (or `-V`)
+    /// then prints `<name> <version>` instead of dispatching.
+    ///
+    /// ```
+    /// let mut cli = modcli::ModCli::new();
+    /// cli.set_app("tool", env!("CARGO_PKG_VERSION"));
+    /// assert_eq!(cli.app_name(), Some("tool"));
+    /// ```
+    pub fn set_app(&mut self, name: &str, version: &str) {
+        self.app = Some((name.to_string(), version.to_string()));
+    }
+
+    pub fn app_name(&self) -> Option<&str> {
+        self.app.as_ref().map(|(name, _)| name.as_str())
+    }
+
+    pub fn app_version(&self) -> Option<&str> {
+        self.app.as_ref().map(|(_, version)| version.as_str())
+    }
+
+    /// Loads `loader`, applies its `modcli` section (see `apply_config`) and
+    /// returns the whole merged config deserialized as `T`.
+    #[cfg(feature = "config")]
+    pub fn configure<T: serde::de::DeserializeOwned>(
+        &mut self,
+        loader: crate::config::ConfigLoader,
+    ) -> Result<T, crate::error::ModCliError> {
+        let value = loader.load_value()?;
+        self.apply_config(&crate::config::FrameworkConfig::from_value(&value)?)?;
+        serde_json::from_value(value).map_err(|e| crate::error::ModCliError::Config(e.to_string()))
+    }
+
+    /// Applies framework settings: prefix, theme, message overrides and the
+    /// startup banner. Unset keys leave the current settings unchanged. The
+    /// shell section is not applied; build it with `FrameworkConfig::shell()`.
+    /// Fails with `ModCliError::Config` when `banner` is set but a startup
+    /// banner is already registered.
+    #[cfg(feature = "config")]
+    pub fn apply_config(
+        &mut self,
+        config: &crate::config::FrameworkConfig,
+    ) -> Result<(), crate::error::ModCliError> {
+        if let Some(prefix) = &config.prefix {
+            self.set_prefix(prefix);
+        }
+        if let Some(theme) = &config.theme {
+            crate::output::themes::apply_theme(theme);
+        }
+        for (key, value) in &config.messages {
+            crate::output::messages::set_message(key.as_str(), value.as_str());
+        }
+        if let Some(banner) = &config.banner {
+            let text = banner.clone();
+            try_set_startup_banner(move || {
+                crate::output::print::line(&text);
+                crate::output::print::newline();
+            })?;
+        }
+        Ok(())
+    }
+
     /// Runs the CLI by dispatching the first arg as the command and the rest as arguments.
-    /// Prints an error if no command is provided.
+    /// Global options (see `with_standard_globals` and `CommandRegistry::add_global`)
+    /// are removed from anywhere before `--` and applied first (see `globals`).
+    /// `--version`/`-V` prints the version set with `set_app`, and
+    /// `<command> --help`/`-h` prints that command's help (see
+    /// `Command::help_flags`).
+    /// Prints an error if no command is provided. Ctrl-C is handled as
+    /// described in `signals`.
     pub fn run(&mut self, args: Vec<String>) {
+        self.run_with_status(args);
+    }
+
+    /// Sets the table used to map errors to process exit codes.
+    pub fn set_exit_codes(&mut self, codes: ExitCodes) {
+        self.exit_codes = codes;
+    }
+
+    /// Gets the error-to-exit-code table.
+    pub fn exit_codes(&self) -> &ExitCodes {
+        &self.exit_codes
+    }
+
+    /// Like `run`, but returns the exit code: the command's own `ExitCode` on
+    /// success, the code mapped from the error (which is printed first), or
+    /// `128 + signal` (130 for Ctrl-C) when the command was interrupted.
+    ///
+    /// Output mode, color choice and verbosity picked with global options
+    /// are restored when the call returns.
+    pub fn run_with_status(&mut self, mut args: Vec<String>) -> ExitCode {
+        let settings = crate::globals::Settings::current();
+        let code = match self.begin(&mut args) {
+            Some(code) => code,
+            None => {
+                let watch = crate::signals::watch(self.registry.cancellation());
+                let result = self.registry.try_run(&args[0], &args[1..]);
+                self.finish(result, watch.interrupted())
+            }
+        };
+        settings.restore();
+        code
+    }
+
+    /// Async counterpart of `run_with_status` (feature `tokio-runtime`); call it
+    /// from inside a tokio runtime.
+    ///
+    /// Async commands are awaited (with their `AsyncCommand::timeout`); sync
+    /// commands run on tokio's blocking pool (`spawn_blocking`), so the
+    /// runtime keeps serving other tasks. Sync commands have no timeout: a
+    /// thread can't be stopped from outside, so long-running ones should
+    /// check `ctx.is_cancelled()`. Ctrl-C cancels the registry's
+    /// `CancellationToken`, drops a running async command (middleware `after`
+    /// sees `ModCliError::Interrupted`) and returns `ExitCode::INTERRUPTED`; a sync command can't be dropped, so the call
+    /// returns once it notices the cancellation. A second Ctrl-C exits the
+    /// process immediately. Settings picked with global options are restored
+    /// as in `run_with_status`.
+    #[cfg(feature = "tokio-runtime")]
+    pub async fn run_async(&mut self, args: Vec<String>) -> ExitCode {
+        let settings = crate::globals::Settings::current();
+        let code = self.dispatch_async(args).await;
+        settings.restore();
+        code
+    }
+
+    #[cfg(feature = "tokio-runtime")]
+    async fn dispatch_async(&mut self, mut args: Vec<String>) -> ExitCode {
+        if let Some(code) = self.begin(&mut args) {
+            return code;
+        }
+        let watch = crate::signals::watch(self.registry.cancellation());
+        // No OS handler off unix: forward tokio's Ctrl-C to `signals`
+        #[cfg(not(unix))]
+        let forward = tokio::spawn(async {
+            while tokio::signal::ctrl_c().await.is_ok() {
+                crate::signals::interrupt();
+            }
+        });
+
+        // Ctrl-C stops an async command inside the registry, so middleware
+        // sees the `Interrupted` error
+        let result = if self.registry.routes_to_async(&args[0], &args[1..]) {
+            self.registry.try_run_async(&args[0], &args[1..]).await
+        } else {
+            self.run_on_blocking_pool(args).await
+        };
+        #[cfg(not(unix))]
+        forward.abort();
+        let interrupted = watch.interrupted().or_else(|| match &result {
+            Err(err @ crate::error::ModCliError::Interrupted { .. }) => {
+                Some(self.exit_codes.code_for(err))
+            }
+            _ => None,
+        });
+        self.finish(result, interrupted)
+    }
+
+    // Move the registry to tokio's blocking pool for a sync command and take
+    // it back from the task's result. A panic is re-raised once the registry
+    // is back in place.
+    #[cfg(feature = "tokio-runtime")]
+    async fn run_on_blocking_pool(
+        &mut self,
+        args: Vec<String>,
+    ) -> Result<ExitCode, crate::error::ModCliError> {
+        let registry = std::mem::take(&mut self.registry);
+        let runtime = tokio::runtime::Handle::current();
+        let task = tokio::task::spawn_blocking(move || {
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                // `try_run_async` still runs an async command a middleware
+                // rewrote the call to
+                runtime.block_on(registry.try_run_async(&args[0], &args[1..]))
+            }));
+            (registry, result)
+        });
+        match task.await {
+            Ok((registry, result)) => {
+                self.registry = registry;
+                result.unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+            }
+            Err(err) if err.is_panic() => std::panic::resume_unwind(err.into_panic()),
+            Err(err) => Err(crate::error::ModCliError::Other(format!(
+                "command task failed: {err}"
+            ))),
+        }
+    }
+
+    // Apply global options, answer `--version`, reset cancellation, install the
+    // signal handler and run the banner. Returns the exit code when there is
+    // nothing to dispatch.
+    fn begin(&mut self, args: &mut Vec<String>) -> Option<ExitCode> {
+        if let Err(err) = self.apply_globals(args) {
+            self.registry.report_error(&err);
+            return Some(self.exit_codes.code_for(&err));
+        }
+        if let (Some((name, version)), Some("--version" | "-V")) =
+            (&self.app, args.first().map(String::as_str))
+        {
+            self.registry.output().line(&format!("{name} {version}"));
+            return Some(ExitCode::SUCCESS);
+        }
+        self.registry.cancellation().reset();
+        crate::signals::install();
         run_startup_banner_if_enabled();
         if args.is_empty() {
             crate::output::hook::status("No command provided. Try `help`.");
-            return;
+            return Some(ExitCode::SUCCESS);
         }
+        None
+    }
 
-        let command = &args[0];
-        let rest = &args[1..];
+    // Strip global options from `args`, apply the standard ones and store
+    // them for `Context::globals()`.
+    fn apply_globals(&mut self, args: &mut Vec<String>) -> Result<(), crate::error::ModCliError> {
+        let registry = &self.registry;
+        let globals = registry
+            .global_options()
+            .extract_for(args, |words| registry.spec_for(words))?;
+        crate::globals::apply(&globals)?;
+        self.registry.set_globals(globals);
+        Ok(())
+    }
 
-        self.registry.execute(command, rest);
+    // Map a dispatch result to the process exit code; an interrupt wins.
+    fn finish(
+        &self,
+        result: Result<ExitCode, crate::error::ModCliError>,
+        interrupted: Option<ExitCode>,
+    ) -> ExitCode {
+        if let Some(code) = interrupted {
+            crate::signals::restore_terminal();
+            crate::output::hook::status("Interrupted.");
+            return code;
+        }
+        match result {
+            Ok(code) => code,
+            Err(err) => {
+                self.registry.report_error(&err);
+                self.exit_codes.code_for(&err)
+            }
+        }
+    }
+
+    /// Runs `run_async` to completion from synchronous code (feature
+    /// `tokio-runtime`), starting a multi-threaded runtime when none is running.
+    ///
+    /// ```no_run
+    /// fn main() -> std::process::ExitCode {
+    ///     let args: Vec<String> = std::env::args().skip(1).collect();
+    ///     let mut cli = modcli::ModCli::new();
+    ///     // register sync and async commands...
+    ///     cli.run_blocking(args).into()
+    /// }
+    /// ```
+    #[cfg(feature = "tokio-runtime")]
+    pub fn run_blocking(&mut self, args: Vec<String>) -> ExitCode {
+        use tokio::runtime::{Builder, Handle, RuntimeFlavor};
+        let err = match Handle::try_current() {
+            Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+                return tokio::task::block_in_place(|| handle.block_on(self.run_async(args)));
+            }
+            Ok(_) => crate::error::ModCliError::Other(
+                "run_blocking cannot block a current-thread runtime; await run_async instead"
+                    .into(),
+            ),
+            Err(_) => match Builder::new_multi_thread().enable_all().build() {
+                Ok(runtime) => return runtime.block_on(self.run_async(args)),
+                Err(e) => crate::error::ModCliError::Io(e),
+            },
+        };
+        self.registry.report_error(&err);
+        self.exit_codes.code_for(&err)
+    }
+
+    /// Runs the command and exits the process with its exit code.
+    pub fn run_and_exit(&mut self, args: Vec<String>) -> ! {
+        let code = self.run_with_status(args);
+        std::process::exit(code.code() as i32)
+    }
+
+    /// Runs with the process arguments (program name skipped) and exits.
+    ///
+    /// ```no_run
+    /// let mut cli = modcli::ModCli::new();
+    /// // register commands...
+    /// cli.main();
+    /// ```
+    pub fn main(&mut self) -> ! {
+        let args: Vec<String> = std::env::args().skip(1).collect();
+        self.run_and_exit(args)
     }
 }
 
@@ -172,6 +489,17 @@ where
     let _ = STARTUP_BANNER.set(Box::new(f));
 }
 
+// Like `set_startup_banner`, but reports a banner that is already registered.
+#[cfg(feature = "config")]
+fn try_set_startup_banner<F>(f: F) -> Result<(), crate::error::ModCliError>
+where
+    F: Fn() + Send + Sync + 'static,
+{
+    STARTUP_BANNER.set(Box::new(f)).map_err(|_| {
+        crate::error::ModCliError::Config("a startup banner is already registered".into())
+    })
+}
+
 fn run_startup_banner_if_enabled() {
     // Ensure one-time run per process
     if BANNER_RAN.swap(true, Ordering::SeqCst) {
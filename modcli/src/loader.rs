@@ -2,14 +2,25 @@
 // use crate::custom::CustomCommand; // feature = "custom-commands"
 
 #[cfg(feature = "internal-commands")]
-use crate::commands::{FrameworkCommand, HelloCommand, HelpCommand, PingCommand};
+use crate::commands::{FrameworkCommand, HelloCommand, HelpCommand, PingCommand, ShellCommand};
 use crate::output::hook;
 
-#[cfg(feature = "async")]
-use crate::command::AsyncCommand;
+use crate::cancel::CancellationToken;
 use crate::command::Command;
+#[cfg(feature = "async")]
+use crate::command::{AsyncCommand, AsyncEntry};
+use crate::context::Context;
+use crate::deprecation::Deprecation;
 #[allow(unused_imports)]
 use crate::error::ModCliError;
+use crate::exit::ExitCode;
+use crate::fn_command::CommandBuilder;
+use crate::globals::GlobalOptions;
+use crate::middleware::{Call, CallResult, Middleware};
+use crate::output::handle::Output;
+use crate::spec::{CommandSpec, ParsedArgs};
+use crate::tree::{alias_path, Conflict, Group, HelpOrder};
+use std::any::{Any, TypeId};
 use std::collections::{HashMap, HashSet};
 
 // Reduce type complexity for registry hooks and error formatter
@@ -20,6 +31,44 @@ type VisibilityPolicyFn = dyn Fn(&dyn Command, &HashSet<String>) -> bool + Send
 type AuthorizePolicyFn =
     dyn Fn(&dyn Command, &HashSet<String>, &[String]) -> Result<(), String> + Send + Sync;
 
+// Outcome of routing an invocation.
+enum Resolved<'r> {
+    Command(&'r dyn Command, &'r [String]),
+    Group(String),
+    Help(String),
+}
+
+// The raw invocation handed to a `Context`: command word, then its arguments.
+fn invocation(cmd: &str, args: &[String]) -> Vec<String> {
+    let mut invocation = Vec::with_capacity(args.len() + 1);
+    invocation.push(cmd.to_string());
+    invocation.extend_from_slice(args);
+    invocation
+}
+
+// True when `rest` asks for the command's help: one of its `help_flags()`
+// before any `--`, unless the command's spec declares that flag itself.
+fn wants_help(command: &dyn Command, rest: &[String]) -> bool {
+    let flags = command.help_flags();
+    if flags.is_empty() {
+        return false;
+    }
+    let spec = command.spec();
+    let claimed = |flag: &str| {
+        spec.as_ref()
+            .is_some_and(|s| match flag.strip_prefix("--") {
+                Some(long) => s.find_long(long).is_some(),
+                None => flag
+                    .chars()
+                    .nth(1)
+                    .is_some_and(|c| s.find_short(c).is_some()),
+            })
+    };
+    rest.iter()
+        .take_while(|a| *a != "--")
+        .any(|a| flags.contains(&a.as_str()) && !claimed(a))
+}
+
 /// Registry for commands and optional alias/prefix routing.
 ///
 /// # Example
@@ -40,17 +89,26 @@ type AuthorizePolicyFn =
 pub struct CommandRegistry {
     prefix: String,
     commands: HashMap<String, Box<dyn Command>>,
+    order: Vec<String>,      // registration order of `commands`
+    duplicates: Vec<String>, // names registered more than once
+    help_order: HelpOrder,
     aliases: HashMap<String, String>,
-    #[cfg(feature = "async")]
-    async_commands: HashMap<String, Box<dyn AsyncCommand>>, // separate store for async commands
-    #[cfg(feature = "async")]
-    async_aliases: HashMap<String, String>,
+    deprecated_aliases: HashMap<String, Deprecation>,
+    list_deprecated: bool,
+    warned: std::sync::Mutex<HashSet<String>>,
+    groups: HashMap<String, Group>,
     caps: HashSet<String>,
     visibility_policy: Option<Box<VisibilityPolicyFn>>,
     authorize_policy: Option<Box<AuthorizePolicyFn>>,
+    middleware: Vec<Box<dyn Middleware>>,
     pre_hook: Option<Box<PreHookFn>>,   // before dispatch
     post_hook: Option<Box<PostHookFn>>, // after dispatch
     error_formatter: Option<Box<ErrorFmtFn>>,
+    state: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+    output: Output,
+    cancel: CancellationToken,
+    global_options: GlobalOptions,
+    globals: ParsedArgs,
     #[cfg(feature = "dispatch-cache")]
     cache: std::sync::Mutex<Option<(String, String)>>,
 }
@@ -67,17 +125,26 @@ impl CommandRegistry {
         let mut reg = Self {
             prefix: String::new(),
             commands: HashMap::new(),
+            order: Vec::new(),
+            duplicates: Vec::new(),
+            help_order: HelpOrder::default(),
             aliases: HashMap::new(),
-            #[cfg(feature = "async")]
-            async_commands: HashMap::new(),
-            #[cfg(feature = "async")]
-            async_aliases: HashMap::new(),
+            deprecated_aliases: HashMap::new(),
+            list_deprecated: true,
+            warned: std::sync::Mutex::new(HashSet::new()),
+            groups: HashMap::new(),
             caps: HashSet::new(),
             visibility_policy: None,
             authorize_policy: None,
+            middleware: Vec::new(),
             pre_hook: None,
             post_hook: None,
             error_formatter: None,
+            state: HashMap::new(),
+            output: Output::default(),
+            cancel: CancellationToken::new(),
+            global_options: GlobalOptions::new(),
+            globals: ParsedArgs::default(),
             #[cfg(feature = "dispatch-cache")]
             cache: std::sync::Mutex::new(None),
         };
@@ -91,16 +158,11 @@ impl CommandRegistry {
         reg
     }
 
-    /// Register an async command (feature: "async")
+    /// Register an async command (feature: "async"). It shares names, aliases,
+    /// groups, help and visibility with sync commands; run it with `execute_async`.
     #[cfg(feature = "async")]
     pub fn register_async(&mut self, cmd: Box<dyn AsyncCommand>) {
-        let name = cmd.name().to_string();
-        self.async_commands.insert(name.clone(), cmd);
-        for &alias in self.async_commands[&name].aliases() {
-            if !self.async_commands.contains_key(alias) {
-                self.async_aliases.insert(alias.to_string(), name.clone());
-            }
-        }
+        self.register(Box::new(AsyncEntry(cmd)));
     }
 
     /// Sets the command prefix
@@ -124,136 +186,455 @@ impl CommandRegistry {
 
     /// Gets a command by name with prefix
     /// Registers a command and records its aliases for reverse lookup.
+    /// A command with the same name is replaced and aliases that collide are
+    /// dropped; use `try_register` to reject those, or `check` to audit.
     #[inline(always)]
     pub fn register(&mut self, cmd: Box<dyn Command>) {
         // capture name before moving the command
         let name = cmd.name().to_string();
-        self.commands.insert(name.clone(), cmd);
+        if self.commands.insert(name.clone(), cmd).is_none() {
+            self.order.push(name.clone());
+        } else {
+            self.duplicates.push(name.clone());
+        }
+
+        // every proper prefix of `a:b:c` is a group node
+        let mut end = 0;
+        while let Some(i) = name[end..].find(':') {
+            end += i;
+            let path = &name[..end];
+            if !self.groups.contains_key(path) {
+                self.groups.insert(path.to_string(), Group::new(path));
+            }
+            end += 1;
+        }
 
-        // map each alias -> primary name without intermediate Vec allocations
+        // map each alias (resolved at the command's level) -> primary name
         for &alias in self.commands[&name].aliases() {
+            let key = alias_path(&name, alias);
             // avoid alias clobbering existing command names
-            if !self.commands.contains_key(alias) {
-                // store alias as owned String
-                self.aliases.insert(alias.to_string(), name.clone());
+            if !self.commands.contains_key(&key) {
+                self.aliases.insert(key, name.clone());
             }
         }
     }
 
-    /// Returns all registered commands (read-only)
-    /// Returns an iterator over all registered commands.
-    pub fn all(&self) -> impl Iterator<Item = &Box<dyn Command>> {
-        self.commands.values()
+    /// Starts a closure-backed command; `run` (or `run_async`) registers it.
+    /// See `fn_command`.
+    pub fn command(&mut self, name: &str) -> CommandBuilder<'_> {
+        CommandBuilder::new(self, name)
     }
 
-    /// Returns all registered async commands (read-only)
-    #[cfg(feature = "async")]
-    pub fn all_async(&self) -> impl Iterator<Item = &Box<dyn AsyncCommand>> {
-        self.async_commands.values()
+    /// Registers a command unless its name or an alias collides with an
+    /// existing command, group or alias.
+    ///
+    /// Errors with `ModCliError::Conflict` describing the first collision;
+    /// the registry is left unchanged.
+    pub fn try_register(&mut self, cmd: Box<dyn Command>) -> Result<(), ModCliError> {
+        let name = cmd.name();
+        let conflict = if self.commands.contains_key(name) {
+            Some(Conflict::DuplicateCommand {
+                name: name.to_string(),
+            })
+        } else {
+            // the name and its implied groups must not hide an alias
+            let paths = name.match_indices(':').map(|(i, _)| &name[..i]);
+            paths
+                .chain(std::iter::once(name))
+                .filter(|path| !self.groups.contains_key(*path))
+                .find_map(|path| {
+                    self.aliases.get(path).map(|owner| Conflict::AliasShadowed {
+                        alias: path.to_string(),
+                        owner: owner.clone(),
+                    })
+                })
+        };
+        let conflict = conflict.or_else(|| {
+            cmd.aliases().iter().find_map(|&alias| {
+                let key = alias_path(name, alias);
+                if key == name {
+                    return None;
+                }
+                if self.commands.contains_key(&key) || self.groups.contains_key(&key) {
+                    return Some(Conflict::AliasShadowed {
+                        alias: key,
+                        owner: name.to_string(),
+                    });
+                }
+                self.aliases
+                    .get(&key)
+                    .map(|other| Conflict::DuplicateAlias {
+                        alias: key.clone(),
+                        owner: name.to_string(),
+                        other: other.clone(),
+                    })
+            })
+        });
+        match conflict {
+            Some(c) => Err(ModCliError::Conflict(c)),
+            None => {
+                self.register(cmd);
+                Ok(())
+            }
+        }
     }
 
-    // --- ASYNC DISPATCH (feature: "async") ---------------------------------
-    #[cfg(feature = "async")]
-    #[inline(always)]
-    pub async fn try_execute_async(&self, cmd: &str, args: &[String]) -> Result<(), ModCliError> {
-        if let Some(ref pre) = self.pre_hook {
-            pre(cmd, args);
+    /// Audits every registration: commands registered twice, aliases that
+    /// are also command or group paths, and aliases declared by more than
+    /// one node (including `rename` aliases). Meant for tests:
+    /// `cli.registry.check().unwrap()`.
+    pub fn check(&self) -> Result<(), Vec<Conflict>> {
+        let mut conflicts: Vec<Conflict> = self
+            .duplicates
+            .iter()
+            .map(|name| Conflict::DuplicateCommand { name: name.clone() })
+            .collect();
+
+        let mut groups: Vec<&Group> = self.groups.values().collect();
+        groups.sort_by(|a, b| a.name.cmp(&b.name));
+        let mut declared: Vec<(String, String)> = Vec::new(); // (alias path, owner)
+        for cmd in self.all() {
+            let name = cmd.name();
+            declared.extend(
+                cmd.aliases()
+                    .iter()
+                    .map(|a| (alias_path(name, a), name.to_string())),
+            );
+        }
+        for group in groups {
+            let name = &group.name;
+            declared.extend(
+                group
+                    .aliases
+                    .iter()
+                    .map(|a| (alias_path(name, a), name.clone())),
+            );
+        }
+        let mut renamed: Vec<(&String, &String)> = self.aliases.iter().collect();
+        renamed.sort();
+        for (key, target) in renamed {
+            if !declared.iter().any(|(k, o)| k == key && o == target) {
+                declared.push((key.clone(), target.clone()));
+            }
         }
 
-        // Strip optional prefix from the incoming token
-        let token: &str = if !self.prefix.is_empty() && cmd.len() > self.prefix.len() + 1 {
-            let (maybe_prefix, rest_with_colon) = cmd.split_at(self.prefix.len());
-            if maybe_prefix == self.prefix && rest_with_colon.as_bytes().first() == Some(&b':') {
-                &rest_with_colon[1..]
-            } else {
-                cmd
+        let mut seen: HashMap<&str, &str> = HashMap::new();
+        for (alias, owner) in &declared {
+            if alias == owner {
+                continue;
             }
+            if self.commands.contains_key(alias) || self.groups.contains_key(alias) {
+                conflicts.push(Conflict::AliasShadowed {
+                    alias: alias.clone(),
+                    owner: owner.clone(),
+                });
+                continue;
+            }
+            match seen.get(alias.as_str()) {
+                Some(&other) if other != owner => conflicts.push(Conflict::DuplicateAlias {
+                    alias: alias.clone(),
+                    owner: owner.clone(),
+                    other: other.to_string(),
+                }),
+                Some(_) => {}
+                None => {
+                    seen.insert(alias, owner);
+                }
+            }
+        }
+        if conflicts.is_empty() {
+            Ok(())
         } else {
-            cmd
-        };
+            Err(conflicts)
+        }
+    }
 
-        // Direct name
-        if let Some(command) = self.async_commands.get(token) {
-            if let Err(e) = self.is_authorized_async(args) {
-                return Err(ModCliError::InvalidUsage(e));
-            }
-            command.execute_async(args).await?;
-            if let Some(ref post) = self.post_hook {
-                post(cmd, args, Ok(()));
+    /// Registers help text and aliases for a group node (e.g. `cluster` for
+    /// `cluster:node:drain`). Groups also exist implicitly for every command path prefix.
+    pub fn register_group(&mut self, group: Group) {
+        for alias in &group.aliases {
+            let key = alias_path(&group.name, alias);
+            if !self.commands.contains_key(&key) {
+                self.aliases.insert(key, group.name.clone());
             }
-            return Ok(());
         }
+        self.groups.insert(group.name.clone(), group);
+    }
 
-        // Alias
-        if let Some(primary) = self.async_aliases.get(token) {
-            if let Some(command) = self.async_commands.get(primary.as_str()) {
-                if let Err(e) = self.is_authorized_async(args) {
-                    return Err(ModCliError::InvalidUsage(e));
-                }
-                command.execute_async(args).await?;
-                if let Some(ref post) = self.post_hook {
-                    post(cmd, args, Ok(()));
-                }
-                return Ok(());
+    /// Gets a group node by its `:` path.
+    pub fn group(&self, path: &str) -> Option<&Group> {
+        self.groups.get(path)
+    }
+
+    /// Marks the alias at full path `alias` (`cluster:node:dr` for alias `dr`
+    /// of `cluster:node:drain`) as deprecated.
+    pub fn deprecate_alias(&mut self, alias: &str, deprecation: Deprecation) {
+        self.deprecated_aliases
+            .insert(alias.to_string(), deprecation);
+    }
+
+    /// Renames the command or group `old` to `new` (full `:` paths): `old`
+    /// keeps working as a deprecated alias that forwards to `new`. The
+    /// replacement defaults to `new`.
+    pub fn rename(&mut self, old: &str, new: &str, deprecation: Deprecation) {
+        let deprecation = match deprecation.replacement {
+            Some(_) => deprecation,
+            None => deprecation.replaced_by(new),
+        };
+        if !self.commands.contains_key(old) {
+            self.aliases.insert(old.to_string(), new.to_string());
+        }
+        self.deprecated_aliases.insert(old.to_string(), deprecation);
+    }
+
+    /// Deprecation of the alias or command at `path`, if any.
+    pub fn deprecation(&self, path: &str) -> Option<Deprecation> {
+        if let Some(dep) = self.deprecated_aliases.get(path) {
+            return Some(dep.clone());
+        }
+        self.get(path).and_then(|c| c.deprecated())
+    }
+
+    /// Whether help lists deprecated commands and aliases (annotated) or
+    /// leaves them out (default: listed).
+    pub fn set_list_deprecated(&mut self, list: bool) {
+        self.list_deprecated = list;
+    }
+
+    pub fn list_deprecated(&self) -> bool {
+        self.list_deprecated
+    }
+
+    // Warn once per registry about a deprecated name.
+    fn warn_deprecated(&self, name: &str, deprecation: &Deprecation) {
+        let first = self
+            .warned
+            .lock()
+            .map(|mut warned| warned.insert(name.to_string()))
+            .unwrap_or(false);
+        if first {
+            hook::deprecated(&deprecation.message(name));
+        }
+    }
+
+    // Warn about deprecated aliases among the typed words.
+    fn check_deprecated_words(&self, first: &str, rest: &[String]) {
+        if self.deprecated_aliases.is_empty() {
+            return;
+        }
+        let mut path = String::new();
+        for word in std::iter::once(first).chain(rest.iter().map(String::as_str)) {
+            let key = if path.is_empty() {
+                word.to_string()
+            } else {
+                format!("{path}:{word}")
+            };
+            if let Some(dep) = self.deprecated_aliases.get(&key) {
+                self.warn_deprecated(&key, dep);
+            }
+            match self.step(&path, word) {
+                Some(next) => path = next,
+                None => break,
             }
         }
+    }
 
-        // Two-token nested: parent child -> parent:child
-        if !args.is_empty() {
-            let combined = format!("{token}:{}", args[0]);
-            if let Some(command) = self.async_commands.get(combined.as_str()) {
-                let rest = &args[1..];
-                if let Err(e) = self.is_authorized_async(rest) {
-                    return Err(ModCliError::InvalidUsage(e));
-                }
-                command.execute_async(rest).await?;
-                if let Some(ref post) = self.post_hook {
-                    post(cmd, args, Ok(()));
-                }
-                return Ok(());
+    /// True if `path` is a group node (explicit or implied by a command path).
+    pub fn is_group(&self, path: &str) -> bool {
+        self.groups.contains_key(path)
+    }
+
+    /// Returns an iterator over all group nodes.
+    pub fn groups(&self) -> impl Iterator<Item = &Group> {
+        self.groups.values()
+    }
+
+    /// Resolves leading words to the deepest matching node (command or group),
+    /// following aliases at each level. Returns the node's `:` path and the
+    /// number of words consumed. If the walk stops on a group with words left
+    /// over, the deepest command passed on the way wins.
+    pub fn resolve(&self, words: &[String]) -> Option<(String, usize)> {
+        let (first, rest) = words.split_first()?;
+        self.walk(first, rest).map(|(path, n)| (path, n + 1))
+    }
+
+    // Walk `first rest..`; returns the node path and how many of `rest` were consumed.
+    fn walk(&self, first: &str, rest: &[String]) -> Option<(String, usize)> {
+        let mut path = self.step("", first)?;
+        let mut consumed = 0;
+        let mut last_cmd = None;
+        for word in rest {
+            if self.commands.contains_key(&path) {
+                last_cmd = Some((path.clone(), consumed));
             }
+            let Some(next) = self.step(&path, word) else {
+                break;
+            };
+            path = next;
+            consumed += 1;
         }
+        if self.commands.contains_key(&path) || consumed == rest.len() {
+            return Some((path, consumed));
+        }
+        // Stopped on a group with words left: back up to the deepest command
+        last_cmd.or(Some((path, consumed)))
+    }
 
-        if let Some(ref post) = self.post_hook {
-            post(cmd, args, Err("unknown"));
+    #[inline(always)]
+    fn step(&self, path: &str, word: &str) -> Option<String> {
+        let key = if path.is_empty() {
+            word.to_string()
+        } else {
+            format!("{path}:{word}")
+        };
+        if self.commands.contains_key(&key) || self.groups.contains_key(&key) {
+            return Some(key);
         }
-        Err(ModCliError::UnknownCommand(cmd.to_string()))
+        self.aliases.get(&key).cloned()
     }
 
-    /// Execute async and print user-friendly messages
+    /// Returns all registered commands (read-only)
+    /// Returns an iterator over all registered commands in registration order.
+    pub fn all(&self) -> impl Iterator<Item = &Box<dyn Command>> {
+        self.order.iter().filter_map(|name| self.commands.get(name))
+    }
+
+    /// Sets how help listings order commands (default: alphabetical).
+    pub fn set_help_order(&mut self, order: HelpOrder) {
+        self.help_order = order;
+    }
+
+    pub fn help_order(&self) -> HelpOrder {
+        self.help_order
+    }
+
+    /// Returns all registered async commands (read-only)
     #[cfg(feature = "async")]
-    #[inline(always)]
-    pub async fn execute_async(&self, cmd: &str, args: &[String]) {
-        if let Err(err) = self.try_execute_async(cmd, args).await {
-            if let Some(ref fmt) = self.error_formatter {
-                hook::error(&fmt(&err));
-            } else {
-                match err {
-                    ModCliError::InvalidUsage(msg) => hook::error(&format!("Invalid usage: {msg}")),
-                    ModCliError::UnknownCommand(name) => hook::unknown(&format!(
-                        "[{name}]. Type `help` or `--help` for a list of available commands."
-                    )),
-                    other => hook::error(&format!("{other}")),
-                }
+    pub fn all_async(&self) -> impl Iterator<Item = &dyn AsyncCommand> {
+        self.commands.values().filter_map(|c| c.as_async())
+    }
+
+    /// True if `name` is registered with `register_async`.
+    #[cfg(feature = "async")]
+    pub fn is_async(&self, name: &str) -> bool {
+        self.get(name).is_some_and(|c| c.as_async().is_some())
+    }
+
+    // --- ASYNC DISPATCH (feature: "async") ---------------------------------
+
+    /// Resolves and runs a sync or async command, awaiting async ones.
+    /// Routing, authorization, spec parsing, validation and hooks match `try_execute`.
+    #[cfg(feature = "async")]
+    pub async fn try_execute_async(&self, cmd: &str, args: &[String]) -> Result<(), ModCliError> {
+        self.try_run_async(cmd, args).await.map(|_| ())
+    }
+
+    /// Like `try_execute_async`, but also returns the command's `ExitCode`
+    /// (async commands report `SUCCESS` when they return `Ok`). Sync commands
+    /// run inline; `ModCli::run_async` runs them on tokio's blocking pool.
+    #[cfg(feature = "async")]
+    pub async fn try_run_async(&self, cmd: &str, args: &[String]) -> Result<ExitCode, ModCliError> {
+        let mut call = Call::new(cmd, args);
+        let (entered, early) = self.enter(&mut call);
+        let mut result = match early {
+            Some(result) => result,
+            None => self.run_call_async(&mut call).await,
+        };
+        self.leave(&call, entered, &mut result);
+        result
+    }
+
+    // True when `cmd args` routes to an async command.
+    #[cfg(feature = "tokio-runtime")]
+    pub(crate) fn routes_to_async(&self, cmd: &str, args: &[String]) -> bool {
+        self.walk(self.strip_route_prefix(cmd), args)
+            .and_then(|(path, _)| self.commands.get(path.as_str()))
+            .is_some_and(|command| command.as_async().is_some())
+    }
+
+    #[cfg(feature = "async")]
+    async fn run_call_async(&self, call: &mut Call) -> Result<ExitCode, ModCliError> {
+        let (cmd, args) = (call.command.as_str(), call.args.as_slice());
+        if let Some(ref pre) = self.pre_hook {
+            pre(cmd, args);
+        }
+        let (path, result) = match self.locate(cmd, args)? {
+            Resolved::Command(command, rest) => {
+                let result = match command.as_async() {
+                    Some(async_cmd) => match self.prepare(command, rest) {
+                        Ok(parsed) => {
+                            let ctx =
+                                Context::new(self, command.name(), invocation(cmd, args), parsed);
+                            self.await_command(async_cmd, &ctx).await
+                        }
+                        Err(err) => Err(err),
+                    },
+                    None => self.dispatch(command, cmd, args, rest),
+                };
+                (command.name().to_string(), result)
+            }
+            Resolved::Group(path) => {
+                self.print_group_help(&path);
+                (path, Ok(ExitCode::SUCCESS))
             }
+            Resolved::Help(path) => {
+                self.print_command_help(&path);
+                (path, Ok(ExitCode::SUCCESS))
+            }
+        };
+        call.set_path(path);
+        let code = result?;
+        if let Some(ref post) = self.post_hook {
+            post(&call.command, &call.args, Ok(()));
         }
+        Ok(code)
     }
 
-    // Authorization shim to reuse existing policy contract for async commands
+    // Run an async command. Under the tokio runtime its future is dropped
+    // when its timeout expires (`Timeout`) or the registry's token is
+    // cancelled (`Interrupted`); either error then reaches middleware like any
+    // other. A timeout leaves the token alone so later commands don't see
+    // themselves cancelled.
     #[cfg(feature = "async")]
-    #[inline(always)]
-    fn is_authorized_async(&self, args: &[String]) -> Result<(), String> {
-        if let Some(ref pol) = self.authorize_policy {
-            struct Dummy;
-            impl Command for Dummy {
-                fn name(&self) -> &str {
-                    "__async_dummy__"
+    async fn await_command(
+        &self,
+        command: &dyn AsyncCommand,
+        ctx: &Context<'_>,
+    ) -> Result<ExitCode, ModCliError> {
+        #[cfg(feature = "tokio-runtime")]
+        let result = {
+            let run = async {
+                match command.timeout() {
+                    Some(after) => tokio::time::timeout(after, command.run_async(ctx))
+                        .await
+                        .unwrap_or_else(|_| {
+                            Err(ModCliError::Timeout {
+                                command: command.name().to_string(),
+                                after,
+                            })
+                        }),
+                    None => command.run_async(ctx).await,
                 }
-                fn execute(&self, _args: &[String]) {}
+            };
+            tokio::select! {
+                biased;
+                result = run => result,
+                _ = self.cancel.cancelled() => Err(ModCliError::Interrupted {
+                    command: command.name().to_string(),
+                }),
             }
-            return pol(&Dummy, &self.caps, args);
+        };
+        #[cfg(not(feature = "tokio-runtime"))]
+        let result = command.run_async(ctx).await;
+        result
+    }
+
+    /// Execute async and print user-friendly messages
+    #[cfg(feature = "async")]
+    pub async fn execute_async(&self, cmd: &str, args: &[String]) {
+        if let Err(err) = self.try_execute_async(cmd, args).await {
+            self.report_error(&err);
         }
-        Ok(())
     }
 
     // --- Capabilities API -----------------------------------------------------
@@ -266,6 +647,10 @@ impl CommandRegistry {
     pub fn has_cap(&self, cap: &str) -> bool {
         self.caps.contains(cap)
     }
+    /// Returns the granted capabilities.
+    pub fn caps(&self) -> &HashSet<String> {
+        &self.caps
+    }
     pub fn set_caps<I, S>(&mut self, caps: I)
     where
         I: IntoIterator<Item = S>,
@@ -277,6 +662,58 @@ impl CommandRegistry {
         }
     }
 
+    // --- Context resources ------------------------------------------------------
+
+    /// Stores application state for commands (`ctx.get::<T>()`); one value per type.
+    pub fn insert_state<T: Any + Send + Sync>(&mut self, value: T) {
+        self.state.insert(TypeId::of::<T>(), Box::new(value));
+    }
+
+    /// Returns application state of type `T`, if registered.
+    pub fn state<T: Any + Send + Sync>(&self) -> Option<&T> {
+        self.state
+            .get(&TypeId::of::<T>())
+            .and_then(|b| b.downcast_ref::<T>())
+    }
+
+    /// Sets the output handle exposed to commands via `Context::out()`.
+    pub fn set_output(&mut self, output: Output) {
+        self.output = output;
+    }
+
+    pub fn output(&self) -> &Output {
+        &self.output
+    }
+
+    /// Cancellation token exposed to commands via `Context::cancellation()`.
+    pub fn cancellation(&self) -> &CancellationToken {
+        &self.cancel
+    }
+
+    /// Global options recognized by `ModCli::run` (see `globals`).
+    pub fn global_options(&self) -> &GlobalOptions {
+        &self.global_options
+    }
+
+    /// Replace the global options (`GlobalOptions::new()` disables them).
+    pub fn set_global_options(&mut self, options: GlobalOptions) {
+        self.global_options = options;
+    }
+
+    /// Declare an additional global option or flag.
+    pub fn add_global(&mut self, arg: crate::spec::ArgSpec) {
+        self.global_options.add(arg);
+    }
+
+    /// Global options parsed for the current run, exposed via `Context::globals()`.
+    pub fn globals(&self) -> &ParsedArgs {
+        &self.globals
+    }
+
+    pub fn set_globals(&mut self, globals: ParsedArgs) {
+        self.globals = globals;
+    }
+
     pub fn set_visibility_policy<F>(&mut self, f: F)
     where
         F: Fn(&dyn Command, &HashSet<String>) -> bool + Send + Sync + 'static,
@@ -294,6 +731,13 @@ impl CommandRegistry {
         self.authorize_policy = Some(Box::new(f));
     }
 
+    /// Appends a middleware layer (see `middleware`). Layers run in the order added.
+    pub fn use_middleware<M: Middleware + 'static>(&mut self, layer: M) {
+        self.middleware.push(Box::new(layer));
+    }
+
+    /// Single pre-dispatch callback, run inside the middleware stack.
+    /// Prefer `use_middleware`, which composes.
     pub fn set_pre_hook<F>(&mut self, f: F)
     where
         F: Fn(&str, &[String]) + Send + Sync + 'static,
@@ -301,6 +745,8 @@ impl CommandRegistry {
         self.pre_hook = Some(Box::new(f));
     }
 
+    /// Single post-dispatch callback: `Ok(())` on success, `Err("unknown")`
+    /// for unknown commands. Prefer `use_middleware`, which sees the real error.
     pub fn set_post_hook<F>(&mut self, f: F)
     where
         F: Fn(&str, &[String], Result<(), &str>) + Send + Sync + 'static,
@@ -331,10 +777,16 @@ impl CommandRegistry {
         if let Some(ref pol) = self.authorize_policy {
             return pol(cmd, &self.caps, args);
         }
-        if cmd.required_caps().iter().all(|c| self.caps.contains(*c)) {
+        let missing: Vec<&str> = cmd
+            .required_caps()
+            .iter()
+            .copied()
+            .filter(|c| !self.caps.contains(*c))
+            .collect();
+        if missing.is_empty() {
             Ok(())
         } else {
-            Err("Not authorized".into())
+            Err(format!("missing capability {}", missing.join(", ")))
         }
     }
 
@@ -344,9 +796,11 @@ impl CommandRegistry {
     ///
     /// Behavior:
     /// - Applies optional prefix routing (e.g., `tool:hello`).
-    /// - Resolves aliases to primary command names.
+    /// - Resolves nested words to the longest matching command path (`a b c` -> `a:b:c`)
+    ///   and aliases at each level; a bare group prints its children's help.
+    /// - Parses args against `Command::spec()` when declared.
     /// - Validates args via `Command::validate()` and logs a themed error on failure.
-    /// - Executes the command via `execute_with()`.
+    /// - Runs the command via `Command::run(&Context)` (defaults to `execute_parsed()`).
     /// - Prints user-facing messages via `output::hook` and does not return an error.
     ///
     /// Example (illustrative):
@@ -359,16 +813,21 @@ impl CommandRegistry {
     #[inline(always)]
     pub fn execute(&self, cmd: &str, args: &[String]) {
         if let Err(err) = self.try_execute(cmd, args) {
-            if let Some(ref fmt) = self.error_formatter {
-                hook::error(&fmt(&err));
-            } else {
-                match err {
-                    ModCliError::InvalidUsage(msg) => hook::error(&format!("Invalid usage: {msg}")),
-                    ModCliError::UnknownCommand(name) => hook::unknown(&format!(
-                        "[{name}]. Type `help` or `--help` for a list of available commands."
-                    )),
-                    other => hook::error(&format!("{other}")),
+            self.report_error(&err);
+        }
+    }
+
+    /// Prints an error via `output::hook`, using the error formatter when set.
+    pub fn report_error(&self, err: &ModCliError) {
+        if let Some(ref fmt) = self.error_formatter {
+            hook::error(&fmt(err));
+        } else {
+            match err {
+                ModCliError::InvalidUsage(msg) => hook::error(&format!("Invalid usage: {msg}")),
+                ModCliError::UnknownCommand { name, suggestions } => {
+                    hook::unknown(&crate::suggest::unknown_hint(name, suggestions))
                 }
+                other => hook::error(&format!("{other}")),
             }
         }
     }
@@ -377,8 +836,10 @@ impl CommandRegistry {
     /// Returns a structured error instead of printing/logging directly.
     ///
     /// Error mapping:
-    /// - `InvalidUsage(String)`: when `validate()` returns an error string.
-    /// - `UnknownCommand(String)`: command not found after alias/prefix resolution.
+    /// - `InvalidUsage(String)`: when spec parsing or `validate()` fails.
+    /// - `Unauthorized(String)`: when the authorization policy refuses the command.
+    /// - `UnknownCommand { name, suggestions }`: command not found after alias/prefix
+    ///   resolution; `suggestions` lists close visible matches.
     ///
     /// Examples (illustrative):
     ///
@@ -390,97 +851,193 @@ impl CommandRegistry {
     /// let _ = reg.try_execute("help", &vec![]);
     /// // Error mapping (unknown)
     /// match reg.try_execute("does-not-exist", &vec![]) {
-    ///     Err(modcli::error::ModCliError::UnknownCommand(name)) => assert_eq!(name, "does-not-exist"),
+    ///     Err(modcli::error::ModCliError::UnknownCommand { name, .. }) => assert_eq!(name, "does-not-exist"),
     ///     _ => {}
     /// }
     /// ```
     #[inline(always)]
     pub fn try_execute(&self, cmd: &str, args: &[String]) -> Result<(), ModCliError> {
+        self.try_run(cmd, args).map(|_| ())
+    }
+
+    /// Like `try_execute`, but also returns the command's `ExitCode`
+    /// (from `Command::run`). The call passes through the middleware stack.
+    pub fn try_run(&self, cmd: &str, args: &[String]) -> Result<ExitCode, ModCliError> {
+        let mut call = Call::new(cmd, args);
+        let (entered, early) = self.enter(&mut call);
+        let mut result = match early {
+            Some(result) => result,
+            None => self.run_call(&mut call),
+        };
+        self.leave(&call, entered, &mut result);
+        result
+    }
+
+    // Run `before` layers in order; returns how many let the call through and
+    // the short-circuit result, if any.
+    fn enter(&self, call: &mut Call) -> (usize, Option<CallResult>) {
+        for (i, layer) in self.middleware.iter().enumerate() {
+            if let Some(result) = layer.before(call) {
+                return (i, Some(result));
+            }
+        }
+        (self.middleware.len(), None)
+    }
+
+    // Run `after` for the entered layers, innermost first.
+    fn leave(&self, call: &Call, entered: usize, result: &mut CallResult) {
+        for layer in self.middleware[..entered].iter().rev() {
+            layer.after(call, result);
+        }
+    }
+
+    fn run_call(&self, call: &mut Call) -> Result<ExitCode, ModCliError> {
+        let (cmd, args) = (call.command.as_str(), call.args.as_slice());
         if let Some(ref pre) = self.pre_hook {
             pre(cmd, args);
         }
-        // Strip optional prefix `<prefix>:` without intermediate allocations
-        let token: &str = if !self.prefix.is_empty() && cmd.len() > self.prefix.len() + 1 {
+        let (path, result) = match self.locate(cmd, args)? {
+            Resolved::Command(command, rest) => (
+                command.name().to_string(),
+                self.dispatch(command, cmd, args, rest),
+            ),
+            Resolved::Group(path) => {
+                self.print_group_help(&path);
+                (path, Ok(ExitCode::SUCCESS))
+            }
+            Resolved::Help(path) => {
+                self.print_command_help(&path);
+                (path, Ok(ExitCode::SUCCESS))
+            }
+        };
+        call.set_path(path);
+        let code = result?;
+        if let Some(ref post) = self.post_hook {
+            post(&call.command, &call.args, Ok(()));
+        }
+        Ok(code)
+    }
+
+    // Resolve the invocation to a command (with its own arguments), a bare
+    // group, or a help request for a visible command (`deploy --help`).
+    fn locate<'r>(&'r self, cmd: &str, args: &'r [String]) -> Result<Resolved<'r>, ModCliError> {
+        let resolved = self.route(cmd, args)?;
+        if let Resolved::Command(command, _) = resolved {
+            if let Some(dep) = command.deprecated() {
+                self.warn_deprecated(command.name(), &dep);
+            }
+        }
+        Ok(match resolved {
+            Resolved::Command(command, rest)
+                if wants_help(command, rest) && self.is_visible(command) =>
+            {
+                Resolved::Help(command.name().to_string())
+            }
+            other => other,
+        })
+    }
+
+    // Strip optional prefix `<prefix>:` without intermediate allocations
+    fn strip_route_prefix<'a>(&self, cmd: &'a str) -> &'a str {
+        if !self.prefix.is_empty() && cmd.len() > self.prefix.len() + 1 {
             let (maybe_prefix, rest_with_colon) = cmd.split_at(self.prefix.len());
             if maybe_prefix == self.prefix && rest_with_colon.as_bytes().first() == Some(&b':') {
-                &rest_with_colon[1..]
-            } else {
-                cmd
+                return &rest_with_colon[1..];
             }
-        } else {
-            cmd
-        };
+        }
+        cmd
+    }
+
+    // Spec of the command `words` (command word first) would run, if any.
+    pub(crate) fn spec_for(&self, words: &[String]) -> Option<CommandSpec> {
+        let (first, rest) = words.split_first()?;
+        let (path, _) = self.walk(self.strip_route_prefix(first), rest)?;
+        self.commands.get(path.as_str())?.spec()
+    }
 
+    fn route<'r>(&'r self, cmd: &str, args: &'r [String]) -> Result<Resolved<'r>, ModCliError> {
+        let token = self.strip_route_prefix(cmd);
+        self.check_deprecated_words(token, args);
+
+        // A hit only stands when the next word doesn't lead deeper
         #[cfg(feature = "dispatch-cache")]
         if let Ok(guard) = self.cache.lock() {
             if let Some((ref t, ref p)) = *guard {
-                if t == token {
+                if t == token && !args.first().is_some_and(|w| self.step(p, w).is_some()) {
                     if let Some(command) = self.commands.get(p.as_str()) {
-                        command.validate(args)?;
-                        command.execute_with(args, self);
-                        return Ok(());
+                        return Ok(Resolved::Command(command.as_ref(), args));
                     }
                 }
             }
         }
 
-        // Try direct name
-        if let Some(command) = self.commands.get(token) {
-            if let Err(err) = self.is_authorized(command.as_ref(), args) {
-                return Err(ModCliError::InvalidUsage(err));
-            }
-            command.validate(args)?;
-            command.execute_with(args, self);
+        // Longest-prefix walk: `a b c ...` -> `a:b:c`, aliases resolved per level
+        let Some((path, consumed)) = self.walk(token, args) else {
+            return Err(self.unknown(cmd, args, vec![token.to_string()]));
+        };
+        let rest = &args[consumed..];
+        if let Some(command) = self.commands.get(path.as_str()) {
             #[cfg(feature = "dispatch-cache")]
-            if let Ok(mut guard) = self.cache.lock() {
-                *guard = Some((token.to_string(), token.to_string()));
-            }
-            if let Some(ref post) = self.post_hook {
-                post(cmd, args, Ok(()));
-            }
-            return Ok(());
-        }
-
-        // Try alias mapping
-        if let Some(primary) = self.aliases.get(token) {
-            if let Some(command) = self.commands.get(primary.as_str()) {
-                if let Err(err) = self.is_authorized(command.as_ref(), args) {
-                    return Err(ModCliError::InvalidUsage(err));
-                }
-                command.validate(args)?;
-                command.execute_with(args, self);
-                #[cfg(feature = "dispatch-cache")]
+            if consumed == 0 {
                 if let Ok(mut guard) = self.cache.lock() {
-                    *guard = Some((token.to_string(), primary.clone()));
-                }
-                if let Some(ref post) = self.post_hook {
-                    post(cmd, args, Ok(()));
+                    *guard = Some((token.to_string(), path.clone()));
                 }
-                return Ok(());
             }
+            return Ok(Resolved::Command(command.as_ref(), rest));
         }
 
-        // Two-token nested dispatch: "parent child ..." -> "parent:child"
-        if !args.is_empty() {
-            let combined = format!("{token}:{}", args[0]);
-            if let Some(command) = self.commands.get(combined.as_str()) {
-                let rest = &args[1..];
-                if let Err(err) = self.is_authorized(command.as_ref(), rest) {
-                    return Err(ModCliError::InvalidUsage(err));
-                }
-                command.validate(rest)?;
-                command.execute_with(rest, self);
-                if let Some(ref post) = self.post_hook {
-                    post(cmd, args, Ok(()));
-                }
-                return Ok(());
-            }
+        // Group node: bare invocation (or `--help`) prints its children;
+        // anything else is unknown
+        if rest.is_empty() || matches!(rest, [flag] if flag == "--help" || flag == "-h") {
+            return Ok(Resolved::Group(path));
         }
-        let err = ModCliError::UnknownCommand(cmd.to_string());
+        let mut words = vec![token.to_string()];
+        words.extend(args[..=consumed].iter().cloned());
+        Err(self.unknown(cmd, args, words))
+    }
+
+    // Build the unknown-command error for the typed `words` and run the post hook.
+    fn unknown(&self, cmd: &str, args: &[String], words: Vec<String>) -> ModCliError {
         if let Some(ref post) = self.post_hook {
             post(cmd, args, Err("unknown"));
         }
-        Err(err)
+        let name = if words.len() > 1 {
+            words.join(" ")
+        } else {
+            cmd.to_string()
+        };
+        ModCliError::UnknownCommand {
+            name,
+            suggestions: self.suggest(&words),
+        }
+    }
+
+    // Authorize, parse against the optional spec and validate.
+    fn prepare(&self, command: &dyn Command, rest: &[String]) -> Result<ParsedArgs, ModCliError> {
+        if let Err(err) = self.is_authorized(command, rest) {
+            return Err(ModCliError::Unauthorized(err));
+        }
+        let parsed = match command.spec() {
+            Some(spec) => spec.parse(rest)?,
+            None => ParsedArgs::unparsed(rest),
+        };
+        command.validate(rest)?;
+        Ok(parsed)
+    }
+
+    // Prepare, then run with a `Context`.
+    // `cmd`/`args` are the raw invocation; `rest` are the command's own arguments.
+    #[inline(always)]
+    fn dispatch(
+        &self,
+        command: &dyn Command,
+        cmd: &str,
+        args: &[String],
+        rest: &[String],
+    ) -> Result<ExitCode, ModCliError> {
+        let parsed = self.prepare(command, rest)?;
+        let ctx = Context::new(self, command.name(), invocation(cmd, args), parsed);
+        command.run(&ctx)
     }
 
     #[cfg(feature = "internal-commands")]
@@ -489,6 +1046,7 @@ impl CommandRegistry {
         self.register(Box::new(HelloCommand));
         self.register(Box::new(FrameworkCommand));
         self.register(Box::new(HelpCommand::new()));
+        self.register(Box::new(ShellCommand::new()));
     }
 
     // Note: JSON loader has been removed from core. Use code registration.
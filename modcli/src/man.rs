@@ -0,0 +1,380 @@
+//! Man page and Markdown reference generation from the command registry.
+//!
+//! Pages are built from the registered, visible commands: names, `help()` text
+//! (converted with `output::markdown::render_roff`), aliases, `parent:child`
+//! namespaces and, when declared, `Command::spec()` arguments. Flag-style
+//! aliases (`--help`, `-h`) are left out. Hidden and capability-gated commands
+//! are filtered through `CommandRegistry::is_visible`.
+//!
+//! # Example
+//! ```no_run
+//! use modcli::man::ManOptions;
+//! use modcli::ModCli;
+//!
+//! let cli = ModCli::new();
+//! let opts = ManOptions::new("tool").version("1.2.0").about("Deployment tool");
+//! std::fs::write("tool.1", cli.registry.render_man(&opts)).unwrap();
+//! for name in cli.registry.documented_commands() {
+//!     if let Some(page) = cli.registry.render_man_command(&name, &opts) {
+//!         std::fs::write(format!("tool-{}.1", name.replace(':', "-")), page).unwrap();
+//!     }
+//! }
+//! ```
+
+use crate::command::Command;
+use crate::help::summary;
+use crate::loader::CommandRegistry;
+use crate::output::markdown::{render_roff, roff_escape};
+use crate::spec::{ArgKind, ArgSpec};
+use crate::tree::{alias_path, display_path};
+
+/// Metadata for generated man pages.
+#[derive(Clone, Debug)]
+pub struct ManOptions {
+    name: String,
+    section: String,
+    version: Option<String>,
+    date: Option<String>,
+    about: Option<String>,
+    description: Option<String>,
+    manual: Option<String>,
+}
+
+impl ManOptions {
+    /// Options for the program `name` (section 1 by default).
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            section: "1".into(),
+            version: None,
+            date: None,
+            about: None,
+            description: None,
+            manual: None,
+        }
+    }
+
+    pub fn section(mut self, section: &str) -> Self {
+        self.section = section.to_string();
+        self
+    }
+
+    pub fn version(mut self, version: &str) -> Self {
+        self.version = Some(version.to_string());
+        self
+    }
+
+    /// Date shown in the page footer (e.g. `2025-09-09`).
+    pub fn date(mut self, date: &str) -> Self {
+        self.date = Some(date.to_string());
+        self
+    }
+
+    /// One-line summary used in NAME.
+    pub fn about(mut self, about: &str) -> Self {
+        self.about = Some(about.to_string());
+        self
+    }
+
+    /// Longer DESCRIPTION text (Markdown subset).
+    pub fn description(mut self, description: &str) -> Self {
+        self.description = Some(description.to_string());
+        self
+    }
+
+    /// Manual title shown in the page header (e.g. `Tool Manual`).
+    pub fn manual(mut self, manual: &str) -> Self {
+        self.manual = Some(manual.to_string());
+        self
+    }
+
+    fn header(&self, title: &str) -> String {
+        let source = match self.version {
+            Some(ref v) => format!("{} {v}", self.name),
+            None => self.name.clone(),
+        };
+        format!(
+            ".TH \"{}\" \"{}\" \"{}\" \"{}\" \"{}\"\n",
+            roff_escape(&title.to_uppercase()),
+            self.section,
+            self.date.as_deref().unwrap_or(""),
+            roff_escape(&source),
+            roff_escape(self.manual.as_deref().unwrap_or("")),
+        )
+    }
+}
+
+impl CommandRegistry {
+    /// Names of visible commands in documentation order (top-level first, then
+    /// namespaced commands), each sorted alphabetically.
+    pub fn documented_commands(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .all()
+            .filter(|c| self.is_visible(c.as_ref()))
+            .map(|c| c.name().to_string())
+            .collect();
+        names.sort_by(|a, b| (a.contains(':'), a).cmp(&(b.contains(':'), b)));
+        names
+    }
+
+    /// Render the main man page listing every visible command.
+    pub fn render_man(&self, opts: &ManOptions) -> String {
+        let bin = &opts.name;
+        let mut out = opts.header(bin);
+
+        out.push_str(".SH NAME\n");
+        match opts.about {
+            Some(ref a) => out.push_str(&format!("{} \\- {}\n", roff_escape(bin), roff_escape(a))),
+            None => out.push_str(&format!("{}\n", roff_escape(bin))),
+        }
+
+        out.push_str(".SH SYNOPSIS\n");
+        out.push_str(&format!(
+            "\\fB{}\\fR \\fIcommand\\fR [\\fIargs\\fR...]\n",
+            roff_escape(bin)
+        ));
+
+        if let Some(ref d) = opts.description {
+            out.push_str(".SH DESCRIPTION\n");
+            out.push_str(&render_roff(d));
+        }
+
+        let names = self.documented_commands();
+        if !names.is_empty() {
+            out.push_str(".SH COMMANDS\n");
+        }
+        let mut namespace = String::new();
+        for name in &names {
+            let Some(cmd) = self.get(name) else { continue };
+            if let Some((ns, _)) = name.rsplit_once(':') {
+                if ns != namespace {
+                    namespace = ns.to_string();
+                    out.push_str(&format!(
+                        ".SS \"{}\"\n",
+                        roff_escape(&display_path(&namespace))
+                    ));
+                    if let Some(help) = self.group(&namespace).and_then(|g| g.help.as_deref()) {
+                        out.push_str(&render_roff(help));
+                    }
+                }
+            }
+            out.push_str(".TP\n");
+            out.push_str(&synopsis_line(cmd));
+            out.push_str(&render_roff(cmd.help().unwrap_or("No description.")));
+            if let Some(spec) = cmd.spec() {
+                for arg in spec.args() {
+                    out.push_str(".RS\n.TP\n");
+                    out.push_str(&arg_label(arg));
+                    out.push_str(&arg_body(arg));
+                    out.push_str(".RE\n");
+                }
+            }
+        }
+
+        let aliases: Vec<(String, String)> = names
+            .iter()
+            .filter_map(|n| self.get(n))
+            .flat_map(|c| {
+                c.aliases()
+                    .iter()
+                    .filter(|a| !a.starts_with('-'))
+                    .map(|a| {
+                        (
+                            display_path(&alias_path(c.name(), a)),
+                            display_path(c.name()),
+                        )
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        if !aliases.is_empty() {
+            out.push_str(".SH ALIASES\n");
+            for (alias, target) in aliases {
+                out.push_str(&format!(
+                    ".TP\n\\fB{}\\fR\nAlias for \\fB{}\\fR.\n",
+                    roff_escape(&alias),
+                    roff_escape(&target)
+                ));
+            }
+        }
+
+        if !names.is_empty() {
+            out.push_str(".SH SEE ALSO\n");
+            let refs: Vec<String> = names
+                .iter()
+                .map(|n| {
+                    format!(
+                        "\\fB{}\\fR({})",
+                        roff_escape(&page_name(bin, n)),
+                        opts.section
+                    )
+                })
+                .collect();
+            out.push_str(&refs.join(",\n"));
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Render a dedicated page for one visible command (e.g. `tool-deploy(1)`).
+    /// Returns `None` if the command is unknown or not visible.
+    pub fn render_man_command(&self, name: &str, opts: &ManOptions) -> Option<String> {
+        let cmd = self.get(name).filter(|c| self.is_visible(*c))?;
+        let bin = &opts.name;
+        let page = page_name(bin, cmd.name());
+        let mut out = opts.header(&page);
+
+        out.push_str(".SH NAME\n");
+        match summary(cmd.help()) {
+            Some(s) => out.push_str(&format!("{} \\- {}\n", roff_escape(&page), roff_escape(&s))),
+            None => out.push_str(&format!("{}\n", roff_escape(&page))),
+        }
+
+        out.push_str(".SH SYNOPSIS\n");
+        out.push_str(&format!("\\fB{}\\fR ", roff_escape(bin)));
+        out.push_str(&synopsis_line(cmd));
+
+        if let Some(help) = cmd.help() {
+            out.push_str(".SH DESCRIPTION\n");
+            out.push_str(&render_roff(help));
+        }
+
+        if let Some(spec) = cmd.spec() {
+            if !spec.args().is_empty() {
+                out.push_str(".SH OPTIONS\n");
+                for arg in spec.args() {
+                    out.push_str(".TP\n");
+                    out.push_str(&arg_label(arg));
+                    out.push_str(&arg_body(arg));
+                }
+            }
+        }
+
+        let aliases: Vec<String> = cmd
+            .aliases()
+            .iter()
+            .filter(|a| !a.starts_with('-'))
+            .map(|a| {
+                let path = display_path(&alias_path(cmd.name(), a));
+                format!("\\fB{}\\fR", roff_escape(&path))
+            })
+            .collect();
+        if !aliases.is_empty() {
+            out.push_str(".SH ALIASES\n");
+            out.push_str(&aliases.join(", "));
+            out.push('\n');
+        }
+
+        out.push_str(".SH SEE ALSO\n");
+        out.push_str(&format!("\\fB{}\\fR({})\n", roff_escape(bin), opts.section));
+        Some(out)
+    }
+
+    /// Render a Markdown command reference (e.g. for `docs/COMMANDS.md`).
+    pub fn render_markdown_reference(&self) -> String {
+        let mut out = String::from("# Command Reference\n");
+        for name in self.documented_commands() {
+            let Some(cmd) = self.get(&name) else { continue };
+            let display = name.replace(':', " ");
+            out.push_str(&format!("\n## `{display}`\n\n"));
+            if let Some(help) = cmd.help() {
+                // Demote headings so they nest under the command heading
+                for line in help.lines() {
+                    if line.trim_start().starts_with('#') {
+                        out.push_str("##");
+                        out.push_str(line.trim_start());
+                    } else {
+                        out.push_str(line);
+                    }
+                    out.push('\n');
+                }
+                out.push('\n');
+            }
+            match cmd.spec() {
+                Some(spec) => {
+                    out.push_str(&format!("**Usage:** `{display} {}`\n", spec.usage()));
+                    if !spec.args().is_empty() {
+                        out.push_str("\n| Argument | Description |\n|---|---|\n");
+                        for arg in spec.args() {
+                            let mut label = format!("`{}`", arg.display_name());
+                            if let (Some(s), Some(_)) = (arg.short, &arg.long) {
+                                label = format!("`-{s}`, {label}");
+                            }
+                            let mut desc = arg.help.clone().unwrap_or_default();
+                            if arg.required {
+                                desc.push_str(" (required)");
+                            }
+                            if let Some(ref d) = arg.default {
+                                desc.push_str(&format!(" [default: `{d}`]"));
+                            }
+                            out.push_str(&format!(
+                                "| {label} | {} |\n",
+                                desc.trim().replace('|', "\\|")
+                            ));
+                        }
+                    }
+                }
+                None => out.push_str(&format!("**Usage:** `{display} [args...]`\n")),
+            }
+            let list: Vec<String> = cmd
+                .aliases()
+                .iter()
+                .filter(|a| !a.starts_with('-'))
+                .map(|a| format!("`{}`", display_path(&alias_path(cmd.name(), a))))
+                .collect();
+            if !list.is_empty() {
+                out.push_str(&format!("\n**Aliases:** {}\n", list.join(", ")));
+            }
+        }
+        out
+    }
+}
+
+// `tool` + `node:drain` -> `tool-node-drain`
+fn page_name(bin: &str, command: &str) -> String {
+    format!("{bin}-{}", command.replace(':', "-"))
+}
+
+fn synopsis_line(cmd: &dyn Command) -> String {
+    let name = format!("\\fB{}\\fR", roff_escape(&cmd.name().replace(':', " ")));
+    match cmd.spec() {
+        Some(spec) => format!("{name} {}\n", roff_escape(&spec.usage())),
+        None => format!("{name} [\\fIargs\\fR...]\n"),
+    }
+}
+
+fn arg_label(arg: &ArgSpec) -> String {
+    let mut parts: Vec<String> = Vec::new();
+    if arg.kind == ArgKind::Positional {
+        parts.push(format!("\\fI{}\\fR", roff_escape(&arg.name)));
+    } else {
+        if let Some(s) = arg.short {
+            parts.push(format!("\\fB\\-{}\\fR", roff_escape(&s.to_string())));
+        }
+        if let Some(ref l) = arg.long {
+            parts.push(format!("\\fB\\-\\-{}\\fR", roff_escape(l)));
+        }
+    }
+    let mut label = parts.join(", ");
+    if arg.kind == ArgKind::Option {
+        label.push_str(&format!(" \\fI{}\\fR", roff_escape(&arg.placeholder())));
+    }
+    label.push('\n');
+    label
+}
+
+fn arg_body(arg: &ArgSpec) -> String {
+    let mut body = arg.help.clone().unwrap_or_default();
+    if !arg.choices.is_empty() {
+        body.push_str(&format!(" (one of: {})", arg.choices.join(", ")));
+    }
+    if let Some(ref d) = arg.default {
+        body.push_str(&format!(" [default: {d}]"));
+    }
+    let body = body.trim();
+    if body.is_empty() {
+        String::new()
+    } else {
+        render_roff(body)
+    }
+}
@@ -0,0 +1,123 @@
+//! Composable middleware around command dispatch.
+//!
+//! Layers added with `CommandRegistry::use_middleware` wrap every invocation
+//! (sync and async) in registration order: `before` runs outermost-first
+//! and may rewrite the command word and arguments or short-circuit with a
+//! result; `after` runs innermost-first and sees, and may replace, the real
+//! `Result<ExitCode, ModCliError>`. Only layers whose `before` let the call
+//! through see it in `after`.
+//!
+//! # Example
+//! ```
+//! use modcli::error::ModCliError;
+//! use modcli::loader::CommandRegistry;
+//! use modcli::middleware;
+//!
+//! let mut reg = CommandRegistry::new();
+//! // Audit / timing
+//! reg.use_middleware(middleware::after(|call, result| {
+//!     eprintln!("{} -> {:?} in {:?}", call.command, result.as_ref().ok(), call.elapsed());
+//! }));
+//! // Auth: short-circuit before resolution
+//! reg.use_middleware(middleware::before(|call| {
+//!     (call.command == "admin")
+//!         .then(|| Err(ModCliError::InvalidUsage("admin is disabled".into())))
+//! }));
+//! assert!(reg.try_execute("admin", &[]).is_err());
+//! ```
+
+use crate::error::ModCliError;
+use crate::exit::ExitCode;
+use std::time::{Duration, Instant};
+
+/// Outcome of a dispatched call.
+pub type CallResult = Result<ExitCode, ModCliError>;
+
+/// One invocation as seen by middleware.
+#[derive(Clone, Debug)]
+pub struct Call {
+    /// Command word as typed (may include the registry prefix).
+    pub command: String,
+    /// Remaining arguments, including nested command words.
+    pub args: Vec<String>,
+    path: Option<String>,
+    started: Instant,
+}
+
+impl Call {
+    pub fn new(command: &str, args: &[String]) -> Self {
+        Self {
+            command: command.to_string(),
+            args: args.to_vec(),
+            path: None,
+            started: Instant::now(),
+        }
+    }
+
+    /// Resolved command or group path (`a:b:c`); set once routing succeeded,
+    /// so it is available in `after` only.
+    pub fn path(&self) -> Option<&str> {
+        self.path.as_deref()
+    }
+
+    pub(crate) fn set_path(&mut self, path: String) {
+        self.path = Some(path);
+    }
+
+    /// Time since the call entered the middleware stack.
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+}
+
+/// A dispatch layer. Both methods default to pass-through.
+pub trait Middleware: Send + Sync {
+    /// Inspect or rewrite the call. Return `Some(result)` to stop here
+    /// without running the command (or any inner layer).
+    fn before(&self, _call: &mut Call) -> Option<CallResult> {
+        None
+    }
+
+    /// Inspect or replace the result.
+    fn after(&self, _call: &Call, _result: &mut CallResult) {}
+}
+
+/// Middleware from a `before` closure.
+pub struct Before<F>(F);
+
+impl<F> Middleware for Before<F>
+where
+    F: Fn(&mut Call) -> Option<CallResult> + Send + Sync,
+{
+    fn before(&self, call: &mut Call) -> Option<CallResult> {
+        (self.0)(call)
+    }
+}
+
+/// Middleware from an `after` closure.
+pub struct After<F>(F);
+
+impl<F> Middleware for After<F>
+where
+    F: Fn(&Call, &mut CallResult) + Send + Sync,
+{
+    fn after(&self, call: &Call, result: &mut CallResult) {
+        (self.0)(call, result)
+    }
+}
+
+/// Layer that only runs before dispatch.
+pub fn before<F>(f: F) -> Before<F>
+where
+    F: Fn(&mut Call) -> Option<CallResult> + Send + Sync,
+{
+    Before(f)
+}
+
+/// Layer that only runs after dispatch.
+pub fn after<F>(f: F) -> After<F>
+where
+    F: Fn(&Call, &mut CallResult) + Send + Sync,
+{
+    After(f)
+}
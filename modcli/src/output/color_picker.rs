@@ -45,7 +45,7 @@ pub fn list_named_colors() -> Vec<(&'static str, Color)> {
 pub fn print_color_swatch() {
     hook::deprecated("color_picker::print_color_swatch(), use colors::print() instead.");
     for (name, color) in list_named_colors() {
-        println!("{}", format!("{:<15}", name).with(color));
+        crate::output::print::line(&format!("{:<15}", name).with(color).to_string());
     }
 }
 
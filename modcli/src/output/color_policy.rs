@@ -0,0 +1,355 @@
+//! When and how deep to color output.
+//!
+//! Everything modcli styles (`style::build`, message shortcuts, help pages,
+//! markdown, tables, progress bars, gradients, pickers) goes through `paint`,
+//! which consults one process-wide policy:
+//!
+//! - `ColorChoice::Never`: no escape sequences at all.
+//! - `ColorChoice::Always`: color at the detected depth, even when piped.
+//! - `ColorChoice::Auto` (default): color only when stdout is a terminal,
+//!   `NO_COLOR` is unset, `TERM` isn't `dumb` and `CLICOLOR` isn't `0`.
+//!   A non-empty `CLICOLOR_FORCE` other than `0` forces color on.
+//!
+//! The depth is `TrueColor` when `COLORTERM` is `truecolor`/`24bit` (or in
+//! Windows Terminal), `Ansi256` when `TERM` mentions `256color`, and `Basic`
+//! otherwise. RGB and 256-palette colors are downsampled to fit; `--color`
+//! sets the choice, and `set_level` pins a depth.
+//!
+//! # Example
+//! ```
+//! use modcli::output::color_policy::{self, ColorChoice, ColorLevel};
+//! use crossterm::style::Color;
+//!
+//! color_policy::set_choice(ColorChoice::Never);
+//! assert_eq!(color_policy::fg("plain", Color::Red), "plain");
+//!
+//! let red = Color::Rgb { r: 250, g: 10, b: 10 };
+//! assert_eq!(color_policy::downsample(red, ColorLevel::Basic), Some(Color::Red));
+//! ```
+
+use crossterm::style::{Attribute, Color};
+use std::fmt::Write as _;
+use std::io::IsTerminal;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+use crate::error::ModCliError;
+use crate::testing;
+
+/// Whether to color output.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorChoice {
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl FromStr for ColorChoice {
+    type Err = ModCliError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "auto" => Ok(Self::Auto),
+            "always" => Ok(Self::Always),
+            "never" => Ok(Self::Never),
+            other => Err(ModCliError::InvalidUsage(format!(
+                "unsupported color choice: {other} (expected auto, always or never)"
+            ))),
+        }
+    }
+}
+
+/// Color depth a terminal supports, from none to 24-bit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColorLevel {
+    None,
+    /// The 16 standard colors.
+    Basic,
+    Ansi256,
+    TrueColor,
+}
+
+impl ColorLevel {
+    fn from_u8(n: u8) -> Self {
+        match n {
+            1 => Self::Basic,
+            2 => Self::Ansi256,
+            3 => Self::TrueColor,
+            _ => Self::None,
+        }
+    }
+}
+
+// 0 = Auto, 1 = Always, 2 = Never.
+static CHOICE: AtomicU8 = AtomicU8::new(0);
+// 0 = detect, otherwise `ColorLevel as u8 + 1`.
+static PINNED: AtomicU8 = AtomicU8::new(0);
+static DETECTED: OnceLock<(ColorLevel, ColorLevel)> = OnceLock::new();
+
+/// Set the process-wide color choice.
+pub fn set_choice(choice: ColorChoice) {
+    // Under `testing::TestCli` the choice belongs to the session.
+    if testing::update_settings(|s| s.color = choice) {
+        return;
+    }
+    let n = match choice {
+        ColorChoice::Auto => 0,
+        ColorChoice::Always => 1,
+        ColorChoice::Never => 2,
+    };
+    CHOICE.store(n, Ordering::SeqCst);
+    // Keep crossterm and console (used by dependencies) in agreement.
+    let enabled = enabled();
+    crossterm::style::force_color_output(enabled);
+    console::set_colors_enabled(enabled);
+    console::set_colors_enabled_stderr(enabled);
+}
+
+pub fn choice() -> ColorChoice {
+    if let Some(settings) = testing::settings() {
+        return settings.color;
+    }
+    match CHOICE.load(Ordering::SeqCst) {
+        1 => ColorChoice::Always,
+        2 => ColorChoice::Never,
+        _ => ColorChoice::Auto,
+    }
+}
+
+/// Pin the color depth used when color is on (`None` = detect).
+pub fn set_level(level: Option<ColorLevel>) {
+    PINNED.store(level.map_or(0, |l| l as u8 + 1), Ordering::SeqCst);
+}
+
+/// Effective color depth for the current choice and environment.
+pub fn level() -> ColorLevel {
+    let choice = choice();
+    if choice == ColorChoice::Never {
+        return ColorLevel::None;
+    }
+    let (auto, depth) = *DETECTED.get_or_init(|| {
+        let env = |key: &str| std::env::var(key).ok();
+        let tty = std::io::stdout().is_terminal();
+        (
+            detect(ColorChoice::Auto, env, tty),
+            detect(ColorChoice::Always, env, tty),
+        )
+    });
+    if choice == ColorChoice::Auto && auto == ColorLevel::None {
+        return ColorLevel::None;
+    }
+    match PINNED.load(Ordering::SeqCst) {
+        0 => depth,
+        n => ColorLevel::from_u8(n - 1),
+    }
+}
+
+/// True when styled output should carry escape sequences.
+pub fn enabled() -> bool {
+    level() != ColorLevel::None
+}
+
+/// Resolve `choice` against an environment (`env` looks up variables) and
+/// whether stdout is a terminal.
+pub fn detect(
+    choice: ColorChoice,
+    env: impl Fn(&str) -> Option<String>,
+    is_tty: bool,
+) -> ColorLevel {
+    let set = |key: &str| env(key).filter(|v| !v.is_empty());
+    let term = env("TERM").unwrap_or_default();
+    let depth = match set("COLORTERM").as_deref() {
+        Some("truecolor") | Some("24bit") => ColorLevel::TrueColor,
+        _ if set("WT_SESSION").is_some() => ColorLevel::TrueColor,
+        _ if term.contains("256color") => ColorLevel::Ansi256,
+        _ => ColorLevel::Basic,
+    };
+    match choice {
+        ColorChoice::Never => ColorLevel::None,
+        ColorChoice::Always => depth,
+        ColorChoice::Auto => {
+            if set("CLICOLOR_FORCE").is_some_and(|v| v != "0") {
+                depth
+            } else if set("NO_COLOR").is_some()
+                || !is_tty
+                || term == "dumb"
+                || env("CLICOLOR").as_deref() == Some("0")
+            {
+                ColorLevel::None
+            } else {
+                depth
+            }
+        }
+    }
+}
+
+/// Fit `color` to `level`: RGB becomes the nearest 256-palette or basic
+/// color, 256-palette colors become basic ones, `None` drops it.
+pub fn downsample(color: Color, level: ColorLevel) -> Option<Color> {
+    match (level, color) {
+        (ColorLevel::None, _) => None,
+        (ColorLevel::TrueColor, c) => Some(c),
+        (ColorLevel::Ansi256, Color::Rgb { r, g, b }) => {
+            Some(Color::AnsiValue(rgb_to_256(r, g, b)))
+        }
+        (ColorLevel::Ansi256, c) => Some(c),
+        (ColorLevel::Basic, Color::Rgb { r, g, b }) => Some(nearest_basic(r, g, b)),
+        (ColorLevel::Basic, Color::AnsiValue(n)) if n < 16 => Some(BASIC[n as usize].0),
+        (ColorLevel::Basic, Color::AnsiValue(n)) => {
+            let (r, g, b) = palette_rgb(n);
+            Some(nearest_basic(r, g, b))
+        }
+        (ColorLevel::Basic, c) => Some(c),
+    }
+}
+
+/// `text` styled with the given colors and attributes, fitted to the
+/// current policy; plain `text` when color is off.
+pub fn paint(text: &str, fg: Option<Color>, bg: Option<Color>, attrs: &[Attribute]) -> String {
+    let start = sgr(fg, bg, attrs);
+    if start.is_empty() {
+        text.to_string()
+    } else {
+        format!("{start}{text}\x1b[0m")
+    }
+}
+
+/// `text` in the foreground `color`.
+pub fn fg(text: &str, color: Color) -> String {
+    paint(text, Some(color), None, &[])
+}
+
+/// The escape sequence that starts the given style (empty when color is off
+/// or there is nothing to set). Reset with `\x1b[0m`.
+pub fn sgr(fg: Option<Color>, bg: Option<Color>, attrs: &[Attribute]) -> String {
+    let level = level();
+    if level == ColorLevel::None {
+        return String::new();
+    }
+    let mut codes: Vec<String> = attrs.iter().filter_map(|a| attr_code(*a)).collect();
+    if let Some(c) = fg.and_then(|c| downsample(c, level)) {
+        codes.extend(color_code(c, false));
+    }
+    if let Some(c) = bg.and_then(|c| downsample(c, level)) {
+        codes.extend(color_code(c, true));
+    }
+    if codes.is_empty() {
+        String::new()
+    } else {
+        format!("\x1b[{}m", codes.join(";"))
+    }
+}
+
+fn attr_code(attr: Attribute) -> Option<String> {
+    let n = match attr {
+        Attribute::Bold => 1,
+        Attribute::Dim => 2,
+        Attribute::Italic => 3,
+        Attribute::Underlined => 4,
+        Attribute::SlowBlink => 5,
+        Attribute::Reverse => 7,
+        Attribute::CrossedOut => 9,
+        _ => return None,
+    };
+    Some(n.to_string())
+}
+
+fn color_code(color: Color, background: bool) -> Option<String> {
+    let base = if background { 40 } else { 30 };
+    let mut s = String::new();
+    match color {
+        Color::Reset => return Some((base + 9).to_string()),
+        Color::AnsiValue(n) => {
+            let _ = write!(s, "{};5;{n}", base + 8);
+        }
+        Color::Rgb { r, g, b } => {
+            let _ = write!(s, "{};2;{r};{g};{b}", base + 8);
+        }
+        named => {
+            let index = BASIC.iter().position(|(c, _)| *c == named)?;
+            let n = if index < 8 {
+                base + index
+            } else {
+                base + 60 + index - 8
+            };
+            let _ = write!(s, "{n}");
+        }
+    }
+    Some(s)
+}
+
+// The 16 basic colors in palette order, with xterm's default RGB values.
+const BASIC: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::DarkRed, (128, 0, 0)),
+    (Color::DarkGreen, (0, 128, 0)),
+    (Color::DarkYellow, (128, 128, 0)),
+    (Color::DarkBlue, (0, 0, 128)),
+    (Color::DarkMagenta, (128, 0, 128)),
+    (Color::DarkCyan, (0, 128, 128)),
+    (Color::Grey, (192, 192, 192)),
+    (Color::DarkGrey, (128, 128, 128)),
+    (Color::Red, (255, 0, 0)),
+    (Color::Green, (0, 255, 0)),
+    (Color::Yellow, (255, 255, 0)),
+    (Color::Blue, (0, 0, 255)),
+    (Color::Magenta, (255, 0, 255)),
+    (Color::Cyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+const CUBE: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> u32 {
+    let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2) as u32;
+    d(r1, r2) + d(g1, g2) + d(b1, b2)
+}
+
+fn nearest_basic(r: u8, g: u8, b: u8) -> Color {
+    BASIC
+        .iter()
+        .min_by_key(|(_, rgb)| distance(*rgb, (r, g, b)))
+        .map_or(Color::White, |(c, _)| *c)
+}
+
+fn rgb_to_256(r: u8, g: u8, b: u8) -> u8 {
+    let cube_index = |v: u8| {
+        (0..CUBE.len())
+            .min_by_key(|&i| (CUBE[i] as i32 - v as i32).abs())
+            .unwrap_or(0) as u8
+    };
+    let (ri, gi, bi) = (cube_index(r), cube_index(g), cube_index(b));
+    let cube = 16 + 36 * ri + 6 * gi + bi;
+    let cube_rgb = (CUBE[ri as usize], CUBE[gi as usize], CUBE[bi as usize]);
+
+    let avg = (r as u32 + g as u32 + b as u32) / 3;
+    let gray_index = (avg.saturating_sub(8) / 10).min(23) as u8;
+    let gray_value = 8 + 10 * gray_index;
+    let gray = 232 + gray_index;
+
+    if distance((gray_value, gray_value, gray_value), (r, g, b)) < distance(cube_rgb, (r, g, b)) {
+        gray
+    } else {
+        cube
+    }
+}
+
+fn palette_rgb(n: u8) -> (u8, u8, u8) {
+    match n {
+        0..=15 => BASIC[n as usize].1,
+        16..=231 => {
+            let i = n - 16;
+            (
+                CUBE[(i / 36) as usize],
+                CUBE[(i / 6 % 6) as usize],
+                CUBE[(i % 6) as usize],
+            )
+        }
+        _ => {
+            let v = 8 + 10 * (n - 232);
+            (v, v, v)
+        }
+    }
+}
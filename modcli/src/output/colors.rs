@@ -1,4 +1,5 @@
-use crossterm::style::{Color, Stylize};
+use crate::output::color_policy;
+use crossterm::style::Color;
 
 /// Default fallback color if a named color is not found.
 pub const DEFAULT: Color = Color::Black;
@@ -178,8 +179,8 @@ pub fn get(name: &str) -> Color {
 
 /// Prints a swatch of all named colors
 pub fn print() {
-    println!("Available Colors:");
+    crate::output::print::line("Available Colors:");
     for (name, color) in list() {
-        println!("{}", format!("{name:<20}").with(color));
+        crate::output::print::line(&color_policy::fg(&format!("{name:<20}"), color));
     }
 }
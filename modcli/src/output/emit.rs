@@ -0,0 +1,83 @@
+//! Rendering of serializable values for `Context::emit` (feature `structured-output`).
+//!
+//! `Human` mode renders arrays of objects as tables, objects as aligned
+//! `key  value` lines and scalars as plain text. Machine modes serialize the
+//! value as JSON, NDJSON (one array element per line) or YAML.
+
+use crate::error::ModCliError;
+use crate::output::mode::OutputMode;
+use crate::output::table::{render_table, TableMode, TableStyle};
+use serde::Serialize;
+use serde_json::Value;
+
+/// Render `value` for `mode`. The result ends with a newline.
+pub fn render<T: Serialize + ?Sized>(value: &T, mode: OutputMode) -> Result<String, ModCliError> {
+    let err = |e: &dyn std::fmt::Display| ModCliError::Other(format!("serialization failed: {e}"));
+    match mode {
+        OutputMode::Json => serde_json::to_string_pretty(value)
+            .map(|s| s + "\n")
+            .map_err(|e| err(&e)),
+        OutputMode::Ndjson => {
+            let lines = match serde_json::to_value(value).map_err(|e| err(&e))? {
+                Value::Array(items) => items,
+                other => vec![other],
+            };
+            let mut out = String::new();
+            for item in lines {
+                out.push_str(&item.to_string());
+                out.push('\n');
+            }
+            Ok(out)
+        }
+        #[cfg(feature = "output-yaml")]
+        OutputMode::Yaml => serde_yaml::to_string(value).map_err(|e| err(&e)),
+        #[cfg(not(feature = "output-yaml"))]
+        OutputMode::Yaml => Err(ModCliError::InvalidUsage(
+            "yaml output requires the `output-yaml` feature".into(),
+        )),
+        OutputMode::Human => Ok(human(&serde_json::to_value(value).map_err(|e| err(&e))?)),
+    }
+}
+
+fn human(value: &Value) -> String {
+    match value {
+        Value::Array(items) if !items.is_empty() && items.iter().all(Value::is_object) => {
+            // Columns in first-seen key order
+            let mut headers: Vec<&str> = Vec::new();
+            for item in items {
+                if let Value::Object(map) = item {
+                    for key in map.keys() {
+                        if !headers.contains(&key.as_str()) {
+                            headers.push(key);
+                        }
+                    }
+                }
+            }
+            let cells: Vec<Vec<String>> = items
+                .iter()
+                .map(|item| headers.iter().map(|h| cell(item.get(*h))).collect())
+                .collect();
+            let rows: Vec<Vec<&str>> = cells
+                .iter()
+                .map(|r| r.iter().map(String::as_str).collect())
+                .collect();
+            render_table(&headers, &rows, TableMode::Flex, TableStyle::Rounded)
+        }
+        Value::Array(items) => items.iter().map(|v| cell(Some(v)) + "\n").collect(),
+        Value::Object(map) => {
+            let width = map.keys().map(|k| k.chars().count()).max().unwrap_or(0);
+            map.iter()
+                .map(|(k, v)| format!("{k:<width$}  {}\n", cell(Some(v))))
+                .collect()
+        }
+        other => cell(Some(other)) + "\n",
+    }
+}
+
+fn cell(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(s)) => s.clone(),
+        Some(other) => other.to_string(),
+    }
+}
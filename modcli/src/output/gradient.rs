@@ -1,4 +1,5 @@
-use crossterm::style::{Color, Stylize};
+use crate::output::color_policy;
+use crossterm::style::Color;
 
 /// Generates a horizontal gradient between two colors.
 /// This can be used for printing rainbow text or gradual transitions.
@@ -18,7 +19,7 @@ pub fn generate(text: &str, start: Color, end: Color) -> String {
         let g = interpolate(get_g(&start), get_g(&end), i, steps);
         let b = interpolate(get_b(&start), get_b(&end), i, steps);
         let color = Color::Rgb { r, g, b };
-        result.push_str(&c.to_string().with(color).to_string());
+        result.push_str(&color_policy::fg(&c.to_string(), color));
     }
 
     /// Common easing modes for gradients
@@ -72,7 +73,7 @@ pub fn generate(text: &str, start: Color, end: Color) -> String {
             let b = interpolate(get_b(from), get_b(to), (seg_t * 100.0) as usize, 100);
 
             let color = Color::Rgb { r, g, b };
-            result.push_str(&c.to_string().with(color).to_string());
+            result.push_str(&color_policy::fg(&c.to_string(), color));
         }
 
         result
@@ -166,7 +167,7 @@ pub fn three_color(text: &str, start: Color, mid: Color, end: Color) -> String {
         let g = interpolate(get_g(&from), get_g(&to), step, steps);
         let b = interpolate(get_b(&from), get_b(&to), step, steps);
         let color = Color::Rgb { r, g, b };
-        result.push_str(&c.to_string().with(color).to_string());
+        result.push_str(&color_policy::fg(&c.to_string(), color));
     }
 
     result
@@ -195,7 +196,7 @@ pub fn multi_color(text: &str, colors: Vec<Color>) -> String {
         let b = interpolate(get_b(from), get_b(to), (seg_t * 100.0) as usize, 100);
 
         let color = Color::Rgb { r, g, b };
-        result.push_str(&c.to_string().with(color).to_string());
+        result.push_str(&color_policy::fg(&c.to_string(), color));
     }
 
     result
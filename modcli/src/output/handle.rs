@@ -0,0 +1,101 @@
+//! Output handle passed to commands through `Context::out()`.
+//!
+//! Defaults to the global `output::sink` (process stdout/stderr unless
+//! replaced). `Output::buffer()` captures both streams in memory so tests can
+//! assert on what a command wrote; `Output::to(sink)` uses any `OutputSink`.
+//!
+//! # Example
+//! ```
+//! use modcli::output::handle::Output;
+//!
+//! let out = Output::buffer();
+//! out.line("hello");
+//! out.eline("oops");
+//! assert_eq!(out.contents(), "hello\n");
+//! assert_eq!(out.err_contents(), "oops\n");
+//! ```
+
+use crate::output::sink::{self, MemorySink, OutputSink, Stream};
+use std::fmt;
+use std::sync::Arc;
+
+/// Cloneable handle to a pair of output streams (clones share the streams).
+#[derive(Clone, Default)]
+pub struct Output {
+    sink: Option<Arc<dyn OutputSink>>, // None: the global sink
+    memory: Option<Arc<MemorySink>>,
+}
+
+impl fmt::Debug for Output {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let target = match (&self.sink, &self.memory) {
+            (_, Some(_)) => "buffer",
+            (Some(_), None) => "sink",
+            (None, None) => "global",
+        };
+        f.debug_struct("Output").field("target", &target).finish()
+    }
+}
+
+impl Output {
+    /// The global sink (`output::sink::global`): process stdout and stderr
+    /// unless replaced.
+    pub fn stdio() -> Self {
+        Self::default()
+    }
+
+    /// In-memory buffers for both streams.
+    pub fn buffer() -> Self {
+        let memory = Arc::new(MemorySink::new());
+        Self {
+            sink: Some(memory.clone()),
+            memory: Some(memory),
+        }
+    }
+
+    /// A specific sink, for this handle only.
+    pub fn to(sink: Arc<dyn OutputSink>) -> Self {
+        Self {
+            sink: Some(sink),
+            memory: None,
+        }
+    }
+
+    fn emit(&self, stream: Stream, s: &str) {
+        match self.sink {
+            Some(ref sink) => sink.write(stream, s),
+            None => sink::write(stream, s),
+        }
+    }
+
+    /// Write text to the output stream.
+    pub fn write(&self, s: &str) {
+        self.emit(Stream::Out, s);
+    }
+
+    /// Write a line to the output stream.
+    pub fn line(&self, s: &str) {
+        self.emit(Stream::Out, &format!("{s}\n"));
+    }
+
+    /// Write a line to the error stream.
+    pub fn eline(&self, s: &str) {
+        self.emit(Stream::Err, &format!("{s}\n"));
+    }
+
+    /// Captured output (empty unless created with `buffer()`).
+    pub fn contents(&self) -> String {
+        self.memory
+            .as_ref()
+            .map(|m| m.contents())
+            .unwrap_or_default()
+    }
+
+    /// Captured error output (empty unless created with `buffer()`).
+    pub fn err_contents(&self) -> String {
+        self.memory
+            .as_ref()
+            .map(|m| m.err_contents())
+            .unwrap_or_default()
+    }
+}
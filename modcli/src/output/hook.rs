@@ -1,68 +1,94 @@
+use crate::globals;
 use crate::output::messages;
+use crate::output::mode;
 use crate::output::print;
+use crate::testing;
 #[cfg(feature = "tracing-logs")]
 use tracing::{debug as tdebug, error as terror, info as tinfo, warn as twarn};
 
+// Apply the message interceptor and record the result for `testing`.
+fn take(category: &str, msg: &str) -> String {
+    let m = messages::intercept(category, msg).into_owned();
+    testing::record_message(category, &m);
+    m
+}
+
 /// Hook for debug messages
 pub fn debug(msg: &str) {
-    let m = messages::intercept("debug", msg);
-    print::debug(&m);
+    let m = take("debug", msg);
+    if !globals::is_quiet() && !mode::route_record("debug", &m) {
+        print::debug(&m);
+    }
     #[cfg(feature = "tracing-logs")]
     tdebug!(target: "modcli", message = %m);
 }
 
 /// Hook for info-level messages
 pub fn info(msg: &str) {
-    let m = messages::intercept("info", msg);
-    print::info(&m);
+    let m = take("info", msg);
+    if !globals::is_quiet() && !mode::route_record("info", &m) {
+        print::info(&m);
+    }
     #[cfg(feature = "tracing-logs")]
     tinfo!(target: "modcli", message = %m);
 }
 
 /// Hook for warning-level messages
 pub fn warn(msg: &str) {
-    let m = messages::intercept("warn", msg);
-    print::warn(&m);
+    let m = take("warn", msg);
+    if !mode::route_record("warn", &m) {
+        print::warn(&m);
+    }
     #[cfg(feature = "tracing-logs")]
     twarn!(target: "modcli", message = %m);
 }
 
 /// Hook for error-level messages
 pub fn error(msg: &str) {
-    let m = messages::intercept("error", msg);
-    print::error(&m);
+    let m = take("error", msg);
+    if !mode::route_record("error", &m) {
+        print::error(&m);
+    }
     #[cfg(feature = "tracing-logs")]
     terror!(target: "modcli", message = %m);
 }
 
 /// Hook for success-level messages
 pub fn success(msg: &str) {
-    let m = messages::intercept("success", msg);
-    print::success(&m);
+    let m = take("success", msg);
+    if !globals::is_quiet() && !mode::route_record("success", &m) {
+        print::success(&m);
+    }
     #[cfg(feature = "tracing-logs")]
     tinfo!(target: "modcli", success = true, message = %m);
 }
 
 /// Hook for status-level messages
 pub fn status(msg: &str) {
-    let m = messages::intercept("status", msg);
-    print::status(&m);
+    let m = take("status", msg);
+    if !globals::is_quiet() && !mode::route_record("status", &m) {
+        print::status(&m);
+    }
     #[cfg(feature = "tracing-logs")]
     tinfo!(target: "modcli", status = true, message = %m);
 }
 
 /// Hook for deprecated messages
 pub fn deprecated(msg: &str) {
-    let m = messages::intercept("deprecated", msg);
-    print::deprecated(&m);
+    let m = take("deprecated", msg);
+    if !mode::route_record("deprecated", &m) {
+        print::deprecated(&m);
+    }
     #[cfg(feature = "tracing-logs")]
     twarn!(target: "modcli", deprecated = true, message = %m);
 }
 
 /// Hook for unknown command situations
 pub fn unknown(msg: &str) {
-    let m = messages::intercept("unknown", msg);
-    print::unknown(&m);
+    let m = take("unknown", msg);
+    if !mode::route_record("unknown", &m) {
+        print::unknown(&m);
+    }
     #[cfg(feature = "tracing-logs")]
     twarn!(target: "modcli", unknown_command = true, message = %m);
 }
@@ -2,6 +2,8 @@
 //! - Auto-detect best renderer (future): Kitty, iTerm2, Sixel
 //! - Universal fallback: ANSI truecolor mosaic
 
+use crate::output::color_policy;
+use crossterm::style::Color;
 #[cfg(feature = "images")]
 use image::{imageops::FilterType, DynamicImage, GenericImageView, Pixel};
 use std::path::Path;
@@ -104,13 +106,27 @@ fn render_mosaic(img: &DynamicImage, opts: ImageOpts) -> String {
             // Simple alpha blend over black
             let (r1, g1, b1) = alpha_over_black(r1, g1, b1, a1);
             let (r2, g2, b2) = alpha_over_black(r2, g2, b2, a2);
-            // ANSI 24-bit FG/BG + '▀'
-            use std::fmt::Write as _;
-            let _ = write!(out, "\x1b[38;2;{r1};{g1};{b1}m\x1b[48;2;{r2};{g2};{b2}m▀");
+            // FG/BG + '▀', downsampled to the terminal's color depth
+            out.push_str(&color_policy::sgr(
+                Some(Color::Rgb {
+                    r: r1,
+                    g: g1,
+                    b: b1,
+                }),
+                Some(Color::Rgb {
+                    r: r2,
+                    g: g2,
+                    b: b2,
+                }),
+                &[],
+            ));
+            out.push('▀');
         }
         out.push('\n');
         // Reset at end of line
-        out.push_str("\x1b[0m");
+        if color_policy::enabled() {
+            out.push_str("\x1b[0m");
+        }
     }
 
     out
@@ -1,17 +1,19 @@
-use crate::output::print;
-use std::io::{self, Write};
+use crate::output::{print, sink};
+use std::io;
 
-/// Prompts the user to confirm an action (yes/no).
+/// Prompts the user to confirm an action (yes/no). Returns false at the end
+/// of scripted input (`testing::TestCli`).
 pub fn prompt_confirm(question: &str) -> bool {
     let mut input = String::new();
     loop {
-        print!("{question} [y/n]: ");
-        if let Err(e) = io::stdout().flush() {
-            print::warn(&format!("flush failed: {e}"));
-        }
+        print::write(&format!("{question} [y/n]: "));
+        sink::flush();
 
         input.clear();
-        if let Err(e) = io::stdin().read_line(&mut input) {
+        if let Err(e) = crate::input::read_line(&mut input) {
+            if e.kind() == io::ErrorKind::UnexpectedEof {
+                return false;
+            }
             print::error(&format!("Error reading input: {e}. Try again."));
             continue;
         }
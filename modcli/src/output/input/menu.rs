@@ -1,14 +1,15 @@
+use crate::output::sink::{self, Stream};
 use crate::output::{hook, print};
 use crossterm::{
     cursor,
-    event::{self, Event, KeyCode},
+    event::{self, Event, KeyCode, KeyModifiers},
     execute,
     terminal::{self, ClearType},
 };
-use std::io::{stdout, Write};
+use std::io::Write;
 
 pub fn interactive_menu() -> Option<usize> {
-    let mut stdout = stdout();
+    let mut out = sink::writer(Stream::Out);
     let options = ["🍕 Pizza", "🍔 Burger", "🌮 Taco", "❌ Exit"];
     let mut selected = 0;
 
@@ -16,36 +17,40 @@ pub fn interactive_menu() -> Option<usize> {
         hook::error(&format!("failed to enable raw mode: {e}"));
         return None;
     }
-    if let Err(e) = execute!(stdout, terminal::Clear(ClearType::All)) {
+    if let Err(e) = execute!(out, terminal::Clear(ClearType::All)) {
         hook::warn(&format!("failed to clear terminal: {e}"));
     }
 
     loop {
-        if let Err(e) = execute!(stdout, cursor::MoveTo(0, 0)) {
+        if let Err(e) = execute!(out, cursor::MoveTo(0, 0)) {
             hook::warn(&format!("failed to move cursor: {e}"));
         }
 
-        println!("\nPick your poison:\n");
+        print::line("\nPick your poison:\n");
         for (i, option) in options.iter().enumerate() {
             if i == selected {
-                println!("  > {option}"); // Highlighted
+                print::line(&format!("  > {option}")); // Highlighted
             } else {
-                println!("    {option}"); // Normal
+                print::line(&format!("    {option}")); // Normal
             }
         }
 
-        if let Err(e) = stdout.flush() {
+        if let Err(e) = out.flush() {
             hook::warn(&format!("flush failed: {e}"));
         }
         match event::read() {
             Ok(Event::Key(key_event)) => match key_event.code {
+                // Raw mode swallows SIGINT; treat Ctrl-C as an interrupt
+                KeyCode::Char('c') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                    let _ = terminal::disable_raw_mode();
+                    crate::signals::interrupt();
+                    return None;
+                }
                 KeyCode::Up => {
                     selected = selected.saturating_sub(1);
                 }
-                KeyCode::Down => {
-                    if selected < options.len() - 1 {
-                        selected += 1;
-                    }
+                KeyCode::Down if selected < options.len() - 1 => {
+                    selected += 1;
                 }
                 KeyCode::Enter => {
                     if let Err(e) = terminal::disable_raw_mode() {
@@ -1,5 +1,4 @@
-use crate::output::print;
-use rpassword::read_password;
+use crate::output::{print, sink};
 
 /// Prompts the user for a password (no echo).
 ///
@@ -14,19 +13,19 @@ pub fn prompt_password(message: &str) -> String {
 /// Prompts for a password with validation.
 ///
 /// Behavior:
-/// - On I/O failure, logs an error via `output::print` and retries.
+/// - On I/O failure, logs an error via `output::print` and retries; at the end
+///   of scripted input (`testing::TestCli`) it returns an empty string.
 /// - On validation failure, shows a status message and retries.
 pub fn prompt_password_with_validation<F>(message: &str, validator: F) -> String
 where
     F: Fn(&str) -> Result<(), &str>,
 {
     loop {
-        print!("{message}: ");
-        if let Err(e) = std::io::Write::flush(&mut std::io::stdout()) {
-            print::warn(&format!("flush failed: {e}"));
-        }
-        let password = match read_password() {
+        print::write(&format!("{message}: "));
+        sink::flush();
+        let password = match crate::input::read_password() {
             Ok(p) => p,
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return String::new(),
             Err(e) => {
                 print::error(&format!("Failed to read password: {e}. Try again."));
                 continue;
@@ -1,10 +1,11 @@
-use crate::output::print;
-use std::io::{stdin, stdout, Write};
+use crate::output::{print, sink};
+use std::io::ErrorKind;
 
 /// Prompts for free-form text and returns the trimmed input.
 ///
 /// Behavior:
-/// - On I/O failure, logs an error via `output::print` and retries.
+/// - On I/O failure, logs an error via `output::print` and retries; at the end
+///   of scripted input (`testing::TestCli`) it returns an empty string.
 /// - If a validator is provided (see `prompt_text_with_validation`), it will retry on validation errors.
 pub fn prompt_text(message: &str) -> String {
     prompt_text_with_validation(message, |_| Ok(()))
@@ -21,12 +22,13 @@ where
 {
     let mut input = String::new();
     loop {
-        print!("{message}: ");
-        if let Err(e) = stdout().flush() {
-            print::warn(&format!("flush failed: {e}"));
-        }
+        print::write(&format!("{message}: "));
+        sink::flush();
         input.clear();
-        if let Err(e) = stdin().read_line(&mut input) {
+        if let Err(e) = crate::input::read_line(&mut input) {
+            if e.kind() == ErrorKind::UnexpectedEof {
+                return String::new();
+            }
             print::error(&format!("Error reading input: {e}. Try again."));
             continue;
         }
@@ -4,20 +4,23 @@
 /// - Lists: lines starting with "- " become bullets
 /// - Inline: **bold**, *italic*, `code`
 /// - Paragraphs: preserved
+///
+/// Plain text (markers removed, no escapes) when the color policy is off.
 pub fn render_markdown(input: &str) -> String {
+    let sgr = Sgr::current();
     let mut out = String::with_capacity(input.len() + 32);
     for line in input.lines() {
         let trimmed = line.trim_start();
-        let styled_line = if let Some(rest) = trimmed.strip_prefix("### ") {
-            format!("\x1b[1m{}\x1b[0m", render_inline(rest))
-        } else if let Some(rest) = trimmed.strip_prefix("## ") {
-            format!("\x1b[1m{}\x1b[0m", render_inline(rest))
-        } else if let Some(rest) = trimmed.strip_prefix("# ") {
-            format!("\x1b[1m{}\x1b[0m", render_inline(rest))
+        let heading = trimmed
+            .strip_prefix("### ")
+            .or_else(|| trimmed.strip_prefix("## "))
+            .or_else(|| trimmed.strip_prefix("# "));
+        let styled_line = if let Some(rest) = heading {
+            format!("{}{}{}", sgr.bold, render_inline(rest, &sgr), sgr.reset)
         } else if let Some(rest) = trimmed.strip_prefix("- ") {
-            format!(" • {}", render_inline(rest))
+            format!(" • {}", render_inline(rest, &sgr))
         } else {
-            render_inline(line)
+            render_inline(line, &sgr)
         };
         out.push_str(&styled_line);
         out.push('\n');
@@ -25,61 +28,126 @@ pub fn render_markdown(input: &str) -> String {
     out
 }
 
-fn render_inline(s: &str) -> String {
-    // Replace code spans first to avoid conflicts with bold/italic
-    let mut out = String::new();
-    let mut i = 0;
-    let bytes = s.as_bytes();
-    while i < bytes.len() {
-        if bytes[i] == b'`' {
-            if let Some(j) = find_next(bytes, i + 1, b'`') {
-                out.push_str("\x1b[7m"); // inverse
-                out.push_str(&s[i + 1..j]);
-                out.push_str("\x1b[0m");
-                i = j + 1;
-                continue;
+/// Minimal Markdown -> roff (man page) renderer for help text.
+/// Understands the same subset as `render_markdown`:
+/// - Headings become bold lines
+/// - "- " list items become bullet paragraphs
+/// - Inline: **bold** and `code` -> bold, *italic* -> italic
+/// - Blank lines become vertical space
+///
+/// Output is a sequence of roff lines intended for the body of a section or `.TP` entry.
+pub fn render_roff(input: &str) -> String {
+    let mut out = String::with_capacity(input.len() + 32);
+    for line in input.lines() {
+        let trimmed = line.trim();
+        let heading = trimmed
+            .strip_prefix("### ")
+            .or_else(|| trimmed.strip_prefix("## "))
+            .or_else(|| trimmed.strip_prefix("# "));
+        if trimmed.is_empty() {
+            out.push_str(".sp\n");
+        } else if let Some(rest) = heading {
+            out.push_str(".br\n");
+            out.push_str(&format!("\\fB{}\\fR\n", roff_inline(rest)));
+        } else if let Some(rest) = trimmed.strip_prefix("- ") {
+            out.push_str(".br\n");
+            out.push_str(&format!("\\(bu {}\n", roff_inline(rest)));
+        } else {
+            out.push_str(&roff_inline(trimmed));
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Escape text for roff: backslashes, hyphens and leading control characters.
+pub fn roff_escape(s: &str) -> String {
+    let escaped = s.replace('\\', "\\e").replace('-', "\\-");
+    if escaped.starts_with('.') || escaped.starts_with('\'') {
+        format!("\\&{escaped}")
+    } else {
+        escaped
+    }
+}
+
+fn roff_inline(s: &str) -> String {
+    let escaped = roff_escape(s);
+    let s2 = replace_code(&escaped, "\\fB", "\\fR");
+    let s3 = replace_enclosed(&s2, "**", "\\fB", "\\fR");
+    replace_enclosed(&s3, "*", "\\fI", "\\fR")
+}
+
+// Escape sequences for the ANSI renderer; empty when color is off.
+struct Sgr {
+    bold: &'static str,
+    italic: &'static str,
+    inverse: &'static str,
+    reset: &'static str,
+}
+
+impl Sgr {
+    fn current() -> Self {
+        if crate::output::color_policy::enabled() {
+            Sgr {
+                bold: "\x1b[1m",
+                italic: "\x1b[3m",
+                inverse: "\x1b[7m",
+                reset: "\x1b[0m",
+            }
+        } else {
+            Sgr {
+                bold: "",
+                italic: "",
+                inverse: "",
+                reset: "",
             }
         }
-        out.push(bytes[i] as char);
-        i += 1;
     }
+}
+
+fn render_inline(s: &str, sgr: &Sgr) -> String {
+    // Replace code spans first to avoid conflicts with bold/italic
+    let s2 = replace_code(s, sgr.inverse, sgr.reset);
     // Bold **...** and italic *...*
-    let s2 = out;
-    let s3 = replace_enclosed(&s2, "**", "\x1b[1m", "\x1b[0m");
-    replace_enclosed(&s3, "*", "\x1b[3m", "\x1b[0m")
+    let s3 = replace_enclosed(&s2, "**", sgr.bold, sgr.reset);
+    replace_enclosed(&s3, "*", sgr.italic, sgr.reset)
 }
 
-fn find_next(bytes: &[u8], mut i: usize, ch: u8) -> Option<usize> {
-    while i < bytes.len() {
-        if bytes[i] == ch {
-            return Some(i);
+fn replace_code(s: &str, start: &str, end: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(i) = rest.find('`') {
+        match rest[i + 1..].find('`') {
+            Some(j) => {
+                out.push_str(&rest[..i]);
+                out.push_str(start);
+                out.push_str(&rest[i + 1..i + 1 + j]);
+                out.push_str(end);
+                rest = &rest[i + 1 + j + 1..];
+            }
+            None => break,
         }
-        i += 1;
     }
-    None
+    out.push_str(rest);
+    out
 }
 
 fn replace_enclosed(s: &str, token: &str, start: &str, end: &str) -> String {
     let mut out = String::with_capacity(s.len());
-    let mut i = 0usize;
-    let tlen = token.len();
-    let bytes = s.as_bytes();
-    while i < bytes.len() {
-        if i + tlen <= bytes.len() && &s[i..i + tlen] == token {
-            if let Some(j) = find_token(&s[i + tlen..], token) {
+    let mut rest = s;
+    while let Some(i) = rest.find(token) {
+        let after = &rest[i + token.len()..];
+        match after.find(token) {
+            Some(j) => {
+                out.push_str(&rest[..i]);
                 out.push_str(start);
-                out.push_str(&s[i + tlen..i + tlen + j]);
+                out.push_str(&after[..j]);
                 out.push_str(end);
-                i = i + tlen + j + tlen;
-                continue;
+                rest = &after[j + token.len()..];
             }
+            None => break,
         }
-        out.push(bytes[i] as char);
-        i += 1;
     }
+    out.push_str(rest);
     out
 }
-
-fn find_token(hay: &str, token: &str) -> Option<usize> {
-    hay.find(token)
-}
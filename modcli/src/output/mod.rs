@@ -33,15 +33,21 @@
 //! let rows = vec![ vec!["Alice", "29"], vec!["Bob", "35"] ];
 //! render_table(&headers, &rows, TableMode::Flex, TableStyle::Rounded);
 //! ```
+pub mod color_policy;
 pub mod colors;
+#[cfg(feature = "structured-output")]
+pub mod emit;
+pub mod handle;
 pub mod hook;
 #[cfg(feature = "images")]
 pub mod images;
 pub mod input;
 pub mod markdown;
 pub mod messages;
+pub mod mode;
 pub mod print;
 pub mod progress;
+pub mod sink;
 pub mod style;
 pub mod table;
 pub mod themes;
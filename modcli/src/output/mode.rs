@@ -0,0 +1,152 @@
+//! Global output mode: human-readable text or machine-readable records.
+//!
+//! In `Human` mode (the default) everything renders as styled text. In the
+//! machine modes (`--output json|ndjson|yaml`), values emitted with
+//! `Context::emit` (feature `structured-output`) are serialized to stdout and
+//! `output::hook` messages are written to stderr as one JSON record per line:
+//! `{"level":"error","message":"..."}`.
+//!
+//! `ModCli::run` reads `--output` as a global option (see `globals`); the
+//! mode it selects lasts for that run only. `yaml` is rejected when parsing
+//! unless the `output-yaml` feature is enabled.
+//!
+//! # Example
+//! ```
+//! use modcli::output::mode::{self, OutputMode};
+//!
+//! mode::set_output_mode("json".parse().unwrap());
+//! assert_eq!(mode::output_mode(), OutputMode::Json);
+//! mode::set_output_mode(OutputMode::Human);
+//! ```
+
+use crate::error::ModCliError;
+use crate::output::sink::{self, Stream};
+use crate::testing;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+static MODE: AtomicU8 = AtomicU8::new(0);
+
+/// How command output is rendered.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputMode {
+    /// Styled text and tables.
+    #[default]
+    Human,
+    /// One pretty-printed JSON document per `emit`.
+    Json,
+    /// One compact JSON value per line (arrays are split into lines).
+    Ndjson,
+    /// YAML documents (feature `output-yaml`).
+    Yaml,
+}
+
+impl OutputMode {
+    /// True for the machine-readable modes.
+    pub fn is_machine(self) -> bool {
+        self != OutputMode::Human
+    }
+
+    fn as_u8(self) -> u8 {
+        match self {
+            OutputMode::Human => 0,
+            OutputMode::Json => 1,
+            OutputMode::Ndjson => 2,
+            OutputMode::Yaml => 3,
+        }
+    }
+
+    fn from_u8(v: u8) -> Self {
+        match v {
+            1 => OutputMode::Json,
+            2 => OutputMode::Ndjson,
+            3 => OutputMode::Yaml,
+            _ => OutputMode::Human,
+        }
+    }
+}
+
+impl FromStr for OutputMode {
+    type Err = ModCliError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "human" | "text" | "table" => Ok(OutputMode::Human),
+            "json" => Ok(OutputMode::Json),
+            "ndjson" | "jsonl" => Ok(OutputMode::Ndjson),
+            "yaml" | "yml" if cfg!(feature = "output-yaml") => Ok(OutputMode::Yaml),
+            "yaml" | "yml" => Err(ModCliError::InvalidUsage(
+                "yaml output requires the `output-yaml` feature".into(),
+            )),
+            other => Err(ModCliError::InvalidUsage(format!(
+                "unsupported output mode: {other} (expected human, json, ndjson or yaml)"
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for OutputMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            OutputMode::Human => "human",
+            OutputMode::Json => "json",
+            OutputMode::Ndjson => "ndjson",
+            OutputMode::Yaml => "yaml",
+        })
+    }
+}
+
+/// Sets the process-wide output mode.
+pub fn set_output_mode(mode: OutputMode) {
+    if !testing::update_settings(|s| s.output = mode) {
+        MODE.store(mode.as_u8(), Ordering::SeqCst);
+    }
+}
+
+/// Returns the process-wide output mode.
+pub fn output_mode() -> OutputMode {
+    match testing::settings() {
+        Some(settings) => settings.output,
+        None => OutputMode::from_u8(MODE.load(Ordering::SeqCst)),
+    }
+}
+
+/// Writes a hook message as a structured stderr record when a machine mode is
+/// active. Returns false (and writes nothing) in `Human` mode.
+pub fn route_record(level: &str, message: &str) -> bool {
+    if !output_mode().is_machine() {
+        return false;
+    }
+    let line = format!("{}\n", record(level, message));
+    sink::write(Stream::Err, &line);
+    true
+}
+
+/// One-line JSON record for a hook message. ANSI escapes are stripped.
+pub fn record(level: &str, message: &str) -> String {
+    let plain = console::strip_ansi_codes(message);
+    format!(
+        "{{\"level\":{},\"message\":{}}}",
+        json_quote(level),
+        json_quote(&plain)
+    )
+}
+
+fn json_quote(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
@@ -1,3 +1,4 @@
+use crate::output::sink::{self, Stream};
 use crate::output::style::build;
 use crate::output::themes::current_theme;
 use std::{
@@ -8,10 +9,20 @@ use std::{
     time::Duration,
 };
 
+// Stdout of the global sink.
+fn out(text: &str) {
+    sink::write(Stream::Out, text);
+}
+
+// Message shortcuts go to `sink::message_stream()`.
+fn message(text: &str) {
+    sink::write(sink::message_stream(), &format!("{text}\n"));
+}
+
 /// Prints a single line with optional delay (ms)
 #[inline(always)]
 pub fn line(text: &str) {
-    println!("{text}");
+    out(&format!("{text}\n"));
 }
 
 /// Prints a clickable hyperlink using OSC 8 sequences when enabled.
@@ -30,10 +41,9 @@ pub fn link(text: &str, url: &str) {
     if osc8_enabled {
         // OSC 8: ESC ] 8 ; ; url BEL text ESC ] 8 ; ; BEL
         // Use \x1b (ESC) and \x07 (BEL)
-        print!("\x1b]8;;{url}\x07{text}\x1b]8;;\x07");
-        println!();
+        line(&format!("\x1b]8;;{url}\x07{text}\x1b]8;;\x07"));
     } else {
-        println!("{text} ({url})");
+        line(&format!("{text} ({url})"));
     }
 }
 
@@ -89,19 +99,19 @@ fn osc8_supported() -> bool {
 /// Prints text without newline
 #[inline(always)]
 pub fn write(text: &str) {
-    print!("{text}");
+    out(text);
 }
 
 /// Prints just a newline
 #[inline(always)]
 pub fn newline() {
-    println!();
+    line("");
 }
 
 /// Prints just a newline
 #[inline(always)]
 pub fn end() {
-    println!();
+    line("");
 }
 
 /// Scrolls through a multi-line string with optional delay
@@ -162,7 +172,7 @@ pub fn debug(msg: &str) {
         .space()
         .part(msg)
         .get();
-    line(&styled);
+    message(&styled);
 }
 
 pub fn info(msg: &str) {
@@ -174,7 +184,7 @@ pub fn info(msg: &str) {
         .space()
         .part(msg)
         .get();
-    line(&styled);
+    message(&styled);
 }
 
 pub fn warn(msg: &str) {
@@ -186,7 +196,7 @@ pub fn warn(msg: &str) {
         .space()
         .part(msg)
         .get();
-    line(&styled);
+    message(&styled);
 }
 
 pub fn error(msg: &str) {
@@ -198,7 +208,7 @@ pub fn error(msg: &str) {
         .space()
         .part(msg)
         .get();
-    line(&styled);
+    message(&styled);
 }
 
 pub fn success(msg: &str) {
@@ -210,7 +220,7 @@ pub fn success(msg: &str) {
         .space()
         .part(msg)
         .get();
-    line(&styled);
+    message(&styled);
 }
 
 pub fn status(msg: &str) {
@@ -222,7 +232,7 @@ pub fn status(msg: &str) {
         .space()
         .part(msg)
         .get();
-    line(&styled);
+    message(&styled);
 }
 
 pub fn deprecated(msg: &str) {
@@ -234,7 +244,7 @@ pub fn deprecated(msg: &str) {
         .space()
         .part(msg)
         .get();
-    line(&styled);
+    message(&styled);
 }
 
 pub fn unknown(msg: &str) {
@@ -246,5 +256,5 @@ pub fn unknown(msg: &str) {
         .space()
         .part(msg)
         .get();
-    line(&styled);
+    message(&styled);
 }
@@ -1,6 +1,7 @@
-use crate::output::hook;
-use crossterm::style::{Color, Stylize};
-use std::io::{stdout, Write};
+use crate::output::color_policy;
+use crate::output::print;
+use crate::output::sink;
+use crossterm::style::Color;
 use std::thread;
 use std::time::{Duration, Instant};
 
@@ -72,12 +73,12 @@ impl MultiProgress {
             return;
         }
         // Move cursor up n lines (except for first draw)
-        print!("\x1B[{n}A"); // ANSI: CUU n
+        print::write(&format!("\x1B[{n}A")); // ANSI: CUU n
         for b in &self.bars {
             b.render();
-            println!();
+            print::newline();
         }
-        let _ = stdout().flush();
+        sink::flush();
     }
 
     /// Finish all bars and print their done labels on separate lines.
@@ -85,9 +86,9 @@ impl MultiProgress {
         for b in &self.bars {
             // Ensure bar is fully rendered at 100%
             b.render();
-            println!(" {}", b.style.done_label);
+            print::line(&format!(" {}", b.style.done_label));
         }
-        let _ = stdout().flush();
+        sink::flush();
     }
 }
 
@@ -234,7 +235,7 @@ impl ProgressBar {
             self.tick();
             thread::sleep(Duration::from_millis(interval));
         }
-        println!(" {}", self.style.done_label);
+        print::line(&format!(" {}", self.style.done_label));
     }
 
     fn render(&self) {
@@ -273,15 +274,12 @@ impl ProgressBar {
         );
 
         if let Some(color) = self.style.color {
-            bar = bar.with(color).to_string();
-        }
-        print!("\r");
-
-        if let Some(ref label) = self.label {
-            print!("{label} {bar}");
-        } else {
-            print!("{bar}");
+            bar = color_policy::fg(&bar, color);
         }
+        let mut frame = match self.label {
+            Some(ref label) => format!("\r{label} {bar}"),
+            None => format!("\r{bar}"),
+        };
 
         // Bytes-specific tail: rate and ETA
         if let Some(total) = self.total_bytes {
@@ -300,14 +298,12 @@ impl ProgressBar {
 
             let rate_str = human_bytes_per_sec(rate_bps);
             let eta_str = human_duration(Duration::from_secs(eta_secs));
-            print!("{percent}  {rate_str}  ETA {eta_str}");
+            frame.push_str(&format!("{percent}  {rate_str}  ETA {eta_str}"));
         } else {
-            print!("{percent}");
-        }
-
-        if let Err(e) = stdout().flush() {
-            hook::warn(&format!("flush failed: {e}"));
+            frame.push_str(&percent);
         }
+        print::write(&frame);
+        sink::flush();
     }
 }
 
@@ -321,43 +317,35 @@ pub fn show_progress_bar(label: &str, total_steps: usize, duration_ms: u64) {
 
 pub fn show_percent_progress(label: &str, percent: usize) {
     let clamped = percent.clamp(0, 100);
-    print!("\r{label}: {clamped:>3}% complete");
-    if let Err(e) = stdout().flush() {
-        hook::warn(&format!("flush failed: {e}"));
-    }
+    print::write(&format!("\r{label}: {clamped:>3}% complete"));
+    sink::flush();
 }
 
 pub fn show_spinner(label: &str, cycles: usize, delay_ms: u64) {
     let spinner = ['|', '/', '-', '\\'];
-    let mut stdout = stdout();
-    print!("{label} ");
+    print::write(&format!("{label} "));
 
     for i in 0..cycles {
         let frame = spinner[i % spinner.len()];
-        print!("\r{label} {frame}");
-        if let Err(e) = stdout.flush() {
-            hook::warn(&format!("flush failed: {e}"));
-        }
+        print::write(&format!("\r{label} {frame}"));
+        sink::flush();
         thread::sleep(Duration::from_millis(delay_ms));
     }
 
-    println!("{label} ✓");
+    print::line(&format!("{label} ✓"));
 }
 
 /// Emoji spinner demo using moon phases. Compatible with most modern terminals.
 pub fn show_emoji_spinner(label: &str, cycles: usize, delay_ms: u64) {
     const FRAMES: [&str; 8] = ["🌑", "🌒", "🌓", "🌔", "🌕", "🌖", "🌗", "🌘"];
-    let mut stdout = stdout();
-    print!("{label} ");
+    print::write(&format!("{label} "));
 
     for i in 0..cycles {
         let frame = FRAMES[i % FRAMES.len()];
-        print!("\r{label} {frame}");
-        if let Err(e) = stdout.flush() {
-            hook::warn(&format!("flush failed: {e}"));
-        }
+        print::write(&format!("\r{label} {frame}"));
+        sink::flush();
         thread::sleep(Duration::from_millis(delay_ms));
     }
 
-    println!("{label} ✅");
+    print::line(&format!("{label} ✅"));
 }
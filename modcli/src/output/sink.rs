@@ -0,0 +1,297 @@
+//! Output sinks: where everything modcli prints ends up.
+//!
+//! `output::print`, `output::hook` messages, progress bars, help pages,
+//! structured records and `Context::out()` all write through an
+//! `OutputSink`, one `Stream` (stdout or stderr) at a time. The global sink
+//! defaults to the process streams; replace it with `set_global` to redirect
+//! everything (a log file, a TUI pane, a remote session), or give a single
+//! registry its own with `CommandRegistry::set_output(Output::to(sink))`.
+//!
+//! Provided sinks: `StdioSink`, `MemorySink`, `FileSink`, `TeeSink` (copy to
+//! several) and `SplitSink` (stdout and stderr to different sinks).
+//! `set_message_stream(Stream::Err)` sends hook messages to stderr.
+//!
+//! # Example
+//! ```
+//! use modcli::output::print;
+//! use modcli::output::sink::{self, MemorySink, StdioSink, TeeSink};
+//! use std::sync::Arc;
+//!
+//! let log = Arc::new(MemorySink::new());
+//! sink::set_global(Arc::new(TeeSink::new(vec![Arc::new(StdioSink), log.clone()])));
+//! print::line("hello");
+//! sink::reset_global();
+//! assert_eq!(log.contents(), "hello\n");
+//! ```
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+
+/// Output stream a write is meant for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stream {
+    Out,
+    Err,
+}
+
+/// Destination for output text.
+pub trait OutputSink: Send + Sync {
+    /// Write `text` (already formatted, newlines included) to `stream`.
+    fn write(&self, stream: Stream, text: &str);
+    fn flush(&self) {}
+}
+
+/// Process stdout and stderr, flushed after every write.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StdioSink;
+
+impl OutputSink for StdioSink {
+    fn write(&self, stream: Stream, text: &str) {
+        match stream {
+            Stream::Out => {
+                let mut out = io::stdout().lock();
+                let _ = out.write_all(text.as_bytes());
+                let _ = out.flush();
+            }
+            Stream::Err => {
+                let mut err = io::stderr().lock();
+                let _ = err.write_all(text.as_bytes());
+                let _ = err.flush();
+            }
+        }
+    }
+
+    fn flush(&self) {
+        let _ = io::stdout().flush();
+        let _ = io::stderr().flush();
+    }
+}
+
+/// In-memory buffers, one per stream.
+#[derive(Debug, Default)]
+pub struct MemorySink {
+    out: Mutex<String>,
+    err: Mutex<String>,
+}
+
+impl MemorySink {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Everything written to `Stream::Out`.
+    pub fn contents(&self) -> String {
+        self.out.lock().map(|s| s.clone()).unwrap_or_default()
+    }
+
+    /// Everything written to `Stream::Err`.
+    pub fn err_contents(&self) -> String {
+        self.err.lock().map(|s| s.clone()).unwrap_or_default()
+    }
+
+    pub fn clear(&self) {
+        for buf in [&self.out, &self.err] {
+            if let Ok(mut b) = buf.lock() {
+                b.clear();
+            }
+        }
+    }
+}
+
+impl OutputSink for MemorySink {
+    fn write(&self, stream: Stream, text: &str) {
+        let buf = match stream {
+            Stream::Out => &self.out,
+            Stream::Err => &self.err,
+        };
+        if let Ok(mut b) = buf.lock() {
+            b.push_str(text);
+        }
+    }
+}
+
+/// A file receiving both streams, in order.
+#[derive(Debug)]
+pub struct FileSink {
+    file: Mutex<File>,
+}
+
+impl FileSink {
+    /// Create (or truncate) `path`.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self::from_file(File::create(path)?))
+    }
+
+    /// Append to `path`, creating it if needed.
+    pub fn append(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self::from_file(file))
+    }
+
+    pub fn from_file(file: File) -> Self {
+        Self {
+            file: Mutex::new(file),
+        }
+    }
+}
+
+impl OutputSink for FileSink {
+    fn write(&self, _stream: Stream, text: &str) {
+        if let Ok(mut f) = self.file.lock() {
+            let _ = f.write_all(text.as_bytes());
+        }
+    }
+
+    fn flush(&self) {
+        if let Ok(mut f) = self.file.lock() {
+            let _ = f.flush();
+        }
+    }
+}
+
+/// Copies every write to each of its sinks.
+pub struct TeeSink {
+    sinks: Vec<Arc<dyn OutputSink>>,
+}
+
+impl TeeSink {
+    pub fn new(sinks: Vec<Arc<dyn OutputSink>>) -> Self {
+        Self { sinks }
+    }
+}
+
+impl OutputSink for TeeSink {
+    fn write(&self, stream: Stream, text: &str) {
+        for sink in &self.sinks {
+            sink.write(stream, text);
+        }
+    }
+
+    fn flush(&self) {
+        for sink in &self.sinks {
+            sink.flush();
+        }
+    }
+}
+
+/// Sends `Stream::Out` to one sink and `Stream::Err` to another.
+pub struct SplitSink {
+    out: Arc<dyn OutputSink>,
+    err: Arc<dyn OutputSink>,
+}
+
+impl SplitSink {
+    pub fn new(out: Arc<dyn OutputSink>, err: Arc<dyn OutputSink>) -> Self {
+        Self { out, err }
+    }
+}
+
+impl OutputSink for SplitSink {
+    fn write(&self, stream: Stream, text: &str) {
+        match stream {
+            Stream::Out => self.out.write(stream, text),
+            Stream::Err => self.err.write(stream, text),
+        }
+    }
+
+    fn flush(&self) {
+        self.out.flush();
+        self.err.flush();
+    }
+}
+
+static GLOBAL: RwLock<Option<Arc<dyn OutputSink>>> = RwLock::new(None);
+static MESSAGES_TO_STDERR: AtomicBool = AtomicBool::new(false);
+
+/// Install the process-wide sink.
+pub fn set_global(sink: Arc<dyn OutputSink>) {
+    if let Ok(mut slot) = GLOBAL.write() {
+        *slot = Some(sink);
+    }
+}
+
+/// Restore the default `StdioSink`.
+pub fn reset_global() {
+    if let Ok(mut slot) = GLOBAL.write() {
+        *slot = None;
+    }
+}
+
+/// The process-wide sink.
+pub fn global() -> Arc<dyn OutputSink> {
+    GLOBAL
+        .read()
+        .ok()
+        .and_then(|slot| slot.clone())
+        .unwrap_or_else(|| Arc::new(StdioSink))
+}
+
+/// Stream used for `output::hook` messages and the `print` message
+/// shortcuts (`print::info`, `print::error`, ...). Default: `Stream::Out`.
+pub fn set_message_stream(stream: Stream) {
+    MESSAGES_TO_STDERR.store(stream == Stream::Err, Ordering::SeqCst);
+}
+
+pub fn message_stream() -> Stream {
+    if MESSAGES_TO_STDERR.load(Ordering::SeqCst) {
+        Stream::Err
+    } else {
+        Stream::Out
+    }
+}
+
+/// Write to the global sink (or the active `testing::TestCli` capture).
+pub fn write(stream: Stream, text: &str) {
+    let captured = match stream {
+        Stream::Out => crate::testing::capture_out(text),
+        Stream::Err => crate::testing::capture_err(text),
+    };
+    if !captured {
+        global().write(stream, text);
+    }
+}
+
+/// Flush the global sink.
+pub fn flush() {
+    global().flush();
+}
+
+// `io::Write` over `write`, for crossterm commands. Bytes are buffered and
+// handed to the sink on flush (crossterm's `execute!` flushes per command),
+// so a frame reaches the sink in one piece.
+pub(crate) struct Writer {
+    stream: Stream,
+    buf: Vec<u8>,
+}
+
+pub(crate) fn writer(stream: Stream) -> Writer {
+    Writer {
+        stream,
+        buf: Vec::new(),
+    }
+}
+
+impl Write for Writer {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        self.buf.extend_from_slice(bytes);
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if !self.buf.is_empty() {
+            write(self.stream, &String::from_utf8_lossy(&self.buf));
+            self.buf.clear();
+        }
+        flush();
+        Ok(())
+    }
+}
+
+impl Drop for Writer {
+    fn drop(&mut self) {
+        let _ = Write::flush(self);
+    }
+}
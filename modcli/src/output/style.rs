@@ -1,4 +1,5 @@
-use crossterm::style::{Attribute, Color, Stylize};
+use crate::output::color_policy;
+use crossterm::style::{Attribute, Color};
 
 #[derive(Clone)]
 pub struct StyledPart {
@@ -11,32 +12,12 @@ pub struct StyledPart {
 
 impl StyledPart {
     pub fn render(&self, default_fg: Option<Color>, default_bg: Option<Color>) -> String {
-        let mut content = self.text.clone();
-
-        if let Some(color) = self.fg.or(default_fg) {
-            content = content.with(color).to_string();
-        }
-
-        if let Some(bg) = self.bg.or(default_bg) {
-            content = content.on(bg).to_string();
-        }
-
-        for attr in &self.styles {
-            content = apply_attr(content, *attr);
-        }
-
-        content
-    }
-}
-
-fn apply_attr(text: String, attr: Attribute) -> String {
-    match attr {
-        Attribute::Bold => text.bold().to_string(),
-        Attribute::Italic => text.italic().to_string(),
-        Attribute::Underlined => text.underlined().to_string(),
-        Attribute::CrossedOut => text.crossed_out().to_string(),
-        Attribute::SlowBlink => text.slow_blink().to_string(),
-        _ => text,
+        color_policy::paint(
+            &self.text,
+            self.fg.or(default_fg),
+            self.bg.or(default_bg),
+            &self.styles,
+        )
     }
 }
 
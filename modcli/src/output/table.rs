@@ -1,5 +1,6 @@
+use crate::output::color_policy;
 use console::measure_text_width;
-use crossterm::style::{Color, Stylize};
+use crossterm::style::{Attribute, Color};
 use terminal_size::{terminal_size, Width};
 use unicode_segmentation::UnicodeSegmentation;
 
@@ -140,7 +141,7 @@ pub fn render_table_with_opts_styled(
         let t = pick_trunc(0, trunc_modes);
         let mut cell = pad_cell_with(h, col_width, a, t);
         if let Some(color) = header_fg {
-            cell = cell.with(color).bold().to_string();
+            cell = color_policy::paint(&cell, Some(color), None, &[Attribute::Bold]);
         }
         out.push_str(&cell);
         out.push(border.vertical);
@@ -167,7 +168,7 @@ pub fn render_table_with_opts_styled(
             let base = pad_cell_with(cell, col_width, a, t);
             let styled = if zebra && (ri % 2 == 1) {
                 if let Some(bg) = zebra_bg {
-                    base.on(bg).to_string()
+                    color_policy::paint(&base, None, Some(bg), &[])
                 } else {
                     base
                 }
@@ -561,11 +562,7 @@ pub fn render_table_with_columns(
     // Remaining space goes to autos evenly
     let used_except_auto: usize = widths.iter().sum();
     let remaining = term_width.saturating_sub(used_except_auto + gaps_total);
-    let auto_share = if auto_count > 0 {
-        remaining / auto_count
-    } else {
-        0
-    };
+    let auto_share = remaining.checked_div(auto_count).unwrap_or(0);
     for (i, spec) in columns.iter().enumerate().take(col_count) {
         if matches!(spec, ColWidth::Auto) {
             widths[i] = auto_share;
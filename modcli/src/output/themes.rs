@@ -1,11 +1,11 @@
 use crate::output::colors::*;
-use crossterm::style::{Color, ResetColor, SetBackgroundColor, SetForegroundColor};
+use crate::output::{color_policy, print};
+use crossterm::style::Color;
 #[cfg(feature = "theme-config")]
 use serde::Deserialize;
 use std::collections::HashMap;
 #[cfg(feature = "theme-config")]
 use std::fs;
-use std::io::{stdout, Write};
 #[cfg(feature = "theme-config")]
 use std::path::Path;
 use std::sync::OnceLock;
@@ -20,18 +20,15 @@ pub struct Theme {
 
 impl Theme {
     pub fn apply(&self) {
-        let _ = write!(
-            stdout(),
-            "{}{}",
-            SetForegroundColor(self.fg),
-            SetBackgroundColor(self.bg)
-        );
-        let _ = stdout().flush();
+        print::write(&color_policy::sgr(Some(self.fg), Some(self.bg), &[]));
+        crate::output::sink::flush();
     }
 
     pub fn reset() {
-        let _ = write!(stdout(), "{ResetColor}");
-        let _ = stdout().flush();
+        if color_policy::enabled() {
+            print::write("\x1b[0m");
+            crate::output::sink::flush();
+        }
     }
 
     pub fn get_log_color(&self, key: &str) -> Color {
@@ -58,6 +55,9 @@ fn log_defaults(base: Color) -> HashMap<&'static str, Color> {
     map.insert("menu_selected_bg", COLOR_STATUS);
     map.insert("menu_selected_fg", BLACK);
     map.insert("menu_stripe_fg", DARK_GREY);
+    // Help pages: section titles and command/argument names
+    map.insert("help_heading", COLOR_STATUS);
+    map.insert("help_name", COLOR_INFO);
     map
 }
 
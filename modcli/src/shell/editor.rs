@@ -0,0 +1,302 @@
+//! Minimal raw-mode line editor used by the interactive shell.
+//!
+//! Keys:
+//! - Left/Right, Home/End, Ctrl-A/Ctrl-E: move the cursor
+//! - Backspace/Delete, Ctrl-U (kill to start), Ctrl-K (kill to end), Ctrl-W (kill word)
+//! - Up/Down: walk history
+//! - Ctrl-R: reverse incremental search over history (repeat to go further back)
+//! - Ctrl-C: discard the current line; Ctrl-D on an empty line: end of input
+//!
+//! When stdin is not a terminal, lines are read with plain buffered IO.
+
+use crate::output::print;
+use crate::output::sink::{self, Stream};
+use crate::shell::history;
+use console::measure_text_width;
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    queue,
+    style::Print,
+    terminal::{self, ClearType},
+};
+use std::io::{self, stdin, BufRead, IsTerminal, Write};
+
+/// Result of reading one line.
+pub enum ReadLine {
+    /// A line was entered (may be empty).
+    Line(String),
+    /// Ctrl-C was pressed; the line was discarded.
+    Interrupted,
+    /// End of input (Ctrl-D on an empty line, or EOF).
+    Eof,
+}
+
+// Restores cooked mode even if rendering fails part-way.
+struct RawGuard;
+
+impl RawGuard {
+    fn enable() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        Ok(Self)
+    }
+}
+
+impl Drop for RawGuard {
+    fn drop(&mut self) {
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+struct Search {
+    query: String,
+    // Offset from the most recent match (0 = newest)
+    skip: usize,
+}
+
+/// Editable line state: characters plus a cursor position (in chars).
+#[derive(Default)]
+struct Buffer {
+    chars: Vec<char>,
+    cursor: usize,
+}
+
+impl Buffer {
+    fn set(&mut self, s: &str) {
+        self.chars = s.chars().collect();
+        self.cursor = self.chars.len();
+    }
+
+    fn text(&self) -> String {
+        self.chars.iter().collect()
+    }
+
+    fn insert(&mut self, c: char) {
+        self.chars.insert(self.cursor, c);
+        self.cursor += 1;
+    }
+
+    fn backspace(&mut self) {
+        if self.cursor > 0 {
+            self.cursor -= 1;
+            self.chars.remove(self.cursor);
+        }
+    }
+
+    fn delete(&mut self) {
+        if self.cursor < self.chars.len() {
+            self.chars.remove(self.cursor);
+        }
+    }
+
+    fn kill_word(&mut self) {
+        let mut start = self.cursor;
+        while start > 0 && self.chars[start - 1].is_whitespace() {
+            start -= 1;
+        }
+        while start > 0 && !self.chars[start - 1].is_whitespace() {
+            start -= 1;
+        }
+        self.chars.drain(start..self.cursor);
+        self.cursor = start;
+    }
+}
+
+/// Line editor holding in-memory history for the session.
+pub struct LineEditor {
+    entries: Vec<String>,
+}
+
+impl LineEditor {
+    pub fn new(entries: Vec<String>) -> Self {
+        Self { entries }
+    }
+
+    /// History entries known to the editor (oldest first).
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    /// Record an entry, skipping blanks and consecutive duplicates.
+    /// Returns true if the entry was added.
+    pub fn push(&mut self, line: &str) -> bool {
+        if line.trim().is_empty() || self.entries.last().map(|s| s.as_str()) == Some(line) {
+            return false;
+        }
+        self.entries.push(line.to_string());
+        true
+    }
+
+    /// Read one line, using the raw-mode editor when attached to a terminal.
+    pub fn read_line(&mut self, prompt: &str) -> io::Result<ReadLine> {
+        if !stdin().is_terminal() {
+            return read_plain(prompt);
+        }
+        let guard = RawGuard::enable()?;
+        let out = self.edit(prompt);
+        drop(guard);
+        // Raw mode swallows the newline; emit it once cooked mode is back.
+        print::newline();
+        out
+    }
+
+    fn edit(&self, prompt: &str) -> io::Result<ReadLine> {
+        let mut buf = Buffer::default();
+        let mut search: Option<Search> = None;
+        // Index into history while walking with Up/Down (None = editing new line)
+        let mut hist_idx: Option<usize> = None;
+        let mut draft = String::new();
+
+        self.render(prompt, &buf, search.as_ref())?;
+        loop {
+            let Event::Key(KeyEvent {
+                code,
+                modifiers,
+                kind,
+                ..
+            }) = event::read()?
+            else {
+                continue;
+            };
+            if kind == KeyEventKind::Release {
+                continue;
+            }
+            let ctrl = modifiers.contains(KeyModifiers::CONTROL);
+
+            if let Some(ref mut s) = search {
+                match code {
+                    KeyCode::Char('r') if ctrl => s.skip += 1,
+                    KeyCode::Char('g') | KeyCode::Char('c') if ctrl => {
+                        search = None;
+                    }
+                    KeyCode::Char(c) if !ctrl => {
+                        s.query.push(c);
+                        s.skip = 0;
+                    }
+                    KeyCode::Backspace => {
+                        s.query.pop();
+                        s.skip = 0;
+                    }
+                    KeyCode::Enter => {
+                        let hit = self.search_hit(s).unwrap_or_default();
+                        return Ok(ReadLine::Line(hit));
+                    }
+                    _ => {
+                        // Any other key accepts the match into the buffer for editing
+                        if let Some(hit) = self.search_hit(s) {
+                            buf.set(&hit);
+                        }
+                        search = None;
+                    }
+                }
+                self.render(prompt, &buf, search.as_ref())?;
+                continue;
+            }
+
+            match code {
+                KeyCode::Enter => return Ok(ReadLine::Line(buf.text())),
+                KeyCode::Char('c') if ctrl => return Ok(ReadLine::Interrupted),
+                KeyCode::Char('d') if ctrl => {
+                    if buf.chars.is_empty() {
+                        return Ok(ReadLine::Eof);
+                    }
+                    buf.delete();
+                }
+                KeyCode::Char('r') if ctrl => {
+                    search = Some(Search {
+                        query: String::new(),
+                        skip: 0,
+                    });
+                }
+                KeyCode::Char('a') if ctrl => buf.cursor = 0,
+                KeyCode::Char('e') if ctrl => buf.cursor = buf.chars.len(),
+                KeyCode::Char('u') if ctrl => {
+                    buf.chars.drain(..buf.cursor);
+                    buf.cursor = 0;
+                }
+                KeyCode::Char('k') if ctrl => buf.chars.truncate(buf.cursor),
+                KeyCode::Char('w') if ctrl => buf.kill_word(),
+                KeyCode::Char(c) if !ctrl => buf.insert(c),
+                KeyCode::Backspace => buf.backspace(),
+                KeyCode::Delete => buf.delete(),
+                KeyCode::Left => buf.cursor = buf.cursor.saturating_sub(1),
+                KeyCode::Right => buf.cursor = (buf.cursor + 1).min(buf.chars.len()),
+                KeyCode::Home => buf.cursor = 0,
+                KeyCode::End => buf.cursor = buf.chars.len(),
+                KeyCode::Up if !self.entries.is_empty() => {
+                    let next = match hist_idx {
+                        None => {
+                            draft = buf.text();
+                            self.entries.len() - 1
+                        }
+                        Some(i) => i.saturating_sub(1),
+                    };
+                    hist_idx = Some(next);
+                    buf.set(&self.entries[next]);
+                }
+                KeyCode::Down => match hist_idx {
+                    Some(i) if i + 1 < self.entries.len() => {
+                        hist_idx = Some(i + 1);
+                        buf.set(&self.entries[i + 1]);
+                    }
+                    Some(_) => {
+                        hist_idx = None;
+                        buf.set(&draft);
+                    }
+                    None => {}
+                },
+                _ => {}
+            }
+            self.render(prompt, &buf, search.as_ref())?;
+        }
+    }
+
+    fn search_hit(&self, s: &Search) -> Option<String> {
+        let hits = history::search(&self.entries, &s.query, usize::MAX);
+        // `search` returns most recent last
+        hits.len()
+            .checked_sub(1 + s.skip)
+            .and_then(|i| hits.get(i).cloned())
+    }
+
+    fn render(&self, prompt: &str, buf: &Buffer, search: Option<&Search>) -> io::Result<()> {
+        let mut out = sink::writer(Stream::Out);
+        queue!(
+            out,
+            cursor::MoveToColumn(0),
+            terminal::Clear(ClearType::CurrentLine)
+        )?;
+        if let Some(s) = search {
+            let hit = self.search_hit(s);
+            let label = if hit.is_some() || s.query.is_empty() {
+                "reverse-i-search"
+            } else {
+                "failing reverse-i-search"
+            };
+            let line = format!("({label})`{}': {}", s.query, hit.unwrap_or_default());
+            queue!(out, Print(line))?;
+        } else {
+            let before: String = buf.chars[..buf.cursor].iter().collect();
+            let col = measure_text_width(prompt) + measure_text_width(&before);
+            queue!(
+                out,
+                Print(prompt),
+                Print(buf.text()),
+                cursor::MoveToColumn(col as u16)
+            )?;
+        }
+        out.flush()
+    }
+}
+
+fn read_plain(prompt: &str) -> io::Result<ReadLine> {
+    print::write(prompt);
+    sink::flush();
+    let mut line = String::new();
+    if stdin().lock().read_line(&mut line)? == 0 {
+        return Ok(ReadLine::Eof);
+    }
+    Ok(ReadLine::Line(
+        line.trim_end_matches(['\r', '\n']).to_string(),
+    ))
+}
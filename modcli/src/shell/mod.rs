@@ -1 +1,165 @@
+//! Interactive shell (REPL) on top of the command registry.
+//!
+//! Lines are read with a small raw-mode editor (cursor movement, history
+//! navigation, Ctrl-R reverse search), split with `parser::parse_line` and
+//! dispatched through the registry.
+//!
+//! # Example
+//! ```no_run
+//! use modcli::shell::Shell;
+//! use modcli::ModCli;
+//!
+//! let cli = ModCli::new();
+//! let shell = Shell::new()
+//!     .prompt("tool> ")
+//!     .welcome("Welcome to tool. Type `help` for commands.")
+//!     .goodbye("Bye!");
+//! shell.run(&cli.registry).unwrap();
+//! ```
+
+pub mod editor;
 pub mod history;
+
+use crate::error::ModCliError;
+use crate::loader::CommandRegistry;
+use crate::output::{hook, print};
+use crate::parser::parse_line;
+use editor::{LineEditor, ReadLine};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// Prevents `shell` from being re-entered from inside a running shell.
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Interactive shell configuration and runner.
+pub struct Shell {
+    prompt: String,
+    welcome: Option<String>,
+    goodbye: Option<String>,
+    exit_commands: Vec<String>,
+    history: bool,
+    history_path: Option<PathBuf>,
+}
+
+impl Default for Shell {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Shell {
+    pub fn new() -> Self {
+        Self {
+            prompt: "> ".into(),
+            welcome: None,
+            goodbye: None,
+            exit_commands: vec!["exit".into(), "quit".into()],
+            history: true,
+            history_path: None,
+        }
+    }
+
+    /// Prompt printed before each line (may contain ANSI styling).
+    pub fn prompt(mut self, prompt: &str) -> Self {
+        self.prompt = prompt.to_string();
+        self
+    }
+
+    /// Text printed once when the shell starts.
+    pub fn welcome(mut self, text: &str) -> Self {
+        self.welcome = Some(text.to_string());
+        self
+    }
+
+    /// Text printed once when the shell exits.
+    pub fn goodbye(mut self, text: &str) -> Self {
+        self.goodbye = Some(text.to_string());
+        self
+    }
+
+    /// Words that end the session (default: `exit`, `quit`).
+    pub fn exit_commands(mut self, words: &[&str]) -> Self {
+        self.exit_commands = words.iter().map(|s| s.to_string()).collect();
+        self
+    }
+
+    /// Enable or disable persistent history (default: enabled).
+    pub fn history(mut self, enabled: bool) -> Self {
+        self.history = enabled;
+        self
+    }
+
+    /// Use a custom history file instead of `history::default_history_path()`.
+    pub fn history_path<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.history_path = Some(path.into());
+        self
+    }
+
+    /// True if `line` is one of the configured exit commands.
+    pub fn is_exit(&self, line: &str) -> bool {
+        let word = line.trim();
+        self.exit_commands.iter().any(|e| e == word)
+    }
+
+    /// Execute a single input line against the registry.
+    /// Returns `false` when the line requests the session to end.
+    pub fn execute_line(&self, registry: &CommandRegistry, line: &str) -> bool {
+        if self.is_exit(line) {
+            return false;
+        }
+        let (cmd, args) = parse_line(line);
+        if cmd.is_empty() {
+            return true;
+        }
+        // Ctrl-C interrupts the line's command, not the session
+        let cancel = registry.cancellation();
+        cancel.reset();
+        let _watch = crate::signals::watch(cancel);
+        registry.execute(&cmd, &args);
+        true
+    }
+
+    /// Run the read–eval–print loop until an exit command or end of input.
+    pub fn run(&self, registry: &CommandRegistry) -> Result<(), ModCliError> {
+        if ACTIVE.swap(true, Ordering::SeqCst) {
+            hook::warn("Shell is already running.");
+            return Ok(());
+        }
+        let result = self.run_loop(registry);
+        ACTIVE.store(false, Ordering::SeqCst);
+        result
+    }
+
+    fn run_loop(&self, registry: &CommandRegistry) -> Result<(), ModCliError> {
+        let path = self.history_path.as_deref();
+        let entries = if self.history {
+            history::load(path)
+        } else {
+            Vec::new()
+        };
+        let mut editor = LineEditor::new(entries);
+
+        if let Some(ref w) = self.welcome {
+            print::line(w);
+        }
+        loop {
+            let line = match editor.read_line(&self.prompt)? {
+                ReadLine::Line(l) => l,
+                ReadLine::Interrupted => continue,
+                ReadLine::Eof => break,
+            };
+            if editor.push(&line) && self.history {
+                if let Err(e) = history::add(path, &line) {
+                    hook::warn(&format!("failed to write history: {e}"));
+                }
+            }
+            if !self.execute_line(registry, &line) {
+                break;
+            }
+        }
+        if let Some(ref g) = self.goodbye {
+            print::line(g);
+        }
+        Ok(())
+    }
+}
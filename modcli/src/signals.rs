@@ -0,0 +1,215 @@
+//! Interrupt handling for running commands.
+//!
+//! `ModCli::run` / `run_with_status` install a process-wide handler for
+//! SIGINT and SIGTERM (unix) and watch the registry's `CancellationToken`
+//! while a command runs. On the first signal the token is cancelled and the
+//! terminal is restored (raw mode off, cursor shown, colors reset), so
+//! commands checking `ctx.is_cancelled()` can stop cleanly; the run then
+//! returns `ExitCode::INTERRUPTED` (130). A second signal exits the process
+//! with `128 + signal`; so does a command still running after the grace
+//! period, if one is set (`set_grace_period`, off by default). A signal that
+//! arrives while no command is watched gets the default action.
+//!
+//! Raw-mode inputs (`input::builders`) treat a Ctrl-C key press the same way
+//! via `interrupt()`, since raw mode suppresses the signal. Outside a watched
+//! run the key press only cancels the input.
+//!
+//! # Example
+//! ```no_run
+//! use modcli::command::Command;
+//! use modcli::context::Context;
+//! use modcli::error::ModCliError;
+//! use modcli::exit::ExitCode;
+//!
+//! struct Sync;
+//!
+//! impl Command for Sync {
+//!     fn name(&self) -> &str { "sync" }
+//!     fn execute(&self, _args: &[String]) {}
+//!     fn run(&self, ctx: &Context) -> Result<ExitCode, ModCliError> {
+//!         for _batch in 0..100 {
+//!             if ctx.is_cancelled() {
+//!                 break; // cleanup, then return; the run exits with 130
+//!             }
+//!             // ... one unit of work ...
+//!         }
+//!         Ok(ExitCode::SUCCESS)
+//!     }
+//! }
+//!
+//! modcli::signals::set_grace_period(Some(std::time::Duration::from_secs(5)));
+//! ```
+
+use crate::cancel::CancellationToken;
+use crate::exit::ExitCode;
+use crossterm::{cursor, execute, style::ResetColor, terminal};
+use std::io::{self, IsTerminal};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicU8, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+const SIGINT: u8 = 2;
+#[cfg(unix)]
+const SIGTERM: u8 = 15;
+
+struct Active {
+    token: CancellationToken,
+    generation: u64,
+}
+
+static ACTIVE: Mutex<Option<Active>> = Mutex::new(None);
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+static ENABLED: AtomicBool = AtomicBool::new(true);
+// u64::MAX: no grace period, wait for the command
+static GRACE_MS: AtomicU64 = AtomicU64::new(u64::MAX);
+static LAST_SIGNAL: AtomicU8 = AtomicU8::new(0);
+static INSTALLED: OnceLock<bool> = OnceLock::new();
+
+/// Enable or disable the framework handler (enabled by default). Takes
+/// effect for handlers not yet installed; call before the first run.
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::SeqCst);
+}
+
+/// How long a command may keep running after an interrupt before the
+/// process exits. `None` (the default) waits for the command to return;
+/// `Some(Duration::ZERO)` exits right away.
+pub fn set_grace_period(grace: Option<Duration>) {
+    let ms = grace.map_or(u64::MAX, |g| (g.as_millis() as u64).min(u64::MAX - 1));
+    GRACE_MS.store(ms, Ordering::SeqCst);
+}
+
+/// Install the SIGINT/SIGTERM handler once per process. Returns true when
+/// a handler is active (always false on non-unix targets or when disabled).
+pub fn install() -> bool {
+    if !ENABLED.load(Ordering::SeqCst) {
+        return INSTALLED.get().copied().unwrap_or(false);
+    }
+    *INSTALLED.get_or_init(install_handler)
+}
+
+#[cfg(unix)]
+fn install_handler() -> bool {
+    use signal_hook::consts::{SIGINT as INT, SIGTERM as TERM};
+    use signal_hook::iterator::Signals;
+
+    let Ok(mut signals) = Signals::new([INT, TERM]) else {
+        return false;
+    };
+    std::thread::Builder::new()
+        .name("modcli-signals".into())
+        .spawn(move || {
+            for sig in signals.forever() {
+                if !handle(if sig == TERM { SIGTERM } else { SIGINT }) {
+                    // Nothing to cancel: behave as if no handler were installed
+                    restore_terminal();
+                    let _ = signal_hook::low_level::emulate_default_handler(sig);
+                }
+            }
+        })
+        .is_ok()
+}
+
+#[cfg(not(unix))]
+fn install_handler() -> bool {
+    false
+}
+
+/// Watch `token` while a command runs: signals cancel it. The previous watch
+/// (if any) is restored when the guard drops.
+pub fn watch(token: &CancellationToken) -> WatchGuard {
+    let last_signal = LAST_SIGNAL.swap(0, Ordering::SeqCst);
+    let generation = GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    let previous = ACTIVE.lock().ok().and_then(|mut slot| {
+        slot.replace(Active {
+            token: token.clone(),
+            generation,
+        })
+    });
+    WatchGuard {
+        previous,
+        last_signal,
+    }
+}
+
+/// Active watch; restores the previous one on drop.
+pub struct WatchGuard {
+    previous: Option<Active>,
+    last_signal: u8,
+}
+
+impl WatchGuard {
+    /// Exit code for an interrupt received during this watch: 130 for
+    /// SIGINT (or `interrupt()`), 143 for SIGTERM.
+    pub fn interrupted(&self) -> Option<ExitCode> {
+        match LAST_SIGNAL.load(Ordering::SeqCst) {
+            0 => None,
+            sig => Some(ExitCode(128 + sig)),
+        }
+    }
+}
+
+impl Drop for WatchGuard {
+    fn drop(&mut self) {
+        if let Ok(mut slot) = ACTIVE.lock() {
+            *slot = self.previous.take();
+        }
+        LAST_SIGNAL.store(self.last_signal, Ordering::SeqCst);
+    }
+}
+
+/// Handle a Ctrl-C that arrived as input (e.g. a key press in raw mode)
+/// like SIGINT. Returns false when no command is being watched; the process
+/// is left alone and the caller should treat the key press as a cancel.
+pub fn interrupt() -> bool {
+    handle(SIGINT)
+}
+
+/// Leave raw mode, show the cursor and reset colors set by `Theme::apply`.
+/// Escape sequences are only written when stdout is a terminal.
+pub fn restore_terminal() {
+    let _ = terminal::disable_raw_mode();
+    let mut out = io::stdout();
+    if out.is_terminal() {
+        let _ = execute!(out, cursor::Show, ResetColor);
+    }
+}
+
+fn handle(sig: u8) -> bool {
+    let active = ACTIVE
+        .lock()
+        .ok()
+        .and_then(|slot| slot.as_ref().map(|a| (a.token.clone(), a.generation)));
+    let Some((token, generation)) = active else {
+        return false;
+    };
+    LAST_SIGNAL.store(sig, Ordering::SeqCst);
+    if token.is_cancelled() {
+        // Second interrupt: stop waiting for the command
+        exit(sig);
+    }
+    token.cancel();
+    restore_terminal();
+
+    let grace = match GRACE_MS.load(Ordering::SeqCst) {
+        u64::MAX => return true,
+        0 => exit(sig),
+        ms => Duration::from_millis(ms),
+    };
+    std::thread::spawn(move || {
+        std::thread::sleep(grace);
+        let still_running = ACTIVE
+            .lock()
+            .map(|slot| slot.as_ref().map(|a| a.generation) == Some(generation))
+            .unwrap_or(false);
+        if still_running {
+            exit(sig);
+        }
+    });
+    true
+}
+
+fn exit(sig: u8) -> ! {
+    restore_terminal();
+    std::process::exit(128 + sig as i32)
+}
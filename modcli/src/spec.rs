@@ -0,0 +1,532 @@
+//! Declarative argument schemas for commands.
+//!
+//! A command may return a [`CommandSpec`] from `Command::spec()` describing its
+//! positionals, `--long`/`-s` options and flags. The registry parses raw arguments
+//! against the spec before dispatch and hands the command a typed [`ParsedArgs`].
+//!
+//! Supported forms:
+//! - `--key value`, `--key=value`, `-k value`, `-k=value`
+//! - `--flag`, `--flag=true|false`, `-f`, clustered short flags (`-vvq`)
+//! - `--` ends option parsing; everything after is positional
+//!
+//! # Example
+//! ```
+//! use modcli::spec::{ArgSpec, CommandSpec, ValueType};
+//!
+//! let spec = CommandSpec::new()
+//!     .arg(ArgSpec::positional("target").required().help("Deployment target"))
+//!     .arg(ArgSpec::option("port").short('p').value_type(ValueType::Int).default("8080"))
+//!     .arg(ArgSpec::flag("verbose").short('v').repeatable());
+//!
+//! let argv: Vec<String> = ["prod", "-vv", "--port=9000"].iter().map(|s| s.to_string()).collect();
+//! let parsed = spec.parse(&argv).unwrap();
+//! assert_eq!(parsed.get_str("target"), Some("prod"));
+//! assert_eq!(parsed.get::<u16>("port"), Some(9000));
+//! assert_eq!(parsed.count("verbose"), 2);
+//! ```
+
+use crate::error::ModCliError;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// Kind of argument: positional, option taking a value, or boolean flag.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArgKind {
+    Positional,
+    Option,
+    Flag,
+}
+
+/// Value type used to validate option and positional values at parse time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValueType {
+    String,
+    Int,
+    Float,
+    Bool,
+    Path,
+}
+
+impl ValueType {
+    fn check(&self, display: &str, raw: &str) -> Result<(), ModCliError> {
+        let ok = match self {
+            ValueType::String | ValueType::Path => true,
+            ValueType::Int => raw.parse::<i64>().is_ok(),
+            ValueType::Float => raw.parse::<f64>().is_ok(),
+            ValueType::Bool => parse_bool(raw).is_some(),
+        };
+        if ok {
+            Ok(())
+        } else {
+            let expected = match self {
+                ValueType::Int => "integer",
+                ValueType::Float => "numeric",
+                _ => "boolean",
+            };
+            Err(ModCliError::InvalidUsage(format!(
+                "expected {expected} value for {display}, got '{raw}'"
+            )))
+        }
+    }
+}
+
+/// Schema for a single argument.
+#[derive(Clone, Debug)]
+pub struct ArgSpec {
+    pub name: String,
+    pub kind: ArgKind,
+    pub long: Option<String>,
+    pub short: Option<char>,
+    pub help: Option<String>,
+    pub value_type: ValueType,
+    pub value_name: Option<String>,
+    pub default: Option<String>,
+    pub choices: Vec<String>,
+    pub required: bool,
+    pub repeatable: bool,
+}
+
+impl ArgSpec {
+    fn base(name: &str, kind: ArgKind) -> Self {
+        Self {
+            name: name.to_string(),
+            kind,
+            long: None,
+            short: None,
+            help: None,
+            value_type: ValueType::String,
+            value_name: None,
+            default: None,
+            choices: Vec::new(),
+            required: false,
+            repeatable: false,
+        }
+    }
+
+    /// A positional argument, filled in declaration order.
+    pub fn positional(name: &str) -> Self {
+        Self::base(name, ArgKind::Positional)
+    }
+
+    /// An option taking a value, matched as `--<name>` unless `long()` overrides it.
+    pub fn option(name: &str) -> Self {
+        let mut a = Self::base(name, ArgKind::Option);
+        a.long = Some(name.to_string());
+        a
+    }
+
+    /// A boolean flag, matched as `--<name>` unless `long()` overrides it.
+    pub fn flag(name: &str) -> Self {
+        let mut a = Self::base(name, ArgKind::Flag);
+        a.long = Some(name.to_string());
+        a.value_type = ValueType::Bool;
+        a
+    }
+
+    pub fn long(mut self, long: &str) -> Self {
+        self.long = Some(long.trim_start_matches('-').to_string());
+        self
+    }
+
+    pub fn short(mut self, short: char) -> Self {
+        self.short = Some(short);
+        self
+    }
+
+    pub fn help(mut self, help: &str) -> Self {
+        self.help = Some(help.to_string());
+        self
+    }
+
+    pub fn value_type(mut self, t: ValueType) -> Self {
+        self.value_type = t;
+        self
+    }
+
+    /// Placeholder shown in usage lines (defaults to the upper-cased name).
+    pub fn value_name(mut self, v: &str) -> Self {
+        self.value_name = Some(v.to_string());
+        self
+    }
+
+    pub fn default(mut self, v: &str) -> Self {
+        self.default = Some(v.to_string());
+        self
+    }
+
+    /// Restrict values to a fixed set.
+    pub fn choices(mut self, values: &[&str]) -> Self {
+        self.choices = values.iter().map(|s| s.to_string()).collect();
+        self
+    }
+
+    pub fn required(mut self) -> Self {
+        self.required = true;
+        self
+    }
+
+    /// Allow the argument more than once. For flags this counts occurrences;
+    /// for a trailing positional it collects all remaining values.
+    pub fn repeatable(mut self) -> Self {
+        self.repeatable = true;
+        self
+    }
+
+    /// Name as shown in errors and usage (`--port`, `-p`, or `<file>`).
+    pub fn display_name(&self) -> String {
+        match (self.kind, &self.long, self.short) {
+            (ArgKind::Positional, _, _) => format!("<{}>", self.name),
+            (_, Some(l), _) => format!("--{l}"),
+            (_, None, Some(s)) => format!("-{s}"),
+            (_, None, None) => self.name.clone(),
+        }
+    }
+
+    /// Value placeholder for usage lines.
+    pub fn placeholder(&self) -> String {
+        self.value_name
+            .clone()
+            .unwrap_or_else(|| self.name.to_ascii_uppercase())
+    }
+
+    fn check_value(&self, raw: &str) -> Result<(), ModCliError> {
+        let display = self.display_name();
+        self.value_type.check(&display, raw)?;
+        if !self.choices.is_empty() && !self.choices.iter().any(|c| c == raw) {
+            return Err(ModCliError::InvalidUsage(format!(
+                "invalid value '{raw}' for {display} (expected one of: {})",
+                self.choices.join(", ")
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Argument schema for a command.
+#[derive(Clone, Debug, Default)]
+pub struct CommandSpec {
+    args: Vec<ArgSpec>,
+}
+
+impl CommandSpec {
+    pub fn new() -> Self {
+        Self { args: Vec::new() }
+    }
+
+    /// Add an argument to the schema.
+    pub fn arg(mut self, arg: ArgSpec) -> Self {
+        self.args.push(arg);
+        self
+    }
+
+    /// All declared arguments in declaration order.
+    pub fn args(&self) -> &[ArgSpec] {
+        &self.args
+    }
+
+    /// Declared positionals in order.
+    pub fn positionals(&self) -> impl Iterator<Item = &ArgSpec> {
+        self.args.iter().filter(|a| a.kind == ArgKind::Positional)
+    }
+
+    /// Declared options and flags.
+    pub fn options(&self) -> impl Iterator<Item = &ArgSpec> {
+        self.args.iter().filter(|a| a.kind != ArgKind::Positional)
+    }
+
+    pub(crate) fn find_long(&self, long: &str) -> Option<&ArgSpec> {
+        self.options().find(|a| a.long.as_deref() == Some(long))
+    }
+
+    pub(crate) fn find_short(&self, short: char) -> Option<&ArgSpec> {
+        self.options().find(|a| a.short == Some(short))
+    }
+
+    /// One-line usage summary, e.g. `<target> [--port <PORT>] [-v]`.
+    pub fn usage(&self) -> String {
+        let mut parts: Vec<String> = Vec::new();
+        for a in self.positionals() {
+            let mut p = format!("<{}>", a.name);
+            if a.repeatable {
+                p.push_str("...");
+            }
+            if !a.required {
+                p = format!("[{p}]");
+            }
+            parts.push(p);
+        }
+        for a in self.options() {
+            let name = match (&a.long, a.short) {
+                (Some(l), _) => format!("--{l}"),
+                (None, Some(s)) => format!("-{s}"),
+                (None, None) => a.name.clone(),
+            };
+            let mut p = if a.kind == ArgKind::Option {
+                format!("{name} <{}>", a.placeholder())
+            } else {
+                name
+            };
+            if a.repeatable {
+                p.push_str("...");
+            }
+            if !a.required {
+                p = format!("[{p}]");
+            }
+            parts.push(p);
+        }
+        parts.join(" ")
+    }
+
+    /// Parse raw arguments against the schema.
+    ///
+    /// Errors are reported as `ModCliError::InvalidUsage` for unknown options,
+    /// missing values, type/choice mismatches, duplicates, missing required
+    /// arguments and unexpected extra positionals.
+    pub fn parse(&self, args: &[String]) -> Result<ParsedArgs, ModCliError> {
+        let mut out = ParsedArgs::unparsed(args);
+        let mut positionals: Vec<&str> = Vec::new();
+        let mut only_positionals = false;
+        let mut i = 0;
+
+        while i < args.len() {
+            let tok = args[i].as_str();
+            i += 1;
+
+            if only_positionals || tok == "-" || !tok.starts_with('-') {
+                positionals.push(tok);
+                continue;
+            }
+            if tok == "--" {
+                only_positionals = true;
+                continue;
+            }
+
+            if let Some(body) = tok.strip_prefix("--") {
+                let (key, inline) = match body.split_once('=') {
+                    Some((k, v)) => (k, Some(v)),
+                    None => (body, None),
+                };
+                let spec = self
+                    .find_long(key)
+                    .ok_or_else(|| ModCliError::InvalidUsage(format!("unknown option: --{key}")))?;
+                i = self.take(spec, inline, args, i, &mut out)?;
+                continue;
+            }
+
+            // Short form: -p value, -p=value, -pvalue or clustered flags -abc
+            let body = &tok[1..];
+            let first = body.chars().next().unwrap_or('-');
+            let Some(spec) = self.find_short(first) else {
+                // Negative numbers are positionals when no short option claims the digit
+                if tok.parse::<f64>().is_ok() {
+                    positionals.push(tok);
+                    continue;
+                }
+                return Err(ModCliError::InvalidUsage(format!(
+                    "unknown option: -{first}"
+                )));
+            };
+            let rest = &body[first.len_utf8()..];
+            if spec.kind == ArgKind::Option {
+                let inline = if rest.is_empty() {
+                    None
+                } else {
+                    Some(rest.strip_prefix('=').unwrap_or(rest))
+                };
+                i = self.take(spec, inline, args, i, &mut out)?;
+                continue;
+            }
+            if let Some(v) = rest.strip_prefix('=') {
+                i = self.take(spec, Some(v), args, i, &mut out)?;
+                continue;
+            }
+            i = self.take(spec, None, args, i, &mut out)?;
+            for c in rest.chars() {
+                match self.find_short(c) {
+                    Some(s) if s.kind == ArgKind::Flag => {
+                        i = self.take(s, None, args, i, &mut out)?;
+                    }
+                    Some(s) => {
+                        return Err(ModCliError::InvalidUsage(format!(
+                            "option {} requires a value and cannot be clustered",
+                            s.display_name()
+                        )))
+                    }
+                    None => return Err(ModCliError::InvalidUsage(format!("unknown option: -{c}"))),
+                }
+            }
+        }
+
+        // Assign positionals in order; a repeatable positional swallows the rest
+        let mut rest = positionals.into_iter();
+        for spec in self.positionals() {
+            if spec.repeatable {
+                for v in rest.by_ref() {
+                    spec.check_value(v)?;
+                    out.push(&spec.name, v);
+                }
+                break;
+            }
+            match rest.next() {
+                Some(v) => {
+                    spec.check_value(v)?;
+                    out.push(&spec.name, v);
+                }
+                None => break,
+            }
+        }
+        if let Some(extra) = rest.next() {
+            return Err(ModCliError::InvalidUsage(format!(
+                "unexpected argument: {extra}"
+            )));
+        }
+
+        // Required + defaults
+        for spec in &self.args {
+            if out.contains(&spec.name) {
+                continue;
+            }
+            if let Some(ref d) = spec.default {
+                out.push(&spec.name, d);
+            } else if spec.required {
+                return Err(ModCliError::InvalidUsage(format!(
+                    "missing required argument: {}",
+                    spec.display_name()
+                )));
+            }
+        }
+
+        Ok(out)
+    }
+
+    // Record one occurrence of an option/flag, consuming the next token as its
+    // value when needed. Returns the updated cursor.
+    fn take(
+        &self,
+        spec: &ArgSpec,
+        inline: Option<&str>,
+        args: &[String],
+        mut i: usize,
+        out: &mut ParsedArgs,
+    ) -> Result<usize, ModCliError> {
+        if !spec.repeatable && out.contains(&spec.name) {
+            return Err(ModCliError::InvalidUsage(format!(
+                "{} provided more than once",
+                spec.display_name()
+            )));
+        }
+        match spec.kind {
+            ArgKind::Flag => {
+                let on = match inline {
+                    Some(v) => parse_bool(v).ok_or_else(|| {
+                        ModCliError::InvalidUsage(format!(
+                            "expected boolean value for {}, got '{v}'",
+                            spec.display_name()
+                        ))
+                    })?,
+                    None => true,
+                };
+                out.push(&spec.name, if on { "true" } else { "false" });
+            }
+            _ => {
+                let value = match inline {
+                    Some(v) => v,
+                    None => {
+                        let v = args.get(i).ok_or_else(|| {
+                            ModCliError::InvalidUsage(format!(
+                                "missing value for {}",
+                                spec.display_name()
+                            ))
+                        })?;
+                        i += 1;
+                        v.as_str()
+                    }
+                };
+                spec.check_value(value)?;
+                out.push(&spec.name, value);
+            }
+        }
+        Ok(i)
+    }
+}
+
+/// Parsed, schema-validated arguments keyed by argument name.
+#[derive(Clone, Debug, Default)]
+pub struct ParsedArgs {
+    values: HashMap<String, Vec<String>>,
+    raw: Vec<String>,
+}
+
+impl ParsedArgs {
+    /// Wrap raw arguments without any schema (used for commands without a spec).
+    pub fn unparsed(raw: &[String]) -> Self {
+        Self {
+            values: HashMap::new(),
+            raw: raw.to_vec(),
+        }
+    }
+
+    fn push(&mut self, name: &str, value: &str) {
+        self.values
+            .entry(name.to_string())
+            .or_default()
+            .push(value.to_string());
+    }
+
+    /// The original, unparsed arguments.
+    pub fn raw(&self) -> &[String] {
+        &self.raw
+    }
+
+    /// True if the argument was given or has a default.
+    pub fn contains(&self, name: &str) -> bool {
+        self.values.contains_key(name)
+    }
+
+    /// Last value for `name` as a string slice.
+    pub fn get_str(&self, name: &str) -> Option<&str> {
+        self.values
+            .get(name)
+            .and_then(|v| v.last())
+            .map(|s| s.as_str())
+    }
+
+    /// Last value for `name` parsed into `T`.
+    pub fn get<T: FromStr>(&self, name: &str) -> Option<T> {
+        self.get_str(name).and_then(|s| s.parse().ok())
+    }
+
+    /// All values for a repeatable argument, in order given.
+    pub fn get_all(&self, name: &str) -> &[String] {
+        self.values.get(name).map(|v| v.as_slice()).unwrap_or(&[])
+    }
+
+    /// All values for `name` parsed into `T` (unparseable values are skipped).
+    pub fn get_many<T: FromStr>(&self, name: &str) -> Vec<T> {
+        self.get_all(name)
+            .iter()
+            .filter_map(|s| s.parse().ok())
+            .collect()
+    }
+
+    /// True if a flag is set (last occurrence wins for `--flag=false`).
+    pub fn flag(&self, name: &str) -> bool {
+        self.get_str(name).and_then(parse_bool).unwrap_or(false)
+    }
+
+    /// Number of times a repeatable flag was set to true (e.g. `-vvv` → 3).
+    pub fn count(&self, name: &str) -> usize {
+        self.get_all(name)
+            .iter()
+            .filter(|v| parse_bool(v) == Some(true))
+            .count()
+    }
+}
+
+fn parse_bool(s: &str) -> Option<bool> {
+    match s.to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "y" => Some(true),
+        "0" | "false" | "no" | "n" => Some(false),
+        _ => None,
+    }
+}
@@ -0,0 +1,135 @@
+//! "Did you mean" suggestions for mistyped command names.
+//!
+//! Candidates are ranked by edit distance (case-insensitive), with prefix
+//! matches accepted regardless of distance. `CommandRegistry::suggest`
+//! supplies the visible commands, groups and aliases at the level where the
+//! typed words stopped resolving.
+
+use crate::loader::CommandRegistry;
+use crate::tree::{alias_path, display_path};
+use std::collections::BTreeSet;
+
+/// Maximum number of suggestions returned.
+pub const MAX_SUGGESTIONS: usize = 3;
+
+/// Edit distance between two strings (by chars), counting insertions,
+/// deletions, substitutions and adjacent transpositions as one edit each.
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    // d[i][j] = distance between a[..i] and b[..j]
+    let mut d = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut best = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                best = best.min(d[i - 2][j - 2] + 1);
+            }
+            d[i][j] = best;
+        }
+    }
+    d[a.len()][b.len()]
+}
+
+/// Rank `candidates` against `input`, closest first (at most `MAX_SUGGESTIONS`).
+pub fn suggestions<'a, I>(input: &str, candidates: I) -> Vec<String>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let needle = input.to_lowercase();
+    if needle.is_empty() {
+        return Vec::new();
+    }
+    // Allow roughly one typo per three characters
+    let max = (needle.chars().count() / 3).max(1);
+    let mut ranked: Vec<(usize, String)> = candidates
+        .into_iter()
+        .filter(|c| *c != input)
+        .filter_map(|c| {
+            let lower = c.to_lowercase();
+            let dist = edit_distance(&needle, &lower);
+            if dist <= max {
+                Some((dist, c.to_string()))
+            } else if needle.chars().count() >= 2 && lower.starts_with(&needle) {
+                Some((max + 1, c.to_string()))
+            } else {
+                None
+            }
+        })
+        .collect();
+    ranked.sort();
+    ranked.dedup_by(|a, b| a.1 == b.1);
+    ranked
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, c)| c)
+        .collect()
+}
+
+/// Default hint text for an unknown command (used by `CommandRegistry::report_error`).
+pub fn unknown_hint(name: &str, suggestions: &[String]) -> String {
+    let mut list: Vec<String> = suggestions.iter().map(|s| format!("`{s}`")).collect();
+    match list.pop() {
+        None => format!("[{name}]. Type `help` or `--help` for a list of available commands."),
+        Some(last) if list.is_empty() => format!("[{name}]. Did you mean {last}?"),
+        Some(last) => format!("[{name}]. Did you mean {} or {last}?", list.join(", ")),
+    }
+}
+
+impl CommandRegistry {
+    /// Suggest completions for typed `words` that failed to resolve: the words
+    /// are resolved as far as possible, then the next word is matched against
+    /// the visible children and aliases at that level. Suggestions are full
+    /// space-separated paths (e.g. `cluster node drain`). Hidden and
+    /// capability-gated commands are never suggested.
+    pub fn suggest(&self, words: &[String]) -> Vec<String> {
+        let (base, n) = self.resolve(words).unwrap_or_default();
+        let Some(word) = words.get(n) else {
+            return Vec::new();
+        };
+
+        let mut segs: BTreeSet<String> = self
+            .children(&base)
+            .iter()
+            .map(|c| c.rsplit(':').next().unwrap_or(c).to_string())
+            .collect();
+        let mut add_alias = |name: &str, alias: &str| {
+            let path = alias_path(name, alias);
+            let (parent, seg) = path.rsplit_once(':').unwrap_or(("", &path));
+            if parent == base {
+                segs.insert(seg.to_string());
+            }
+        };
+        for cmd in self.all().filter(|c| self.is_visible(c.as_ref())) {
+            for alias in cmd.aliases() {
+                add_alias(cmd.name(), alias);
+            }
+        }
+        for group in self.groups().filter(|g| self.is_group_visible(&g.name)) {
+            for alias in &group.aliases {
+                add_alias(&group.name, alias);
+            }
+        }
+
+        let prefix = display_path(&base);
+        suggestions(word, segs.iter().map(String::as_str))
+            .into_iter()
+            .map(|s| {
+                if prefix.is_empty() {
+                    s
+                } else {
+                    format!("{prefix} {s}")
+                }
+            })
+            .collect()
+    }
+}
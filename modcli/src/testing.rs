@@ -0,0 +1,256 @@
+//! Running commands in tests.
+//!
+//! `TestCli` runs an argv through a `ModCli` (global options, `--version`,
+//! routing, help) and returns a `TestOutput` with the exit code, everything
+//! written to stdout and stderr, and each `output::hook` message by category.
+//! Answers queued with `answer` are fed to the stdin-based prompts in `input`
+//! (`text`, `number`, `confirm`, `select`, `buttons`, `prompt_text`, ...);
+//! once they run out, prompts see end of input instead of blocking. Raw-mode
+//! pickers (`raw_select` and friends) read the terminal and can't be scripted.
+//!
+//! Capture is per thread: output from threads a command spawns is not seen.
+//! ANSI escapes are stripped unless `keep_ansi` is set. The color choice,
+//! verbosity and output mode a run picks (`--color`, `-q`, `--output`) are
+//! kept in the session too, so tests running in parallel don't see them.
+//!
+//! # Example
+//! ```
+//! use modcli::exit::ExitCode;
+//! use modcli::input;
+//! use modcli::testing::TestCli;
+//!
+//! let mut t = TestCli::default();
+//! t.registry().command("init").run(|ctx| {
+//!     let name = input::text("Project name?").get().unwrap_or_default();
+//!     ctx.out().line(&format!("created {name}"));
+//!     Ok(ExitCode::SUCCESS)
+//! });
+//!
+//! let out = t.answer("demo").run(&["init"]);
+//! assert_eq!(out.code, ExitCode::SUCCESS);
+//! assert_eq!(out.stdout, "Project name? demo\ncreated demo\n");
+//! ```
+
+use crate::exit::ExitCode;
+use crate::globals::Settings;
+use crate::loader::CommandRegistry;
+use crate::ModCli;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::io;
+
+#[derive(Default)]
+struct Session {
+    out: String,
+    err: String,
+    messages: Vec<(String, String)>,
+    answers: VecDeque<String>,
+    settings: Option<Settings>,
+}
+
+thread_local! {
+    static SESSION: RefCell<Option<Session>> = const { RefCell::new(None) };
+}
+
+fn with_session<R>(f: impl FnOnce(&mut Session) -> R) -> Option<R> {
+    SESSION.with(|s| s.borrow_mut().as_mut().map(f))
+}
+
+// Appends to the captured stdout; false when no session is active.
+pub(crate) fn capture_out(s: &str) -> bool {
+    with_session(|session| session.out.push_str(s)).is_some()
+}
+
+// Appends to the captured stderr; false when no session is active.
+pub(crate) fn capture_err(s: &str) -> bool {
+    with_session(|session| session.err.push_str(s)).is_some()
+}
+
+pub(crate) fn record_message(category: &str, message: &str) {
+    with_session(|session| {
+        session
+            .messages
+            .push((category.to_string(), message.to_string()))
+    });
+}
+
+// Color choice, verbosity and output mode of the active session. They
+// shadow the process-wide settings, so parallel tests don't see each other's
+// `--color`, `-q` or `--output`.
+pub(crate) fn settings() -> Option<Settings> {
+    with_session(|session| session.settings).flatten()
+}
+
+// Updates the session's settings; false when no session is active.
+pub(crate) fn update_settings(f: impl FnOnce(&mut Settings)) -> bool {
+    with_session(|session| session.settings.as_mut().map(f))
+        .flatten()
+        .is_some()
+}
+
+// Next scripted answer as a line of input, or `None` outside a session.
+// `echo` shows the answer in the captured output like a terminal would.
+pub(crate) fn scripted_line(buf: &mut String, echo: bool) -> Option<io::Result<usize>> {
+    with_session(|session| match session.answers.pop_front() {
+        Some(answer) => {
+            if echo {
+                session.out.push_str(&answer);
+            }
+            session.out.push('\n');
+            buf.push_str(&answer);
+            buf.push('\n');
+            Ok(answer.len() + 1)
+        }
+        None => Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "no scripted answer left",
+        )),
+    })
+}
+
+/// Drives a `ModCli` in-process and captures what it prints.
+pub struct TestCli {
+    cli: ModCli,
+    answers: VecDeque<String>,
+    keep_ansi: bool,
+}
+
+impl Default for TestCli {
+    fn default() -> Self {
+        Self::new(ModCli::new())
+    }
+}
+
+impl TestCli {
+    pub fn new(cli: ModCli) -> Self {
+        Self {
+            cli,
+            answers: VecDeque::new(),
+            keep_ansi: false,
+        }
+    }
+
+    /// The wrapped CLI, e.g. to call `set_app`.
+    pub fn cli(&mut self) -> &mut ModCli {
+        &mut self.cli
+    }
+
+    /// The registry to add commands to.
+    pub fn registry(&mut self) -> &mut CommandRegistry {
+        &mut self.cli.registry
+    }
+
+    /// Queue an answer for the next prompt.
+    pub fn answer(&mut self, line: &str) -> &mut Self {
+        self.answers.push_back(line.to_string());
+        self
+    }
+
+    /// Queue several answers, in order.
+    pub fn answers<I, S>(&mut self, lines: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.answers
+            .extend(lines.into_iter().map(|s| s.as_ref().to_string()));
+        self
+    }
+
+    /// Keep ANSI escape sequences in captured output.
+    pub fn keep_ansi(&mut self, keep: bool) -> &mut Self {
+        self.keep_ansi = keep;
+        self
+    }
+
+    /// Run `args` (without the program name). Unused answers stay queued
+    /// for the next run.
+    pub fn run<S: AsRef<str>>(&mut self, args: &[S]) -> TestOutput {
+        let argv = args.iter().map(|s| s.as_ref().to_string()).collect();
+        let session = Session {
+            answers: std::mem::take(&mut self.answers),
+            settings: Some(Settings::current()),
+            ..Session::default()
+        };
+        let previous = SESSION.with(|s| s.borrow_mut().replace(session));
+        let code = self.cli.run_with_status(argv);
+        let session = SESSION
+            .with(|s| std::mem::replace(&mut *s.borrow_mut(), previous))
+            .unwrap_or_default();
+        self.answers = session.answers;
+
+        let clean = |s: String| if self.keep_ansi { s } else { strip_ansi(&s) };
+        TestOutput {
+            code,
+            stdout: clean(session.out),
+            stderr: clean(session.err),
+            messages: session
+                .messages
+                .into_iter()
+                .map(|(category, message)| (category, clean(message)))
+                .collect(),
+        }
+    }
+}
+
+/// Result of `TestCli::run`.
+#[derive(Clone, Debug)]
+pub struct TestOutput {
+    pub code: ExitCode,
+    pub stdout: String,
+    pub stderr: String,
+    /// `output::hook` messages as `(category, message)`, in order, including
+    /// ones hidden by `--quiet`.
+    pub messages: Vec<(String, String)>,
+}
+
+impl TestOutput {
+    pub fn success(&self) -> bool {
+        self.code == ExitCode::SUCCESS
+    }
+
+    /// Hook messages of one category (`info`, `warn`, `error`, `unknown`, ...).
+    pub fn messages(&self, category: &str) -> Vec<&str> {
+        self.messages
+            .iter()
+            .filter(|(c, _)| c == category)
+            .map(|(_, m)| m.as_str())
+            .collect()
+    }
+}
+
+/// Remove ANSI escape sequences (CSI such as colors, and OSC such as links).
+pub fn strip_ansi(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            // CSI: parameters then a final byte in @..~
+            Some('[') => {
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            // OSC: until BEL or ESC \
+            Some(']') => {
+                while let Some(c) = chars.next() {
+                    if c == '\x07' {
+                        break;
+                    }
+                    if c == '\x1b' && chars.peek() == Some(&'\\') {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    out
+}
@@ -0,0 +1,231 @@
+//! Nested command trees.
+//!
+//! Commands are registered with `:`-separated paths (`cluster:node:drain`) and
+//! dispatched from space-separated words (`cluster node drain`) to any depth,
+//! using the longest registered prefix. Every proper prefix of a command path is
+//! a group node; invoking a group without a child prints its children's help.
+//! Register a `Group` to give a group help text or aliases.
+//!
+//! Aliases are resolved at the command's own level: alias `dr` on
+//! `cluster:node:drain` matches `cluster node dr`. An alias containing `:` is
+//! taken as a full path.
+//!
+//! `register` replaces a command with the same name and drops colliding
+//! aliases; `CommandRegistry::try_register` rejects them with a `Conflict`,
+//! and `CommandRegistry::check` lists every conflict in a registry.
+//!
+//! # Example
+//! ```no_run
+//! use modcli::tree::Group;
+//! use modcli::ModCli;
+//!
+//! let mut cli = ModCli::new();
+//! cli.registry
+//!     .register_group(Group::new("cluster").help("Manage clusters").aliases(&["cl"]));
+//! // `tool cl node drain web-1` dispatches to `cluster:node:drain`
+//! ```
+
+use crate::command::Command;
+use crate::help::HelpRenderer;
+use crate::loader::CommandRegistry;
+use crate::output::print;
+use std::collections::HashSet;
+
+/// Metadata for a group node (a path with children but no handler).
+#[derive(Clone, Debug, Default)]
+pub struct Group {
+    pub name: String,
+    pub help: Option<String>,
+    pub aliases: Vec<String>,
+    pub category: Option<String>,
+}
+
+/// Order of nodes in help listings, completions and references.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HelpOrder {
+    /// Sorted by name; categories sorted by title.
+    #[default]
+    Alphabetical,
+    /// In the order commands were registered; categories by first use.
+    Registration,
+}
+
+impl Group {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            ..Self::default()
+        }
+    }
+
+    pub fn help(mut self, help: &str) -> Self {
+        self.help = Some(help.to_string());
+        self
+    }
+
+    pub fn aliases(mut self, aliases: &[&str]) -> Self {
+        self.aliases = aliases.iter().map(|s| s.to_string()).collect();
+        self
+    }
+
+    /// Heading the group is listed under in its parent's help.
+    pub fn category(mut self, category: &str) -> Self {
+        self.category = Some(category.to_string());
+        self
+    }
+}
+
+/// A name or alias that collides with another registration.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Conflict {
+    /// Two commands registered under the same name; the later one won.
+    DuplicateCommand { name: String },
+    /// Alias `alias` of `owner` is the path of a command or group, which
+    /// takes precedence when routing.
+    AliasShadowed { alias: String, owner: String },
+    /// Two nodes declare the same alias path.
+    DuplicateAlias {
+        alias: String,
+        owner: String,
+        other: String,
+    },
+}
+
+impl std::fmt::Display for Conflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Conflict::DuplicateCommand { name } => {
+                write!(
+                    f,
+                    "command `{}` is registered more than once",
+                    display_path(name)
+                )
+            }
+            Conflict::AliasShadowed { alias, owner } => write!(
+                f,
+                "alias `{}` of `{}` is also a command or group path",
+                display_path(alias),
+                display_path(owner)
+            ),
+            Conflict::DuplicateAlias {
+                alias,
+                owner,
+                other,
+            } => write!(
+                f,
+                "alias `{}` is declared by both `{}` and `{}`",
+                display_path(alias),
+                display_path(other),
+                display_path(owner)
+            ),
+        }
+    }
+}
+
+/// Full `:` path of `alias` declared on the node `name` (`a:b:c` + `x` -> `a:b:x`).
+pub fn alias_path(name: &str, alias: &str) -> String {
+    if alias.contains(':') {
+        return alias.to_string();
+    }
+    match name.rsplit_once(':') {
+        Some((parent, _)) => format!("{parent}:{alias}"),
+        None => alias.to_string(),
+    }
+}
+
+/// Display form of a `:` path (`a:b` -> `a b`).
+pub fn display_path(path: &str) -> String {
+    path.replace(':', " ")
+}
+
+impl CommandRegistry {
+    /// Visible immediate children (commands and groups) of `path` as full `:`
+    /// paths, ordered by `help_order()`. The empty path lists top-level nodes.
+    pub fn children(&self, path: &str) -> Vec<String> {
+        let mut seen = HashSet::new();
+        let mut out = Vec::new();
+        let listed = |c: &dyn Command| {
+            self.is_visible(c) && (self.list_deprecated() || c.deprecated().is_none())
+        };
+        for cmd in self.all().filter(|c| listed(c.as_ref())) {
+            let rest = if path.is_empty() {
+                Some(cmd.name())
+            } else {
+                cmd.name()
+                    .strip_prefix(path)
+                    .and_then(|r| r.strip_prefix(':'))
+            };
+            if let Some(rest) = rest {
+                let seg = rest.split(':').next().unwrap_or(rest);
+                let child = if path.is_empty() {
+                    seg.to_string()
+                } else {
+                    format!("{path}:{seg}")
+                };
+                if seen.insert(child.clone()) {
+                    out.push(child);
+                }
+            }
+        }
+        if self.help_order() == HelpOrder::Alphabetical {
+            out.sort();
+        }
+        out
+    }
+
+    /// True if `path` is a group with at least one visible descendant.
+    pub fn is_group_visible(&self, path: &str) -> bool {
+        self.is_group(path) && !self.children(path).is_empty()
+    }
+
+    /// Category of a node: the command's `category()` or the group's.
+    pub fn node_category(&self, path: &str) -> Option<&str> {
+        match self.get(path) {
+            Some(cmd) => cmd.category(),
+            None => self.group(path).and_then(|g| g.category.as_deref()),
+        }
+    }
+
+    /// Aliases of a node as typed at its own level (`dr` for
+    /// `cluster:node:drain`; full-path aliases in word form). Deprecated
+    /// aliases are left out.
+    pub fn node_aliases(&self, path: &str) -> Vec<String> {
+        let aliases: Vec<&str> = match self.get(path) {
+            Some(cmd) => cmd.aliases().to_vec(),
+            None => self
+                .group(path)
+                .map(|g| g.aliases.iter().map(String::as_str).collect())
+                .unwrap_or_default(),
+        };
+        aliases
+            .into_iter()
+            .filter(|a| self.deprecation(&alias_path(path, a)).is_none())
+            .map(display_path)
+            .collect()
+    }
+
+    /// Help text for a node: the command's `help()` or the group's help.
+    pub fn node_help(&self, path: &str) -> Option<&str> {
+        match self.get(path) {
+            Some(cmd) => cmd.help(),
+            None => self.group(path).and_then(|g| g.help.as_deref()),
+        }
+    }
+
+    /// Print a visible command's help page (see `help::HelpRenderer`).
+    /// Returns false (printing nothing) for unknown or invisible commands.
+    pub fn print_command_help(&self, path: &str) -> bool {
+        match HelpRenderer::new().command(self, path) {
+            Some(page) => {
+                print::write(&page);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Print a group's help text followed by its visible children.
+    pub fn print_group_help(&self, path: &str) {
+        print::write(&HelpRenderer::new().listing(self, path));
+    }
+}
@@ -0,0 +1,216 @@
+#![cfg(all(feature = "async", feature = "tokio-runtime"))]
+
+use modcli::command::{AsyncCommand, Command, CommandFuture};
+use modcli::context::Context;
+use modcli::error::ModCliError;
+use modcli::exit::ExitCode;
+use modcli::loader::CommandRegistry;
+use modcli::output::handle::Output;
+use modcli::spec::{ArgSpec, CommandSpec};
+use std::cell::RefCell;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+type BoxFut<'a> = Pin<Box<dyn Future<Output = Result<(), ModCliError>> + Send + 'a>>;
+
+static RUNS: AtomicUsize = AtomicUsize::new(0);
+
+struct Fetch;
+
+impl AsyncCommand for Fetch {
+    fn name(&self) -> &str {
+        "net:fetch"
+    }
+    fn aliases(&self) -> &[&str] {
+        &["get"]
+    }
+    fn help(&self) -> Option<&str> {
+        Some("Fetch a URL")
+    }
+    fn required_caps(&self) -> &[&str] {
+        &["net"]
+    }
+    fn validate(&self, args: &[String]) -> Result<(), ModCliError> {
+        if args.is_empty() {
+            return Err(ModCliError::InvalidUsage("fetch needs a URL".into()));
+        }
+        Ok(())
+    }
+    fn execute_async<'a>(&'a self, _args: &'a [String]) -> BoxFut<'a> {
+        Box::pin(async move {
+            tokio::task::yield_now().await;
+            RUNS.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        })
+    }
+}
+
+struct Secret;
+
+impl AsyncCommand for Secret {
+    fn name(&self) -> &str {
+        "secret"
+    }
+    fn hidden(&self) -> bool {
+        true
+    }
+    fn execute_async<'a>(&'a self, _args: &'a [String]) -> BoxFut<'a> {
+        Box::pin(async { Ok(()) })
+    }
+}
+
+/// Counts its `--retry` option through `run_async`.
+struct Ping;
+
+impl AsyncCommand for Ping {
+    fn name(&self) -> &str {
+        "net:ping"
+    }
+    fn spec(&self) -> Option<CommandSpec> {
+        Some(
+            CommandSpec::new()
+                .arg(ArgSpec::positional("host").required())
+                .arg(ArgSpec::option("retry").default("1")),
+        )
+    }
+    fn execute_async<'a>(&'a self, _args: &'a [String]) -> BoxFut<'a> {
+        Box::pin(async { Ok(()) })
+    }
+    fn run_async<'a>(&'a self, ctx: &'a Context<'_>) -> CommandFuture<'a> {
+        Box::pin(async move {
+            tokio::task::yield_now().await;
+            let host = ctx.args().get_str("host").unwrap_or_default();
+            let retry: u8 = ctx.args().get("retry").unwrap_or_default();
+            ctx.out().line(&format!("{} {host} x{retry}", ctx.path()));
+            Ok(ExitCode(retry))
+        })
+    }
+}
+
+struct Local;
+
+impl Command for Local {
+    fn name(&self) -> &str {
+        "local"
+    }
+    fn execute(&self, _args: &[String]) {}
+}
+
+fn registry() -> CommandRegistry {
+    let mut reg = CommandRegistry::new();
+    reg.register_async(Box::new(Fetch));
+    reg.register_async(Box::new(Secret));
+    reg.register_async(Box::new(Ping));
+    reg.register(Box::new(Local));
+    reg.set_output(Output::buffer());
+    reg
+}
+
+fn s(items: &[&str]) -> Vec<String> {
+    items.iter().map(|s| s.to_string()).collect()
+}
+
+#[tokio::test]
+async fn async_commands_route_through_groups_and_aliases() {
+    let mut reg = registry();
+    reg.grant_cap("net");
+    let before = RUNS.load(Ordering::SeqCst);
+    reg.try_execute_async("net", &s(&["fetch", "https://a"]))
+        .await
+        .unwrap();
+    reg.try_execute_async("net", &s(&["get", "https://b"]))
+        .await
+        .unwrap();
+    assert_eq!(RUNS.load(Ordering::SeqCst), before + 2);
+
+    // sync commands run through the async entry point too
+    reg.try_execute_async("local", &[]).await.unwrap();
+}
+
+#[tokio::test]
+async fn run_async_gets_the_parsed_context() {
+    let reg = registry();
+    let code = reg
+        .try_run_async("net", &s(&["ping", "example.org", "--retry", "3"]))
+        .await
+        .unwrap();
+    assert_eq!(code, ExitCode(3));
+    assert_eq!(reg.output().contents(), "net:ping example.org x3\n");
+}
+
+#[tokio::test]
+async fn async_dispatch_checks_caps_and_validate() {
+    let mut reg = registry();
+    let err = reg
+        .try_execute_async("net:fetch", &s(&["https://a"]))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ModCliError::Unauthorized(_)));
+
+    reg.grant_cap("net");
+    let err = reg.try_execute_async("net:fetch", &[]).await.unwrap_err();
+    assert!(err.to_string().contains("needs a URL"));
+}
+
+#[tokio::test]
+async fn authorize_policy_sees_the_async_command() {
+    thread_local! {
+        static SEEN: RefCell<Vec<(String, Vec<String>)>> = const { RefCell::new(Vec::new()) };
+    }
+    let mut reg = registry();
+    reg.set_authorize_policy(|cmd, _caps, _args| {
+        SEEN.with(|s| {
+            s.borrow_mut().push((
+                cmd.name().to_string(),
+                cmd.required_caps().iter().map(|c| c.to_string()).collect(),
+            ))
+        });
+        Ok(())
+    });
+    reg.try_execute_async("net:fetch", &s(&["u"]))
+        .await
+        .unwrap();
+    let seen = SEEN.with(|s| s.borrow().clone());
+    assert_eq!(
+        seen,
+        vec![("net:fetch".to_string(), vec!["net".to_string()])]
+    );
+}
+
+#[tokio::test]
+async fn unknown_async_command_suggests_from_unified_registry() {
+    let reg = registry();
+    match reg.try_execute_async("locl", &[]).await {
+        Err(ModCliError::UnknownCommand { suggestions, .. }) => {
+            assert_eq!(suggestions, vec!["local".to_string()])
+        }
+        other => panic!("unexpected: {other:?}"),
+    }
+}
+
+#[test]
+fn async_commands_share_metadata_and_visibility() {
+    let mut reg = registry();
+    let fetch = reg.get("net:fetch").expect("async command is registered");
+    assert_eq!(fetch.help(), Some("Fetch a URL"));
+    assert!(!reg.is_visible(fetch));
+    assert!(reg.is_group("net"));
+    assert!(reg.is_async("net:fetch"));
+    assert!(!reg.is_async("local"));
+    assert!(!reg.is_visible(reg.get("secret").unwrap()));
+
+    reg.grant_cap("net");
+    assert!(reg.is_visible(reg.get("net:fetch").unwrap()));
+    let mut names: Vec<&str> = reg.all_async().map(|c| c.name()).collect();
+    names.sort();
+    assert_eq!(names, vec!["net:fetch", "net:ping", "secret"]);
+}
+
+#[test]
+fn sync_dispatch_of_async_command_is_an_error() {
+    let mut reg = registry();
+    reg.grant_cap("net");
+    let err = reg.try_execute("net:fetch", &s(&["u"])).unwrap_err();
+    assert!(err.to_string().contains("execute_async"));
+}
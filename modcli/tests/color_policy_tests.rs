@@ -0,0 +1,158 @@
+use crossterm::style::{Attribute, Color};
+use modcli::output::color_policy::{self, detect, downsample, ColorChoice, ColorLevel};
+use modcli::output::{build, markdown};
+use std::collections::HashMap;
+
+fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+    let map: HashMap<String, String> = vars
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+    move |key| map.get(key).cloned()
+}
+
+#[test]
+fn auto_follows_tty_and_environment() {
+    let term = [("TERM", "xterm")];
+    assert_eq!(
+        detect(ColorChoice::Auto, env(&term), true),
+        ColorLevel::Basic
+    );
+    assert_eq!(
+        detect(ColorChoice::Auto, env(&term), false),
+        ColorLevel::None
+    );
+    assert_eq!(
+        detect(ColorChoice::Auto, env(&[("TERM", "dumb")]), true),
+        ColorLevel::None
+    );
+    assert_eq!(
+        detect(
+            ColorChoice::Auto,
+            env(&[("TERM", "xterm"), ("NO_COLOR", "1")]),
+            true
+        ),
+        ColorLevel::None
+    );
+    assert_eq!(
+        detect(
+            ColorChoice::Auto,
+            env(&[("TERM", "xterm"), ("NO_COLOR", "")]),
+            true
+        ),
+        ColorLevel::Basic
+    );
+    assert_eq!(
+        detect(
+            ColorChoice::Auto,
+            env(&[("TERM", "xterm"), ("CLICOLOR", "0")]),
+            true
+        ),
+        ColorLevel::None
+    );
+    assert_eq!(
+        detect(
+            ColorChoice::Auto,
+            env(&[("TERM", "xterm-256color"), ("CLICOLOR_FORCE", "1")]),
+            false
+        ),
+        ColorLevel::Ansi256
+    );
+    assert_eq!(
+        detect(ColorChoice::Auto, env(&[("CLICOLOR_FORCE", "0")]), false),
+        ColorLevel::None
+    );
+}
+
+#[test]
+fn explicit_choices_and_depth() {
+    let truecolor = env(&[("TERM", "xterm-256color"), ("COLORTERM", "truecolor")]);
+    assert_eq!(
+        detect(ColorChoice::Always, &truecolor, false),
+        ColorLevel::TrueColor
+    );
+    assert_eq!(
+        detect(ColorChoice::Never, &truecolor, true),
+        ColorLevel::None
+    );
+    assert_eq!(
+        detect(ColorChoice::Always, env(&[("NO_COLOR", "1")]), false),
+        ColorLevel::Basic
+    );
+    assert_eq!("never".parse::<ColorChoice>().unwrap(), ColorChoice::Never);
+    assert!("sometimes".parse::<ColorChoice>().is_err());
+}
+
+#[test]
+fn rgb_is_downsampled_to_the_level() {
+    let orange = Color::Rgb {
+        r: 255,
+        g: 135,
+        b: 0,
+    };
+    assert_eq!(downsample(orange, ColorLevel::TrueColor), Some(orange));
+    assert_eq!(
+        downsample(orange, ColorLevel::Ansi256),
+        Some(Color::AnsiValue(208))
+    );
+    assert_eq!(downsample(orange, ColorLevel::Basic), Some(Color::Yellow));
+    assert_eq!(downsample(orange, ColorLevel::None), None);
+
+    let grey = Color::Rgb {
+        r: 100,
+        g: 100,
+        b: 100,
+    };
+    assert_eq!(
+        downsample(grey, ColorLevel::Ansi256),
+        Some(Color::AnsiValue(241))
+    );
+    assert_eq!(
+        downsample(Color::AnsiValue(196), ColorLevel::Basic),
+        Some(Color::Red)
+    );
+    assert_eq!(
+        downsample(Color::AnsiValue(4), ColorLevel::Basic),
+        Some(Color::DarkBlue)
+    );
+    assert_eq!(
+        downsample(Color::Cyan, ColorLevel::Basic),
+        Some(Color::Cyan)
+    );
+}
+
+// The policy is process-wide, so everything that changes it runs in one test.
+#[test]
+fn styling_follows_the_policy() {
+    let red = Color::Rgb {
+        r: 250,
+        g: 10,
+        b: 10,
+    };
+
+    color_policy::set_choice(ColorChoice::Always);
+    color_policy::set_level(Some(ColorLevel::TrueColor));
+    assert_eq!(
+        color_policy::paint("x", Some(red), Some(Color::Blue), &[Attribute::Bold]),
+        "\x1b[1;38;2;250;10;10;104mx\x1b[0m"
+    );
+    color_policy::set_level(Some(ColorLevel::Ansi256));
+    assert_eq!(color_policy::fg("x", red), "\x1b[38;5;196mx\x1b[0m");
+    color_policy::set_level(Some(ColorLevel::Basic));
+    assert_eq!(build().part("x").color(red).get(), "\x1b[91mx\x1b[0m");
+    assert_eq!(
+        markdown::render_markdown("# Title"),
+        "\x1b[1mTitle\x1b[0m\n"
+    );
+
+    color_policy::set_choice(ColorChoice::Never);
+    assert!(!color_policy::enabled());
+    assert_eq!(build().part("x").color(red).bold().get(), "x");
+    assert_eq!(
+        markdown::render_markdown("# Title\nsome **bold** `code`"),
+        "Title\nsome bold code\n"
+    );
+
+    color_policy::set_choice(ColorChoice::Auto);
+    color_policy::set_level(None);
+}
@@ -0,0 +1,124 @@
+use modcli::command::Command;
+use modcli::completions::Shell;
+use modcli::loader::CommandRegistry;
+use modcli::spec::{ArgSpec, CommandSpec};
+
+struct Deploy;
+
+impl Command for Deploy {
+    fn name(&self) -> &str {
+        "deploy"
+    }
+    fn aliases(&self) -> &[&str] {
+        &["d"]
+    }
+    fn help(&self) -> Option<&str> {
+        Some("Deploy the **app**")
+    }
+    fn spec(&self) -> Option<CommandSpec> {
+        Some(
+            CommandSpec::new()
+                .arg(ArgSpec::positional("target"))
+                .arg(ArgSpec::option("port").short('p').help("Port to bind")),
+        )
+    }
+    fn execute(&self, _args: &[String]) {}
+}
+
+struct NodeDrain;
+
+impl Command for NodeDrain {
+    fn name(&self) -> &str {
+        "node:drain"
+    }
+    fn help(&self) -> Option<&str> {
+        Some("Drain a node")
+    }
+    fn execute(&self, _args: &[String]) {}
+}
+
+struct Secret;
+
+impl Command for Secret {
+    fn name(&self) -> &str {
+        "secret"
+    }
+    fn hidden(&self) -> bool {
+        true
+    }
+    fn execute(&self, _args: &[String]) {}
+}
+
+struct Admin;
+
+impl Command for Admin {
+    fn name(&self) -> &str {
+        "admin"
+    }
+    fn required_caps(&self) -> &[&str] {
+        &["admin"]
+    }
+    fn execute(&self, _args: &[String]) {}
+}
+
+fn registry() -> CommandRegistry {
+    let mut reg = CommandRegistry::new();
+    reg.register(Box::new(Deploy));
+    reg.register(Box::new(NodeDrain));
+    reg.register(Box::new(Secret));
+    reg.register(Box::new(Admin));
+    reg
+}
+
+#[test]
+fn bash_lists_commands_aliases_namespaces_and_flags() {
+    let script = registry().generate_completions(Shell::Bash, "tool");
+    assert!(script.contains("complete -F _tool tool"));
+    let top = script
+        .lines()
+        .find(|l| l.trim_start().starts_with("'')"))
+        .unwrap();
+    for word in ["deploy", "d", "node", "help"] {
+        assert!(top.split([' ', '\'']).any(|w| w == word), "{word}: {top}");
+    }
+    assert!(script.contains("'node') echo 'drain'"));
+    assert!(script.contains("'deploy') echo '--port -p'"));
+    assert!(script.contains("'d') echo '--port -p'"));
+}
+
+#[test]
+fn hidden_and_gated_commands_are_not_leaked() {
+    let mut reg = registry();
+    for shell in Shell::all() {
+        let script = reg.generate_completions(shell, "tool");
+        assert!(!script.contains("secret"), "{shell:?}");
+        assert!(!script.contains("admin"), "{shell:?}");
+    }
+    reg.grant_cap("admin");
+    let script = reg.generate_completions(Shell::Zsh, "tool");
+    assert!(script.contains("'admin'"));
+}
+
+#[test]
+fn descriptions_and_shell_specific_forms() {
+    let reg = registry();
+    let zsh = reg.generate_completions(Shell::Zsh, "tool");
+    assert!(zsh.starts_with("#compdef tool"));
+    assert!(zsh.contains("'deploy:Deploy the app'"));
+    assert!(zsh.contains("'--port:Port to bind'"));
+
+    let fish = reg.generate_completions(Shell::Fish, "tool");
+    assert!(fish.contains("complete -c tool -n '__tool_at node' -a 'drain' -d 'Drain a node'"));
+    assert!(fish.contains("complete -c tool -n '__tool_at deploy' -l port -d 'Port to bind'"));
+
+    let ps = reg.generate_completions(Shell::PowerShell, "tool");
+    assert!(ps.contains("Register-ArgumentCompleter -Native -CommandName 'tool'"));
+    assert!(ps.contains("'node' = @([pscustomobject]@{ n = 'drain'; d = 'Drain a node' })"));
+}
+
+#[test]
+fn shell_parses_from_name() {
+    assert_eq!("pwsh".parse::<Shell>().unwrap(), Shell::PowerShell);
+    assert_eq!("ZSH".parse::<Shell>().unwrap(), Shell::Zsh);
+    assert!("tcsh".parse::<Shell>().is_err());
+}
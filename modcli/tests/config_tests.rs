@@ -0,0 +1,193 @@
+#![cfg(feature = "config")]
+
+use modcli::config::{ConfigLoader, FrameworkConfig};
+use modcli::error::ModCliError;
+use modcli::exit::{ExitCode, ExitCodes};
+use modcli::ModCli;
+use serde::Deserialize;
+use serde_json::json;
+use std::path::PathBuf;
+
+fn argv(items: &[&str]) -> Vec<String> {
+    items.iter().map(|s| s.to_string()).collect()
+}
+
+// Unique scratch file per test; tests run in parallel.
+fn write_file(name: &str, contents: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("modcli-config-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Server {
+    host: String,
+    port: u16,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct AppConfig {
+    server: Server,
+    #[serde(default)]
+    verbose: bool,
+}
+
+#[test]
+fn layers_merge_in_precedence_order() {
+    let file = write_file(
+        "layers.toml",
+        "verbose = true\n[server]\nhost = \"file.example\"\nport = 8000\n",
+    );
+    std::env::set_var("CFGLAYERS_SERVER__PORT", "9000");
+    let mut args = argv(&["--set", "server.host=flag.example", "status"]);
+    let cfg: AppConfig = ConfigLoader::new("cfglayers")
+        .search(false)
+        .defaults(&json!({ "server": { "host": "localhost", "port": 80 } }))
+        .file(&file)
+        .args(&mut args)
+        .load()
+        .unwrap();
+    assert_eq!(
+        cfg,
+        AppConfig {
+            server: Server {
+                host: "flag.example".into(),
+                port: 9000,
+            },
+            verbose: true,
+        }
+    );
+    assert_eq!(args, argv(&["status"]));
+}
+
+#[test]
+fn config_flag_reads_json_and_leaves_command_args() {
+    let file = write_file(
+        "flag.json",
+        r#"{ "server": { "host": "json", "port": 1 } }"#,
+    );
+    let flag = format!("--config={}", file.display());
+    let mut args = argv(&[&flag, "run", "--set", "x=1", "--config", "job.toml"]);
+    let value = ConfigLoader::new("cfgflag")
+        .search(false)
+        .no_env()
+        .args(&mut args)
+        .load_value()
+        .unwrap();
+    assert_eq!(value["server"]["host"], "json");
+    assert_eq!(value.get("x"), None);
+    assert_eq!(args, argv(&["run", "--set", "x=1", "--config", "job.toml"]));
+
+    let mut args = argv(&["-v", "--", "--set", "x=1"]);
+    ConfigLoader::new("cfgflag").args(&mut args);
+    assert_eq!(args, argv(&["-v", "--", "--set", "x=1"]));
+}
+
+#[test]
+fn xdg_config_home_is_searched() {
+    let file = write_file("xdg/cfgxdg/config.toml", "name = \"from-xdg\"\n");
+    std::env::set_var("XDG_CONFIG_HOME", file.parent().unwrap().parent().unwrap());
+
+    let loader = ConfigLoader::new("cfgxdg").no_env();
+    assert_eq!(loader.search_paths(), vec![file]);
+    assert_eq!(loader.load_value().unwrap()["name"], "from-xdg");
+}
+
+#[test]
+fn env_values_are_typed_when_they_parse() {
+    std::env::set_var("CFGENV_LIMITS__MAX", "12");
+    std::env::set_var("CFGENV_DEBUG", "true");
+    std::env::set_var("CFGENV_LABEL", "v1.2");
+    let value = ConfigLoader::new("cfgenv")
+        .search(false)
+        .load_value()
+        .unwrap();
+    assert_eq!(
+        value,
+        json!({ "limits": { "max": 12 }, "debug": true, "label": "v1.2" })
+    );
+}
+
+#[test]
+fn errors_map_to_config_exit_code() {
+    let bad = write_file("bad.toml", "server = [");
+    let err = ConfigLoader::new("cfgbad")
+        .search(false)
+        .file(&bad)
+        .load_value()
+        .unwrap_err();
+    assert!(matches!(err, ModCliError::Config(_)));
+    assert_eq!(ExitCodes::default().code_for(&err), ExitCode::CONFIG);
+
+    let missing = ConfigLoader::new("cfgbad")
+        .search(false)
+        .file("/nonexistent/modcli.toml")
+        .load_value();
+    assert!(missing.is_err());
+
+    let mut args = argv(&["--set", "novalue"]);
+    let err = ConfigLoader::new("cfgbad")
+        .search(false)
+        .args(&mut args)
+        .load_value()
+        .unwrap_err();
+    assert!(err.to_string().contains("key=value"));
+
+    let err = ConfigLoader::new("cfgbad")
+        .search(false)
+        .no_env()
+        .load::<AppConfig>()
+        .unwrap_err();
+    assert!(matches!(err, ModCliError::Config(_)));
+}
+
+#[test]
+fn framework_section_reads_example_schema() {
+    let data = include_str!("../examples/config.json");
+    let value: serde_json::Value = serde_json::from_str(data).unwrap();
+    let fw = FrameworkConfig::from_value(&value).unwrap();
+    assert_eq!(fw.prefix.as_deref(), Some("mod"));
+    assert_eq!(fw.theme.as_deref(), Some("default"));
+    assert!(fw.messages.contains_key("not_found"));
+    assert_eq!(fw.shell.as_ref().unwrap().welcome.len(), 2);
+    assert!(fw.shell().is_some());
+
+    assert_eq!(
+        FrameworkConfig::from_value(&json!({})).unwrap(),
+        FrameworkConfig::default()
+    );
+}
+
+#[test]
+fn configure_reports_a_banner_that_cannot_be_applied() {
+    modcli::set_startup_banner(|| {});
+    let mut cli = ModCli::new();
+    let loader = ConfigLoader::new("cfgbanner")
+        .search(false)
+        .no_env()
+        .defaults(&json!({ "modcli": { "banner": "Welcome" } }));
+    let err = cli.configure::<serde_json::Value>(loader).unwrap_err();
+    assert!(matches!(err, ModCliError::Config(ref msg) if msg.contains("banner")));
+}
+
+#[test]
+fn configure_applies_prefix_and_messages() {
+    let mut cli = ModCli::new();
+    let loader = ConfigLoader::new("cfgapply")
+        .search(false)
+        .no_env()
+        .defaults(&json!({
+            "modcli": { "prefix": "tool", "messages": { "help.footer": "See docs" } },
+            "server": { "host": "h", "port": 2 }
+        }));
+    let cfg: AppConfig = cli.configure(loader).unwrap();
+    assert_eq!(cfg.server.port, 2);
+    assert_eq!(cli.get_prefix(), "tool");
+    assert_eq!(
+        modcli::output::messages::get_message("help.footer").as_deref(),
+        Some("See docs")
+    );
+}
@@ -0,0 +1,113 @@
+use modcli::command::Command;
+use modcli::deprecation::Deprecation;
+use modcli::error::ModCliError;
+use modcli::loader::CommandRegistry;
+use modcli::tree::{Conflict, Group};
+
+struct Cmd {
+    name: &'static str,
+    aliases: &'static [&'static str],
+}
+
+impl Command for Cmd {
+    fn name(&self) -> &str {
+        self.name
+    }
+    fn aliases(&self) -> &[&str] {
+        self.aliases
+    }
+    fn execute(&self, _args: &[String]) {}
+}
+
+fn cmd(name: &'static str, aliases: &'static [&'static str]) -> Box<Cmd> {
+    Box::new(Cmd { name, aliases })
+}
+
+fn conflict(result: Result<(), ModCliError>) -> Conflict {
+    match result {
+        Err(ModCliError::Conflict(c)) => c,
+        other => panic!("expected a conflict, got {other:?}"),
+    }
+}
+
+#[test]
+fn default_registry_has_no_conflicts() {
+    CommandRegistry::new().check().unwrap();
+}
+
+#[test]
+fn try_register_rejects_duplicate_names() {
+    let mut reg = CommandRegistry::new();
+    reg.try_register(cmd("deploy", &["dp"])).unwrap();
+    let err = reg.try_register(cmd("deploy", &[])).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "registration conflict: command `deploy` is registered more than once"
+    );
+    assert_eq!(reg.get("deploy").unwrap().aliases(), &["dp"]);
+}
+
+#[test]
+fn try_register_rejects_alias_collisions() {
+    let mut reg = CommandRegistry::new();
+    reg.try_register(cmd("cluster:node:drain", &["dr"]))
+        .unwrap();
+    reg.register_group(Group::new("cluster").aliases(&["cl"]));
+
+    // alias vs alias, at the same level
+    assert_eq!(
+        conflict(reg.try_register(cmd("cluster:node:describe", &["dr"]))),
+        Conflict::DuplicateAlias {
+            alias: "cluster:node:dr".into(),
+            owner: "cluster:node:describe".into(),
+            other: "cluster:node:drain".into(),
+        }
+    );
+    // alias vs existing command
+    assert_eq!(
+        conflict(reg.try_register(cmd("cluster:node:cordon", &["drain"]))),
+        Conflict::AliasShadowed {
+            alias: "cluster:node:drain".into(),
+            owner: "cluster:node:cordon".into(),
+        }
+    );
+    // new command (or namespace) over an existing alias
+    assert_eq!(
+        conflict(reg.try_register(cmd("cl:status", &[]))),
+        Conflict::AliasShadowed {
+            alias: "cl".into(),
+            owner: "cluster".into(),
+        }
+    );
+    // nothing was registered
+    assert!(reg.get("cluster:node:describe").is_none());
+    assert!(!reg.is_group("cl"));
+    reg.check().unwrap();
+}
+
+#[test]
+fn check_reports_what_register_allowed() {
+    let mut reg = CommandRegistry::new();
+    reg.register(cmd("build", &["b"]));
+    reg.register(cmd("build", &["b"]));
+    reg.register(cmd("bench", &["b", "test"]));
+    reg.register(cmd("test", &[]));
+    reg.rename("make", "build", Deprecation::new());
+    reg.register(cmd("make", &[]));
+
+    let conflicts: Vec<String> = reg
+        .check()
+        .unwrap_err()
+        .iter()
+        .map(|c| c.to_string())
+        .collect();
+    assert_eq!(
+        conflicts,
+        vec![
+            "command `build` is registered more than once",
+            "alias `b` is declared by both `build` and `bench`",
+            "alias `test` of `bench` is also a command or group path",
+            "alias `make` of `build` is also a command or group path",
+        ]
+    );
+}
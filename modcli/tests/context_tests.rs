@@ -0,0 +1,96 @@
+use modcli::command::Command;
+use modcli::context::Context;
+use modcli::error::ModCliError;
+use modcli::exit::ExitCode;
+use modcli::loader::CommandRegistry;
+use modcli::output::handle::Output;
+use modcli::spec::{ArgSpec, CommandSpec};
+
+struct AppState {
+    region: String,
+}
+
+struct Drain;
+
+impl Command for Drain {
+    fn name(&self) -> &str {
+        "cluster:node:drain"
+    }
+    fn spec(&self) -> Option<CommandSpec> {
+        Some(CommandSpec::new().arg(ArgSpec::positional("node")))
+    }
+    fn execute(&self, _args: &[String]) {}
+    fn run(&self, ctx: &Context) -> Result<ExitCode, ModCliError> {
+        let state = ctx
+            .get::<AppState>()
+            .ok_or(ModCliError::Other("no state".into()))?;
+        ctx.out().line(&format!(
+            "{} {} {} admin={}",
+            ctx.path(),
+            ctx.args().get_str("node").unwrap_or("-"),
+            state.region,
+            ctx.has_cap("admin")
+        ));
+        ctx.out().eline(&ctx.invocation().join(" "));
+        if ctx.is_cancelled() {
+            return Ok(ExitCode(130));
+        }
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+fn registry(out: &Output) -> CommandRegistry {
+    let mut reg = CommandRegistry::new();
+    reg.register(Box::new(Drain));
+    reg.set_output(out.clone());
+    reg
+}
+
+fn argv(items: &[&str]) -> Vec<String> {
+    items.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn context_carries_path_args_state_caps_and_output() {
+    let out = Output::buffer();
+    let mut reg = registry(&out);
+    reg.insert_state(AppState {
+        region: "eu".into(),
+    });
+    reg.grant_cap("admin");
+
+    let code = reg
+        .try_run("cluster", &argv(&["node", "drain", "web-1"]))
+        .unwrap();
+    assert_eq!(code, ExitCode::SUCCESS);
+    assert_eq!(out.contents(), "cluster:node:drain web-1 eu admin=true\n");
+    assert_eq!(out.err_contents(), "cluster node drain web-1\n");
+}
+
+#[test]
+fn missing_state_is_an_error() {
+    let reg = registry(&Output::buffer());
+    assert!(matches!(
+        reg.try_run("cluster:node:drain", &[]),
+        Err(ModCliError::Other(_))
+    ));
+}
+
+#[test]
+fn cancellation_is_visible_to_commands() {
+    let out = Output::buffer();
+    let mut reg = registry(&out);
+    reg.insert_state(AppState {
+        region: "us".into(),
+    });
+    reg.cancellation().cancel();
+    assert_eq!(
+        reg.try_run("cluster:node:drain", &[]).unwrap(),
+        ExitCode(130)
+    );
+    reg.cancellation().reset();
+    assert_eq!(
+        reg.try_run("cluster:node:drain", &[]).unwrap(),
+        ExitCode::SUCCESS
+    );
+}
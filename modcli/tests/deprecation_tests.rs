@@ -0,0 +1,168 @@
+use modcli::command::Command;
+use modcli::context::Context;
+use modcli::deprecation::Deprecation;
+use modcli::error::ModCliError;
+use modcli::exit::ExitCode;
+use modcli::help::HelpRenderer;
+use modcli::loader::CommandRegistry;
+use modcli::output::messages;
+use std::borrow::Cow;
+use std::sync::{Mutex, Once};
+
+static WARNINGS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+// Deprecation warnings mentioning `name` seen so far.
+fn warnings(name: &str) -> Vec<String> {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        messages::set_output_interceptor(|category, text| {
+            if category == "deprecated" {
+                WARNINGS.lock().unwrap().push(text.to_string());
+            }
+            Cow::Owned(text.to_string())
+        })
+    });
+    WARNINGS
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|w| w.contains(name))
+        .cloned()
+        .collect()
+}
+
+struct Cmd {
+    name: &'static str,
+    aliases: &'static [&'static str],
+    deprecated: Option<Deprecation>,
+}
+
+impl Command for Cmd {
+    fn name(&self) -> &str {
+        self.name
+    }
+    fn aliases(&self) -> &[&str] {
+        self.aliases
+    }
+    fn help(&self) -> Option<&str> {
+        Some("Does things")
+    }
+    fn deprecated(&self) -> Option<Deprecation> {
+        self.deprecated.clone()
+    }
+    fn execute(&self, _args: &[String]) {}
+    fn run(&self, ctx: &Context) -> Result<ExitCode, ModCliError> {
+        ctx.out().line(ctx.path());
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+fn cmd(name: &'static str) -> Box<Cmd> {
+    Box::new(Cmd {
+        name,
+        aliases: &[],
+        deprecated: None,
+    })
+}
+
+fn registry() -> CommandRegistry {
+    let mut reg = CommandRegistry::new();
+    reg.set_output(modcli::output::handle::Output::buffer());
+    reg
+}
+
+#[test]
+fn deprecated_command_warns_once() {
+    let _ = warnings("");
+    let mut reg = registry();
+    reg.register(Box::new(Cmd {
+        name: "legacy-sync",
+        aliases: &[],
+        deprecated: Some(Deprecation::new().removed_in("3.0").note("It was slow.")),
+    }));
+    reg.try_run("legacy-sync", &[]).unwrap();
+    reg.try_run("legacy-sync", &[]).unwrap();
+    assert_eq!(
+        warnings("legacy-sync"),
+        vec!["`legacy-sync` is deprecated and will be removed in 3.0. It was slow."]
+    );
+}
+
+#[test]
+fn renamed_command_forwards_to_new_name() {
+    let _ = warnings("");
+    let mut reg = registry();
+    reg.register(cmd("release:publish"));
+    reg.rename("push-release", "release:publish", Deprecation::new());
+    reg.try_run("push-release", &[]).unwrap();
+    reg.try_run("release", &["publish".into()]).unwrap();
+    assert_eq!(
+        reg.output().contents(),
+        "release:publish\nrelease:publish\n"
+    );
+    assert_eq!(
+        warnings("push-release"),
+        vec!["`push-release` is deprecated; use `release publish` instead."]
+    );
+}
+
+#[test]
+fn deprecated_nested_alias_warns_and_still_routes() {
+    let _ = warnings("");
+    let mut reg = registry();
+    reg.register(Box::new(Cmd {
+        name: "fleet:node:drain",
+        aliases: &["dr", "evict"],
+        deprecated: None,
+    }));
+    reg.deprecate_alias(
+        "fleet:node:evict",
+        Deprecation::new().replaced_by("fleet:node:drain"),
+    );
+    reg.try_run("fleet", &["node".into(), "evict".into()])
+        .unwrap();
+    reg.try_run("fleet", &["node".into(), "dr".into()]).unwrap();
+    assert_eq!(
+        warnings("evict"),
+        vec!["`fleet node evict` is deprecated; use `fleet node drain` instead."]
+    );
+    assert!(warnings("fleet node dr`").is_empty());
+    assert_eq!(reg.node_aliases("fleet:node:drain"), vec!["dr"]);
+}
+
+// Listing rows with column padding collapsed.
+fn rows(text: &str) -> Vec<String> {
+    text.lines()
+        .map(|l| l.split_whitespace().collect::<Vec<_>>().join(" "))
+        .collect()
+}
+
+#[test]
+fn help_annotates_or_hides_deprecated_entries() {
+    let mut reg = registry();
+    reg.register(Box::new(Cmd {
+        name: "old-report",
+        aliases: &["or"],
+        deprecated: Some(Deprecation::new().replaced_by("report")),
+    }));
+    reg.register(Box::new(Cmd {
+        name: "report",
+        aliases: &["rp", "rep"],
+        deprecated: None,
+    }));
+    reg.deprecate_alias("rep", Deprecation::new());
+    let help = HelpRenderer::new().width(80).color(false);
+
+    let listing = rows(&help.listing(&reg, ""));
+    assert!(listing.contains(&"old-report, or Does things [deprecated, use report]".to_string()));
+    assert!(listing.contains(&"report, rp Does things".to_string()));
+    let page = help.command(&reg, "report").unwrap();
+    assert!(page.contains("ALIASES:\n  rp, rep (deprecated)\n"));
+    let page = help.command(&reg, "old-report").unwrap();
+    assert!(page.contains("Does things\nold-report is deprecated; use report instead.\n"));
+
+    reg.set_list_deprecated(false);
+    assert!(!help.listing(&reg, "").contains("old-report"));
+    let page = help.command(&reg, "report").unwrap();
+    assert!(page.contains("ALIASES:\n  rp\n"));
+}
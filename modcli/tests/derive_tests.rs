@@ -0,0 +1,158 @@
+#![cfg(feature = "derive")]
+
+use modcli::context::Context;
+use modcli::derive::{ModCommand, Run};
+use modcli::error::ModCliError;
+use modcli::exit::ExitCode;
+use modcli::help::HelpRenderer;
+use modcli::loader::CommandRegistry;
+use modcli::output::handle::Output;
+use std::path::PathBuf;
+
+/// Deploy a build
+///
+/// Uploads the build and switches traffic over.
+#[derive(ModCommand)]
+#[command(aliases("dp"), category = "Deployment", caps("deploy"))]
+struct DeployCommand {
+    /// Environment to deploy to
+    #[arg(positional, choices("staging", "prod"))]
+    target: String,
+    #[arg(short = 'p', default = "8080", help = "Port to expose")]
+    port: u16,
+    #[arg(short = 'v', count)]
+    verbose: u8,
+    dry_run: bool,
+    manifest: Option<PathBuf>,
+    #[arg(long = "tag", value_name = "TAG")]
+    tags: Vec<String>,
+}
+
+impl Run for DeployCommand {
+    fn run(self, ctx: &Context) -> Result<ExitCode, ModCliError> {
+        ctx.out().line(&format!(
+            "{} port={} v={} dry={} manifest={:?} tags={:?}",
+            self.target, self.port, self.verbose, self.dry_run, self.manifest, self.tags
+        ));
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+#[derive(ModCommand)]
+#[command(name = "cache:clear", help = "Clear caches", hidden)]
+struct ClearCache;
+
+impl Run for ClearCache {
+    fn run(self, ctx: &Context) -> Result<ExitCode, ModCliError> {
+        ctx.out().line("cleared");
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+/// Serve files
+#[derive(ModCommand)]
+struct HTTPServerCommand {
+    #[arg(positional)]
+    max_bytes: u64,
+}
+
+impl Run for HTTPServerCommand {
+    fn run(self, ctx: &Context) -> Result<ExitCode, ModCliError> {
+        ctx.out().line(&format!("max={}", self.max_bytes));
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+fn registry() -> CommandRegistry {
+    let mut reg = CommandRegistry::new();
+    reg.set_output(Output::buffer());
+    reg.grant_cap("deploy");
+    reg.register(DeployCommand::command());
+    reg.register(ClearCache::command());
+    reg.register(HTTPServerCommand::command());
+    reg
+}
+
+fn argv(items: &[&str]) -> Vec<String> {
+    items.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn metadata_comes_from_attributes_and_docs() {
+    let reg = registry();
+    let deploy = reg.get("deploy").unwrap();
+    assert_eq!(deploy.aliases(), &["dp"]);
+    assert_eq!(deploy.help(), Some("Deploy a build"));
+    assert_eq!(
+        deploy.long_help(),
+        Some("Uploads the build and switches traffic over.")
+    );
+    assert_eq!(deploy.category(), Some("Deployment"));
+    assert_eq!(deploy.required_caps(), &["deploy"]);
+    let clear = reg.get("cache:clear").unwrap();
+    assert!(clear.hidden());
+    assert_eq!(clear.help(), Some("Clear caches"));
+}
+
+#[test]
+fn fields_are_parsed_into_the_struct() {
+    let reg = registry();
+    reg.try_run(
+        "dp",
+        &argv(&["prod", "-vv", "--dry-run", "--tag", "a", "--tag=b"]),
+    )
+    .unwrap();
+    reg.try_run(
+        "deploy",
+        &argv(&["staging", "-p", "9000", "--manifest", "app.toml"]),
+    )
+    .unwrap();
+    reg.try_run("cache", &argv(&["clear"])).unwrap();
+    assert_eq!(
+        reg.output().contents(),
+        "prod port=8080 v=2 dry=true manifest=None tags=[\"a\", \"b\"]\n\
+         staging port=9000 v=0 dry=false manifest=Some(\"app.toml\") tags=[]\n\
+         cleared\n"
+    );
+}
+
+#[test]
+fn spec_rejects_bad_arguments() {
+    let reg = registry();
+    for args in [
+        argv(&[]),
+        argv(&["dev"]),
+        argv(&["prod", "--port", "http"]),
+        argv(&["prod", "--force"]),
+    ] {
+        assert!(matches!(
+            reg.try_run("deploy", &args),
+            Err(ModCliError::InvalidUsage(_))
+        ));
+    }
+}
+
+#[test]
+fn derived_spec_drives_help() {
+    let reg = registry();
+    let page = HelpRenderer::new()
+        .width(100)
+        .color(false)
+        .command(&reg, "deploy")
+        .unwrap();
+    assert!(page.contains("Environment to deploy to"));
+    assert!(page.contains("Port to expose [default: 8080]"));
+    assert!(page.contains("--tag <TAG>"));
+}
+
+#[test]
+fn acronyms_stay_one_word_and_wide_integers_parse() {
+    let reg = registry();
+    reg.try_run("http-server", &argv(&["18446744073709551615"]))
+        .unwrap();
+    assert_eq!(reg.output().contents(), "max=18446744073709551615\n");
+    assert!(matches!(
+        reg.try_run("http-server", &argv(&["many"])),
+        Err(ModCliError::InvalidUsage(_))
+    ));
+}
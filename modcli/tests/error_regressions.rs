@@ -7,7 +7,7 @@ fn registry_try_execute_unknown_returns_error() {
     let reg = CommandRegistry::new();
     let err = reg.try_execute("does-not-exist", &[]).unwrap_err();
     match err {
-        ModCliError::UnknownCommand(name) => assert_eq!(name, "does-not-exist"),
+        ModCliError::UnknownCommand { name, .. } => assert_eq!(name, "does-not-exist"),
         other => panic!("expected UnknownCommand, got {other:?}"),
     }
 }
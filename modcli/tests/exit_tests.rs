@@ -0,0 +1,123 @@
+use modcli::command::Command;
+use modcli::context::Context;
+use modcli::error::ModCliError;
+use modcli::exit::{ExitCode, ExitCodes};
+use modcli::ModCli;
+
+struct Check;
+
+impl Command for Check {
+    fn name(&self) -> &str {
+        "check"
+    }
+    fn execute(&self, _args: &[String]) {}
+    fn run(&self, ctx: &Context) -> Result<ExitCode, ModCliError> {
+        match ctx.args().raw().first().map(String::as_str) {
+            Some("fail") => Ok(ExitCode(3)),
+            Some("io") => Err(std::io::Error::other("disk").into()),
+            Some("bad") => Err(ModCliError::Validation("bad input".into())),
+            _ => Ok(ExitCode::SUCCESS),
+        }
+    }
+}
+
+struct Plain;
+
+impl Command for Plain {
+    fn name(&self) -> &str {
+        "plain"
+    }
+    fn validate(&self, args: &[String]) -> Result<(), ModCliError> {
+        if args.is_empty() {
+            Ok(())
+        } else {
+            Err(ModCliError::InvalidUsage("no args".into()))
+        }
+    }
+    fn execute(&self, _args: &[String]) {}
+}
+
+fn cli() -> ModCli {
+    let mut cli = ModCli::new();
+    cli.registry.register(Box::new(Check));
+    cli.registry.register(Box::new(Plain));
+    cli
+}
+
+fn argv(items: &[&str]) -> Vec<String> {
+    items.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn try_run_returns_command_exit_code() {
+    let cli = cli();
+    assert_eq!(
+        cli.registry.try_run("check", &[]).unwrap(),
+        ExitCode::SUCCESS
+    );
+    assert_eq!(
+        cli.registry.try_run("check", &argv(&["fail"])).unwrap(),
+        ExitCode(3)
+    );
+    // try_execute keeps its unit result for non-zero exit codes
+    assert!(cli.registry.try_execute("check", &argv(&["fail"])).is_ok());
+    assert_eq!(
+        cli.registry.try_run("plain", &[]).unwrap(),
+        ExitCode::SUCCESS
+    );
+}
+
+#[test]
+fn errors_map_to_sysexits_by_default() {
+    let mut cli = cli();
+    assert_eq!(cli.run_with_status(argv(&["check"])), ExitCode::SUCCESS);
+    assert_eq!(cli.run_with_status(argv(&["check", "fail"])), ExitCode(3));
+    assert_eq!(cli.run_with_status(argv(&["check", "io"])), ExitCode::IOERR);
+    assert_eq!(
+        cli.run_with_status(argv(&["check", "bad"])),
+        ExitCode::DATAERR
+    );
+    assert_eq!(cli.run_with_status(argv(&["plain", "x"])), ExitCode::USAGE);
+    assert_eq!(cli.run_with_status(argv(&["nope"])), ExitCode::NOT_FOUND);
+}
+
+#[test]
+fn exit_code_table_is_configurable() {
+    let mut cli = cli();
+    cli.set_exit_codes(ExitCodes {
+        usage: 2,
+        unknown_command: 2,
+        ..ExitCodes::default()
+    });
+    assert_eq!(cli.run_with_status(argv(&["plain", "x"])), ExitCode(2));
+    assert_eq!(cli.run_with_status(argv(&["nope"])), ExitCode(2));
+    assert_eq!(
+        cli.exit_codes().code_for(&ModCliError::Other("x".into())),
+        ExitCode::FAILURE
+    );
+}
+
+struct Purge;
+
+impl Command for Purge {
+    fn name(&self) -> &str {
+        "purge"
+    }
+    fn required_caps(&self) -> &[&str] {
+        &["admin"]
+    }
+    fn execute(&self, _args: &[String]) {}
+}
+
+#[test]
+fn unauthorized_commands_exit_noperm() {
+    let mut cli = cli();
+    cli.registry.register(Box::new(Purge));
+    assert!(matches!(
+        cli.registry.try_run("purge", &[]),
+        Err(ModCliError::Unauthorized(msg)) if msg.contains("admin")
+    ));
+    assert_eq!(cli.run_with_status(argv(&["purge"])), ExitCode::NOPERM);
+    cli.registry.grant_cap("admin");
+    assert_eq!(cli.run_with_status(argv(&["purge"])), ExitCode::SUCCESS);
+}
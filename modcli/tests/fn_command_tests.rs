@@ -0,0 +1,108 @@
+use modcli::error::ModCliError;
+use modcli::exit::ExitCode;
+use modcli::loader::CommandRegistry;
+use modcli::output::handle::Output;
+use modcli::spec::{ArgSpec, CommandSpec};
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::Arc;
+
+fn registry() -> CommandRegistry {
+    let mut reg = CommandRegistry::new();
+    reg.set_output(Output::buffer());
+    reg
+}
+
+#[test]
+fn closure_command_runs_with_parsed_args() {
+    let mut reg = registry();
+    let calls = Arc::new(AtomicI32::new(0));
+    let seen = calls.clone();
+    reg.command("db:migrate")
+        .alias("up")
+        .help("Run migrations")
+        .category("Database")
+        .arg(ArgSpec::option("steps").default("1"))
+        .arg(ArgSpec::flag("dry-run"))
+        .run(move |ctx| {
+            seen.fetch_add(1, Ordering::SeqCst);
+            ctx.out().line(&format!(
+                "{} steps={} dry={}",
+                ctx.path(),
+                ctx.args().get_str("steps").unwrap_or("-"),
+                ctx.args().flag("dry-run")
+            ));
+            Ok(ExitCode::SUCCESS)
+        });
+
+    reg.try_run("db", &["up".into(), "--steps=3".into()])
+        .unwrap();
+    reg.try_run("db", &["migrate".into(), "--dry-run".into()])
+        .unwrap();
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    assert_eq!(
+        reg.output().contents(),
+        "db:migrate steps=3 dry=false\ndb:migrate steps=1 dry=true\n"
+    );
+    let cmd = reg.get("db:migrate").unwrap();
+    assert_eq!(cmd.help(), Some("Run migrations"));
+    assert_eq!(cmd.category(), Some("Database"));
+    assert!(matches!(
+        reg.try_run("db", &["up".into(), "--force".into()]),
+        Err(ModCliError::InvalidUsage(_))
+    ));
+}
+
+#[test]
+fn closure_results_and_caps_are_honored() {
+    let mut reg = registry();
+    reg.command("purge")
+        .caps(&["admin"])
+        .hidden()
+        .spec(CommandSpec::new().arg(ArgSpec::positional("what").required()))
+        .run(|_| Ok(ExitCode::from(3)));
+    reg.command("fail")
+        .run(|_| Err(ModCliError::Validation("nope".into())));
+
+    assert!(!reg.is_visible(reg.get("purge").unwrap()));
+    assert!(reg.try_run("purge", &["all".into()]).is_err());
+    reg.grant_cap("admin");
+    assert_eq!(
+        reg.try_run("purge", &["all".into()]).unwrap(),
+        ExitCode::from(3)
+    );
+    assert!(matches!(
+        reg.try_run("fail", &[]),
+        Err(ModCliError::Validation(_))
+    ));
+}
+
+#[cfg(all(feature = "async", feature = "tokio-runtime"))]
+#[tokio::test]
+async fn async_closure_command_runs() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let mut reg = registry();
+    let total = Arc::new(AtomicUsize::new(0));
+    let sum = total.clone();
+    reg.command("net:sum")
+        .alias("add")
+        .arg(ArgSpec::positional("a").required())
+        .arg(ArgSpec::positional("b").required())
+        .run_async(move |ctx| {
+            let sum = sum.clone();
+            Box::pin(async move {
+                tokio::task::yield_now().await;
+                let a: usize = ctx.args().get("a").unwrap_or_default();
+                let b: usize = ctx.args().get("b").unwrap_or_default();
+                sum.fetch_add(a + b, Ordering::SeqCst);
+                Ok(ExitCode(3))
+            })
+        });
+    let code = reg
+        .try_run_async("net", &["add".into(), "2".into(), "3".into()])
+        .await
+        .unwrap();
+    assert_eq!(code, ExitCode(3));
+    assert_eq!(total.load(Ordering::SeqCst), 5);
+}
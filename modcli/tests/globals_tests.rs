@@ -0,0 +1,192 @@
+use modcli::command::Command;
+use modcli::context::Context;
+use modcli::error::ModCliError;
+use modcli::exit::ExitCode;
+use modcli::globals::{self, GlobalOptions};
+use modcli::output::color_policy::{self, ColorChoice};
+use modcli::spec::{ArgSpec, CommandSpec};
+use modcli::ModCli;
+use std::sync::{Arc, Mutex};
+
+fn argv(items: &[&str]) -> Vec<String> {
+    items.iter().map(|s| s.to_string()).collect()
+}
+
+type Seen = Arc<Mutex<Vec<String>>>;
+
+// The color choice is process-wide; tests that set it hold this.
+static COLOR: Mutex<()> = Mutex::new(());
+
+/// Records its own arguments and the globals it was given.
+struct Deploy {
+    seen: Seen,
+}
+
+impl Command for Deploy {
+    fn name(&self) -> &str {
+        "deploy"
+    }
+    fn execute(&self, _args: &[String]) {}
+    fn spec(&self) -> Option<CommandSpec> {
+        Some(CommandSpec::new().arg(ArgSpec::positional("target").required()))
+    }
+    fn run(&self, ctx: &Context) -> Result<ExitCode, ModCliError> {
+        let g = ctx.globals();
+        self.seen.lock().unwrap().push(format!(
+            "{} v={} color={} profile={}",
+            ctx.args().get_str("target").unwrap_or("-"),
+            g.count("verbose"),
+            g.get_str("color").unwrap_or("-"),
+            g.get_str("profile").unwrap_or("-"),
+        ));
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+fn cli() -> (ModCli, Seen) {
+    let seen = Seen::default();
+    let mut cli = ModCli::with_standard_globals();
+    cli.registry
+        .register(Box::new(Deploy { seen: seen.clone() }));
+    cli.registry
+        .add_global(ArgSpec::option("profile").short('p').help("Profile"));
+    (cli, seen)
+}
+
+#[test]
+fn globals_are_accepted_before_and_after_the_command() {
+    let _color = COLOR.lock().unwrap_or_else(|e| e.into_inner());
+    let (mut cli, seen) = cli();
+    assert_eq!(
+        cli.run_with_status(argv(&["-v", "--color", "never", "deploy", "prod"])),
+        ExitCode::SUCCESS
+    );
+    assert_eq!(
+        cli.run_with_status(argv(&["deploy", "-vv", "prod", "--profile=ci"])),
+        ExitCode::SUCCESS
+    );
+    assert_eq!(
+        *seen.lock().unwrap(),
+        vec![
+            "prod v=1 color=never profile=-",
+            "prod v=2 color=- profile=ci"
+        ]
+    );
+}
+
+#[test]
+fn separator_and_unknown_clusters_are_left_for_the_command() {
+    let mut args = argv(&["deploy", "-vx", "--", "-v"]);
+    let parsed = GlobalOptions::standard().extract(&mut args).unwrap();
+    assert_eq!(args, argv(&["deploy", "-vx", "--", "-v"]));
+    assert_eq!(parsed.count("verbose"), 0);
+
+    let mut args = argv(&["run", "-1", "-q"]);
+    let parsed = GlobalOptions::standard().extract(&mut args).unwrap();
+    assert_eq!(args, argv(&["run", "-1"]));
+    assert!(parsed.flag("quiet"));
+}
+
+#[test]
+fn invalid_global_values_are_usage_errors() {
+    let (mut cli, seen) = cli();
+    assert_eq!(
+        cli.run_with_status(argv(&["deploy", "prod", "--color", "sometimes"])),
+        ExitCode::USAGE
+    );
+    assert_eq!(
+        cli.run_with_status(argv(&["deploy", "prod", "--profile"])),
+        ExitCode::USAGE
+    );
+    assert!(seen.lock().unwrap().is_empty());
+}
+
+#[test]
+fn removed_globals_reach_the_command() {
+    let (mut cli, _) = cli();
+    let mut options = cli.registry.global_options().clone();
+    options.remove("verbose");
+    cli.registry.set_global_options(options);
+    // `-v` is now an unknown option for `deploy`
+    assert_eq!(
+        cli.run_with_status(argv(&["deploy", "prod", "-v"])),
+        ExitCode::USAGE
+    );
+}
+
+#[test]
+fn quiet_sets_negative_verbosity() {
+    let parsed = GlobalOptions::standard()
+        .extract(&mut argv(&["-q", "-vvv"]))
+        .unwrap();
+    globals::apply(&parsed).unwrap();
+    assert!(globals::is_quiet());
+    globals::set_verbosity(0);
+    assert_eq!(globals::verbosity(), 0);
+}
+
+#[test]
+fn color_choice_set_in_code_survives_a_run() {
+    let _color = COLOR.lock().unwrap_or_else(|e| e.into_inner());
+    let (mut cli, _) = cli();
+    color_policy::set_choice(ColorChoice::Never);
+    assert_eq!(
+        cli.run_with_status(argv(&["deploy", "prod"])),
+        ExitCode::SUCCESS
+    );
+    assert_eq!(color_policy::choice(), ColorChoice::Never);
+    // `--color auto` is applied when given
+    let parsed = GlobalOptions::standard()
+        .extract(&mut argv(&["--color", "auto"]))
+        .unwrap();
+    globals::apply(&parsed).unwrap();
+    assert_eq!(color_policy::choice(), ColorChoice::Auto);
+}
+
+/// Declares `-v` and `--output` itself, like grep's invert and a report file.
+struct Grep {
+    seen: Seen,
+}
+
+impl Command for Grep {
+    fn name(&self) -> &str {
+        "grep"
+    }
+    fn execute(&self, _args: &[String]) {}
+    fn spec(&self) -> Option<CommandSpec> {
+        Some(
+            CommandSpec::new()
+                .arg(ArgSpec::flag("invert").short('v'))
+                .arg(ArgSpec::option("output"))
+                .arg(ArgSpec::positional("pattern").required()),
+        )
+    }
+    fn run(&self, ctx: &Context) -> Result<ExitCode, ModCliError> {
+        self.seen.lock().unwrap().push(format!(
+            "{} invert={} output={} verbose={} quiet={}",
+            ctx.args().get_str("pattern").unwrap_or("-"),
+            ctx.args().flag("invert"),
+            ctx.args().get_str("output").unwrap_or("-"),
+            ctx.globals().count("verbose"),
+            ctx.globals().flag("quiet"),
+        ));
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+#[test]
+fn options_the_command_declares_stay_with_it() {
+    let (mut cli, seen) = cli();
+    cli.registry.register(Box::new(Grep { seen: seen.clone() }));
+    assert_eq!(
+        cli.run_with_status(argv(&[
+            "-v", "grep", "-v", "todo", "--output", "hits.txt", "-q"
+        ])),
+        ExitCode::SUCCESS
+    );
+    assert_eq!(
+        *seen.lock().unwrap(),
+        vec!["todo invert=true output=hits.txt verbose=1 quiet=true"]
+    );
+    globals::set_verbosity(0);
+}
@@ -0,0 +1,126 @@
+use modcli::command::Command;
+use modcli::context::Context;
+use modcli::error::ModCliError;
+use modcli::exit::ExitCode;
+use modcli::output::handle::Output;
+use modcli::spec::{ArgSpec, CommandSpec};
+use modcli::ModCli;
+
+fn argv(items: &[&str]) -> Vec<String> {
+    items.iter().map(|s| s.to_string()).collect()
+}
+
+/// Writes its raw arguments; `-h` is `--host` when `claims_h` is set.
+struct Deploy {
+    name: &'static str,
+    claims_h: bool,
+    flags: &'static [&'static str],
+}
+
+impl Command for Deploy {
+    fn name(&self) -> &str {
+        self.name
+    }
+    fn help(&self) -> Option<&str> {
+        Some("Deploy a release")
+    }
+    fn help_flags(&self) -> &[&str] {
+        self.flags
+    }
+    fn spec(&self) -> Option<CommandSpec> {
+        let spec = CommandSpec::new().arg(ArgSpec::positional("target").required());
+        Some(if self.claims_h {
+            spec.arg(ArgSpec::option("host").short('h'))
+        } else {
+            spec
+        })
+    }
+    fn execute(&self, _args: &[String]) {}
+    fn run(&self, ctx: &Context) -> Result<ExitCode, ModCliError> {
+        ctx.out().line(&ctx.args().raw().join(" "));
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+fn cli() -> ModCli {
+    let mut cli = ModCli::with_standard_globals();
+    cli.registry.set_output(Output::buffer());
+    cli.registry.register(Box::new(Deploy {
+        name: "deploy",
+        claims_h: false,
+        flags: &["--help", "-h"],
+    }));
+    cli.registry.register(Box::new(Deploy {
+        name: "cloud:push",
+        claims_h: true,
+        flags: &["--help", "-h"],
+    }));
+    cli.registry.register(Box::new(Deploy {
+        name: "raw",
+        claims_h: false,
+        flags: &[],
+    }));
+    cli
+}
+
+#[test]
+fn command_help_flag_prints_help_instead_of_running() {
+    let mut cli = cli();
+    // `target` is required, so running would be a usage error
+    assert_eq!(
+        cli.run_with_status(argv(&["deploy", "--help"])),
+        ExitCode::SUCCESS
+    );
+    assert_eq!(
+        cli.run_with_status(argv(&["deploy", "-h"])),
+        ExitCode::SUCCESS
+    );
+    assert_eq!(
+        cli.run_with_status(argv(&["cloud", "--help"])),
+        ExitCode::SUCCESS
+    );
+    assert_eq!(cli.registry.output().contents(), "");
+}
+
+#[test]
+fn flags_after_separator_or_claimed_by_spec_are_arguments() {
+    let mut cli = cli();
+    assert_eq!(
+        cli.run_with_status(argv(&["deploy", "--", "--help"])),
+        ExitCode::SUCCESS
+    );
+    assert_eq!(
+        cli.run_with_status(argv(&["cloud", "push", "prod", "-h", "example.org"])),
+        ExitCode::SUCCESS
+    );
+    assert_eq!(
+        cli.registry.output().contents(),
+        "-- --help\nprod -h example.org\n"
+    );
+}
+
+#[test]
+fn commands_can_opt_out_of_help_flags() {
+    let mut cli = cli();
+    // `raw` has no help flags, so `--help` reaches its spec and is rejected
+    assert_eq!(
+        cli.run_with_status(argv(&["raw", "prod", "--help"])),
+        ExitCode::USAGE
+    );
+}
+
+#[test]
+fn version_flag_prints_app_version() {
+    let mut cli = cli();
+    // Without `set_app`, `--version` is dispatched like any other word
+    assert_eq!(
+        cli.run_with_status(argv(&["--version"])),
+        ExitCode::NOT_FOUND
+    );
+
+    cli.set_app("tool", "1.2.3");
+    assert_eq!(cli.run_with_status(argv(&["--version"])), ExitCode::SUCCESS);
+    assert_eq!(cli.run_with_status(argv(&["-q", "-V"])), ExitCode::SUCCESS);
+    assert_eq!(cli.registry.output().contents(), "tool 1.2.3\ntool 1.2.3\n");
+    assert_eq!(cli.app_version(), Some("1.2.3"));
+}
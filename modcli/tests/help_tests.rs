@@ -0,0 +1,266 @@
+use modcli::command::Command;
+use modcli::globals::GlobalOptions;
+use modcli::help::HelpRenderer;
+use modcli::loader::CommandRegistry;
+use modcli::output::messages;
+use modcli::spec::{ArgSpec, CommandSpec};
+use modcli::tree::{Group, HelpOrder};
+
+struct Deploy;
+
+impl Command for Deploy {
+    fn name(&self) -> &str {
+        "release:deploy"
+    }
+    fn aliases(&self) -> &[&str] {
+        &["ship"]
+    }
+    fn help(&self) -> Option<&str> {
+        Some("Deploy a release")
+    }
+    fn long_help(&self) -> Option<&str> {
+        Some("Builds the artifact, uploads it and switches traffic once health checks pass on every node in the target environment.")
+    }
+    fn examples(&self) -> &[(&str, &str)] {
+        &[(
+            "release deploy prod -p 9000",
+            "Deploy to production on port 9000",
+        )]
+    }
+    fn see_also(&self) -> &[&str] {
+        &["release rollback"]
+    }
+    fn spec(&self) -> Option<CommandSpec> {
+        Some(
+            CommandSpec::new()
+                .arg(ArgSpec::positional("target").required().help("Environment"))
+                .arg(
+                    ArgSpec::option("port")
+                        .short('p')
+                        .default("8080")
+                        .help("Port"),
+                )
+                .arg(
+                    ArgSpec::option("strategy")
+                        .choices(&["rolling", "blue-green"])
+                        .help("Rollout strategy"),
+                ),
+        )
+    }
+    fn execute(&self, _args: &[String]) {}
+}
+
+struct Named(&'static str, &'static str);
+
+impl Command for Named {
+    fn name(&self) -> &str {
+        self.0
+    }
+    fn help(&self) -> Option<&str> {
+        Some(self.1)
+    }
+    fn execute(&self, _args: &[String]) {}
+}
+
+fn registry() -> CommandRegistry {
+    let mut reg = CommandRegistry::new();
+    reg.set_global_options(GlobalOptions::standard());
+    reg.register(Box::new(Deploy));
+    reg.register(Box::new(Named("status", "Show status")));
+    reg.register(Box::new(Named(
+        "synchronize-everything-remotely",
+        "Sync all remotes",
+    )));
+    reg.register_group(Group::new("release").help("Release management"));
+    reg
+}
+
+fn renderer() -> HelpRenderer {
+    HelpRenderer::new().width(60).color(false)
+}
+
+#[test]
+fn command_page_has_all_sections() {
+    let page = renderer()
+        .width(70)
+        .command(&registry(), "release:deploy")
+        .unwrap();
+    assert_eq!(
+        page,
+        "\
+release deploy
+Deploy a release
+
+USAGE:
+  release deploy <target> [--port <PORT>] [--strategy <STRATEGY>]
+
+Builds the artifact, uploads it and switches traffic once health
+checks pass on every node in the target environment.
+
+ARGS:
+  <target>  Environment
+
+OPTIONS:
+  -p, --port <PORT>      Port [default: 8080]
+  --strategy <STRATEGY>  Rollout strategy [possible values: rolling,
+                         blue-green]
+
+EXAMPLES:
+  release deploy prod -p 9000
+      Deploy to production on port 9000
+
+ALIASES:
+  release ship
+
+SEE ALSO:
+  release rollback
+"
+    );
+}
+
+#[test]
+fn listing_aligns_to_the_longest_name_and_lists_globals() {
+    let text = renderer().listing(&registry(), "");
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines[0], "Help:");
+    let column = |prefix: &str| {
+        let line = lines.iter().find(|l| l.starts_with(prefix)).unwrap();
+        line.len() - line[prefix.len()..].trim_start().len()
+    };
+    // Descriptions share one column; over-long names wrap below it
+    assert_eq!(column("  release ..."), column("  status"));
+    let long = lines
+        .iter()
+        .position(|l| *l == "  synchronize-everything-remotely")
+        .unwrap();
+    assert_eq!(lines[long + 1].trim(), "Sync all remotes");
+    assert_eq!(
+        lines[long + 1].len() - "Sync all remotes".len(),
+        column("  status")
+    );
+    assert!(text.contains("\nGLOBAL OPTIONS:\n  -v, --verbose"));
+}
+
+#[test]
+fn section_titles_come_from_messages() {
+    struct Bare;
+    impl Command for Bare {
+        fn name(&self) -> &str {
+            "bare"
+        }
+        fn usage(&self) -> Option<&str> {
+            Some("bare [FILE]")
+        }
+        fn execute(&self, _args: &[String]) {}
+    }
+    let mut reg = CommandRegistry::new();
+    reg.register(Box::new(Bare));
+    // Only this test touches this key (messages are process-wide)
+    messages::set_message("help.no_description", "Keine Beschreibung");
+    let page = renderer().command(&reg, "bare").unwrap();
+    messages::reset_message("help.no_description");
+    assert_eq!(page, "bare\nKeine Beschreibung\n\nUSAGE:\n  bare [FILE]\n");
+}
+
+#[test]
+fn group_listing_shows_group_help_and_children() {
+    let text = renderer().listing(&registry(), "release");
+    assert_eq!(
+        text,
+        "Help (release):\nRelease management\n  deploy, ship  Deploy a release\n"
+    );
+    assert!(renderer().command(&registry(), "release").is_none());
+}
+
+struct Categorized(&'static str, &'static str);
+
+impl Command for Categorized {
+    fn name(&self) -> &str {
+        self.0
+    }
+    fn help(&self) -> Option<&str> {
+        Some("-")
+    }
+    fn category(&self) -> Option<&str> {
+        Some(self.1)
+    }
+    fn execute(&self, _args: &[String]) {}
+}
+
+fn categorized(order: HelpOrder) -> String {
+    let mut reg = CommandRegistry::new();
+    reg.set_global_options(Default::default());
+    reg.set_help_order(order);
+    reg.register(Box::new(Categorized("stop", "Runtime")));
+    reg.register(Box::new(Named("zz-plain", "-")));
+    reg.register(Box::new(Categorized("build", "Build")));
+    reg.register(Box::new(Categorized("start", "Runtime")));
+    reg.register(Box::new(Categorized("db:migrate", "-")));
+    reg.register_group(Group::new("db").category("Build").aliases(&["data"]));
+    renderer().listing(&reg, "")
+}
+
+fn names(listing: &str) -> Vec<&str> {
+    listing
+        .lines()
+        .skip(1)
+        .filter(|l| !l.is_empty())
+        .map(|l| l.split("  ").find(|p| !p.is_empty()).unwrap_or(""))
+        .filter(|n| !["hello", "help, --help, -h", "ping", "shell"].contains(n))
+        .collect()
+}
+
+#[test]
+fn listing_groups_commands_by_category() {
+    assert_eq!(
+        names(&categorized(HelpOrder::Alphabetical)),
+        vec![
+            "zz-plain",
+            "Build:",
+            "build",
+            "db, data ...",
+            "Runtime:",
+            "start",
+            "stop"
+        ]
+    );
+    assert_eq!(
+        names(&categorized(HelpOrder::Registration)),
+        vec![
+            "zz-plain",
+            "Runtime:",
+            "stop",
+            "start",
+            "Build:",
+            "build",
+            "db, data ..."
+        ]
+    );
+}
+
+#[test]
+fn all_iterates_in_registration_order() {
+    let mut reg = CommandRegistry::new();
+    for name in ["zeta", "alpha", "mid"] {
+        reg.register(Box::new(Named(name, "-")));
+    }
+    reg.register(Box::new(Named("alpha", "again")));
+    let order: Vec<&str> = reg
+        .all()
+        .map(|c| c.name())
+        .filter(|n| ["zeta", "alpha", "mid"].contains(n))
+        .collect();
+    assert_eq!(order, vec!["zeta", "alpha", "mid"]);
+    assert_eq!(reg.get("alpha").unwrap().help(), Some("again"));
+}
+
+#[test]
+fn summary_is_the_first_plain_line() {
+    use modcli::help::summary;
+    assert_eq!(
+        summary(Some("\n  ## Deploy a `release`\nMore text")).as_deref(),
+        Some("Deploy a release")
+    );
+    assert_eq!(summary(Some(" \n")), None);
+    assert_eq!(summary(None), None);
+}
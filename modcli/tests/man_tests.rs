@@ -0,0 +1,123 @@
+use modcli::command::Command;
+use modcli::loader::CommandRegistry;
+use modcli::man::ManOptions;
+use modcli::output::markdown::{render_roff, roff_escape};
+use modcli::spec::{ArgSpec, CommandSpec};
+
+struct Deploy;
+
+impl Command for Deploy {
+    fn name(&self) -> &str {
+        "deploy"
+    }
+    fn aliases(&self) -> &[&str] {
+        &["d"]
+    }
+    fn help(&self) -> Option<&str> {
+        Some("Deploy the **app**\n\n- rolls out `all` nodes")
+    }
+    fn spec(&self) -> Option<CommandSpec> {
+        Some(
+            CommandSpec::new()
+                .arg(ArgSpec::positional("target").required())
+                .arg(
+                    ArgSpec::option("port")
+                        .short('p')
+                        .help("Port to bind")
+                        .default("8080"),
+                ),
+        )
+    }
+    fn execute(&self, _args: &[String]) {}
+}
+
+struct NodeDrain;
+
+impl Command for NodeDrain {
+    fn name(&self) -> &str {
+        "node:drain"
+    }
+    fn help(&self) -> Option<&str> {
+        Some("Drain a node")
+    }
+    fn execute(&self, _args: &[String]) {}
+}
+
+struct Secret;
+
+impl Command for Secret {
+    fn name(&self) -> &str {
+        "secret"
+    }
+    fn hidden(&self) -> bool {
+        true
+    }
+    fn execute(&self, _args: &[String]) {}
+}
+
+fn registry() -> CommandRegistry {
+    let mut reg = CommandRegistry::new();
+    reg.register(Box::new(NodeDrain));
+    reg.register(Box::new(Deploy));
+    reg.register(Box::new(Secret));
+    reg
+}
+
+fn opts() -> ManOptions {
+    ManOptions::new("tool")
+        .version("1.2.0")
+        .date("2025-01-01")
+        .about("Deployment tool")
+}
+
+#[test]
+fn main_page_lists_visible_commands() {
+    let page = registry().render_man(&opts());
+    assert!(page.starts_with(".TH \"TOOL\" \"1\" \"2025-01-01\" \"tool 1.2.0\""));
+    assert!(page.contains("tool \\- Deployment tool"));
+    assert!(page.contains(".SH COMMANDS"));
+    assert!(page.contains("\\fBdeploy\\fR"));
+    assert!(page.contains("Deploy the \\fBapp\\fR"));
+    assert!(page.contains(".SS \"node\""));
+    assert!(page.contains("\\fBnode drain\\fR"));
+    assert!(page.contains("\\fB\\-p\\fR, \\fB\\-\\-port\\fR"));
+    assert!(page.contains("Alias for \\fBdeploy\\fR."));
+    assert!(page.contains("\\fBtool\\-node\\-drain\\fR(1)"));
+    assert!(!page.contains("secret"));
+    // Top-level commands come before namespaced ones
+    assert!(page.find("\\fBdeploy\\fR").unwrap() < page.find(".SS").unwrap());
+}
+
+#[test]
+fn per_command_pages() {
+    let reg = registry();
+    let page = reg.render_man_command("deploy", &opts()).unwrap();
+    assert!(page.starts_with(".TH \"TOOL\\-DEPLOY\""));
+    assert!(page.contains("tool\\-deploy \\- Deploy the app"));
+    assert!(page.contains(".SH OPTIONS"));
+    assert!(page.contains("[default: 8080]"));
+    assert!(page.contains(".SH ALIASES\n\\fBd\\fR"));
+    assert!(reg.render_man_command("secret", &opts()).is_none());
+    assert!(reg.render_man_command("missing", &opts()).is_none());
+}
+
+#[test]
+fn markdown_reference() {
+    let md = registry().render_markdown_reference();
+    assert!(md.starts_with("# Command Reference"));
+    assert!(md.contains("## `deploy`"));
+    assert!(md.contains("## `node drain`"));
+    assert!(md.contains("| `-p`, `--port` | Port to bind [default: `8080`] |"));
+    assert!(md.contains("| `<target>` | (required) |"));
+    assert!(md.contains("**Aliases:** `d`"));
+    assert!(!md.contains("secret"));
+}
+
+#[test]
+fn roff_escaping() {
+    assert_eq!(roff_escape(".hidden"), "\\&.hidden");
+    assert_eq!(roff_escape("a-b\\c"), "a\\-b\\ec");
+    let roff = render_roff("# Title\n\n- item *one*");
+    assert!(roff.contains("\\fBTitle\\fR"));
+    assert!(roff.contains("\\(bu item \\fIone\\fR"));
+}
@@ -0,0 +1,162 @@
+use modcli::command::Command;
+use modcli::context::Context;
+use modcli::error::ModCliError;
+use modcli::exit::ExitCode;
+use modcli::loader::CommandRegistry;
+use modcli::middleware::{self, Call, CallResult, Middleware};
+use std::cell::RefCell;
+use std::sync::{Arc, Mutex};
+
+thread_local! {
+    static RAN: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+fn ran() -> Vec<String> {
+    RAN.with(|r| r.borrow_mut().drain(..).collect())
+}
+
+struct Echo;
+
+impl Command for Echo {
+    fn name(&self) -> &str {
+        "ops:echo"
+    }
+    fn execute(&self, _args: &[String]) {}
+    fn run(&self, ctx: &Context) -> Result<ExitCode, ModCliError> {
+        RAN.with(|r| r.borrow_mut().push(ctx.args().raw().join(" ")));
+        Ok(ExitCode(ctx.args().raw().len() as u8))
+    }
+}
+
+fn registry() -> CommandRegistry {
+    let mut reg = CommandRegistry::new();
+    reg.register(Box::new(Echo));
+    reg
+}
+
+fn s(items: &[&str]) -> Vec<String> {
+    items.iter().map(|s| s.to_string()).collect()
+}
+
+/// Records `before`/`after` order into a shared log.
+struct Trace {
+    name: &'static str,
+    log: Arc<Mutex<Vec<String>>>,
+}
+
+impl Middleware for Trace {
+    fn before(&self, _call: &mut Call) -> Option<CallResult> {
+        self.log
+            .lock()
+            .unwrap()
+            .push(format!("{}:before", self.name));
+        None
+    }
+    fn after(&self, call: &Call, result: &mut CallResult) {
+        self.log.lock().unwrap().push(format!(
+            "{}:after {} {:?}",
+            self.name,
+            call.path().unwrap_or("-"),
+            result.as_ref().ok()
+        ));
+    }
+}
+
+#[test]
+fn layers_run_as_an_onion_and_see_the_resolved_path() {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let mut reg = registry();
+    reg.use_middleware(Trace {
+        name: "outer",
+        log: log.clone(),
+    });
+    reg.use_middleware(Trace {
+        name: "inner",
+        log: log.clone(),
+    });
+    let code = reg.try_run("ops", &s(&["echo", "a"])).unwrap();
+    assert_eq!(code, ExitCode(1));
+    assert_eq!(
+        *log.lock().unwrap(),
+        vec![
+            "outer:before",
+            "inner:before",
+            "inner:after ops:echo Some(ExitCode(1))",
+            "outer:after ops:echo Some(ExitCode(1))",
+        ]
+    );
+}
+
+#[test]
+fn before_can_rewrite_args() {
+    let mut reg = registry();
+    reg.use_middleware(middleware::before(|call| {
+        call.args.retain(|a| a != "--debug");
+        call.args.push("injected".into());
+        None
+    }));
+    reg.try_run("ops", &s(&["echo", "--debug", "x"])).unwrap();
+    assert_eq!(ran(), vec!["x injected"]);
+}
+
+#[test]
+fn short_circuit_skips_command_and_inner_layers() {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let mut reg = registry();
+    reg.use_middleware(Trace {
+        name: "audit",
+        log: log.clone(),
+    });
+    reg.use_middleware(middleware::before(|_| {
+        Some(Err(ModCliError::InvalidUsage("denied".into())))
+    }));
+    reg.use_middleware(Trace {
+        name: "never",
+        log: log.clone(),
+    });
+    let err = reg.try_run("ops:echo", &[]).unwrap_err();
+    assert!(err.to_string().contains("denied"));
+    assert!(ran().is_empty());
+    assert_eq!(
+        *log.lock().unwrap(),
+        vec!["audit:before", "audit:after - None"]
+    );
+}
+
+#[test]
+fn after_sees_and_can_replace_the_real_error() {
+    let seen = Arc::new(Mutex::new(None));
+    let mut reg = registry();
+    let sink = seen.clone();
+    reg.use_middleware(middleware::after(move |_, result| {
+        if let Err(ModCliError::UnknownCommand { name, .. }) = result {
+            *sink.lock().unwrap() = Some(name.clone());
+            *result = Ok(ExitCode(42));
+        }
+    }));
+    assert_eq!(reg.try_run("ops:ech", &[]).unwrap(), ExitCode(42));
+    assert_eq!(seen.lock().unwrap().as_deref(), Some("ops:ech"));
+}
+
+#[test]
+fn legacy_hooks_still_run_inside_the_stack() {
+    let hooks = Arc::new(Mutex::new(Vec::new()));
+    let mut reg = registry();
+    let pre = hooks.clone();
+    reg.set_pre_hook(move |cmd, _| pre.lock().unwrap().push(format!("pre {cmd}")));
+    let post = hooks.clone();
+    reg.set_post_hook(move |cmd, _, res| {
+        post.lock()
+            .unwrap()
+            .push(format!("post {cmd} {}", res.is_ok()))
+    });
+    reg.use_middleware(middleware::before(|call| {
+        call.command = "ops:echo".into();
+        None
+    }));
+    reg.try_run("alias-from-middleware", &[]).unwrap();
+    assert_eq!(
+        *hooks.lock().unwrap(),
+        vec!["pre ops:echo", "post ops:echo true"]
+    );
+}
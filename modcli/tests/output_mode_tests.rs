@@ -0,0 +1,107 @@
+use modcli::exit::ExitCode;
+use modcli::output::mode::{self, record, OutputMode};
+use modcli::ModCli;
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn modes_parse_with_aliases() {
+    assert_eq!("ndjson".parse::<OutputMode>().unwrap(), OutputMode::Ndjson);
+    assert_eq!("jsonl".parse::<OutputMode>().unwrap(), OutputMode::Ndjson);
+    assert_eq!("TEXT".parse::<OutputMode>().unwrap(), OutputMode::Human);
+    assert!("xml".parse::<OutputMode>().is_err());
+    // yaml is only accepted when it can be rendered
+    assert_eq!(
+        "yml".parse::<OutputMode>().is_ok(),
+        cfg!(feature = "output-yaml")
+    );
+}
+
+#[test]
+fn records_are_single_line_json_without_ansi() {
+    assert_eq!(
+        record("error", "\x1b[31mbad \"quote\"\nnext\x1b[0m"),
+        r#"{"level":"error","message":"bad \"quote\"\nnext"}"#
+    );
+    assert_eq!(OutputMode::Json.to_string(), "json");
+    assert!(!OutputMode::Human.is_machine());
+}
+
+#[test]
+fn output_option_lasts_for_one_run() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let modes = seen.clone();
+    let mut cli = ModCli::with_standard_globals();
+    cli.registry.command("show").run(move |_| {
+        modes.lock().unwrap().push(mode::output_mode());
+        Ok(ExitCode::SUCCESS)
+    });
+    let argv = |items: &[&str]| items.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+    assert_eq!(
+        cli.run_with_status(argv(&["--output", "json", "show"])),
+        ExitCode::SUCCESS
+    );
+    assert_eq!(mode::output_mode(), OutputMode::Human);
+    assert_eq!(cli.run_with_status(argv(&["show"])), ExitCode::SUCCESS);
+    assert_eq!(
+        *seen.lock().unwrap(),
+        vec![OutputMode::Json, OutputMode::Human]
+    );
+    if !cfg!(feature = "output-yaml") {
+        assert_eq!(
+            cli.run_with_status(argv(&["show", "--output", "yaml"])),
+            ExitCode::USAGE
+        );
+    }
+}
+
+#[cfg(feature = "structured-output")]
+mod emit {
+    use modcli::output::emit::render;
+    use modcli::output::mode::OutputMode;
+    use serde::Serialize;
+
+    #[derive(Serialize)]
+    struct Node {
+        name: &'static str,
+        ready: bool,
+    }
+
+    fn nodes() -> Vec<Node> {
+        vec![
+            Node {
+                name: "web-1",
+                ready: true,
+            },
+            Node {
+                name: "web-2",
+                ready: false,
+            },
+        ]
+    }
+
+    #[test]
+    fn machine_modes() {
+        let json = render(&nodes(), OutputMode::Json).unwrap();
+        assert!(json.starts_with("[\n"));
+        assert!(json.contains("\"name\": \"web-1\""));
+
+        let nd = render(&nodes(), OutputMode::Ndjson).unwrap();
+        assert_eq!(
+            nd,
+            "{\"name\":\"web-1\",\"ready\":true}\n{\"name\":\"web-2\",\"ready\":false}\n"
+        );
+    }
+
+    #[test]
+    fn human_mode_renders_tables_and_key_values() {
+        let table = render(&nodes(), OutputMode::Human).unwrap();
+        let plain = console::strip_ansi_codes(&table).to_string();
+        assert!(plain.contains("name"));
+        assert!(plain.contains("web-2"));
+        assert!(plain.find("name").unwrap() < plain.find("ready").unwrap());
+
+        let kv = render(&nodes()[0], OutputMode::Human).unwrap();
+        assert_eq!(kv, "name   web-1\nready  true\n");
+    }
+}
@@ -0,0 +1,186 @@
+#![cfg(feature = "tokio-runtime")]
+
+use modcli::command::{AsyncCommand, Command, CommandFuture};
+use modcli::context::Context;
+use modcli::error::ModCliError;
+use modcli::exit::ExitCode;
+use modcli::ModCli;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+type BoxFut<'a> = Pin<Box<dyn Future<Output = Result<(), ModCliError>> + Send + 'a>>;
+
+struct Sleep {
+    name: &'static str,
+    duration: Duration,
+    timeout: Option<Duration>,
+    interrupt: bool,
+}
+
+impl AsyncCommand for Sleep {
+    fn name(&self) -> &str {
+        self.name
+    }
+    fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+    fn execute_async<'a>(&'a self, _args: &'a [String]) -> BoxFut<'a> {
+        Box::pin(async { Ok(()) })
+    }
+    fn run_async<'a>(&'a self, _ctx: &'a Context<'_>) -> CommandFuture<'a> {
+        Box::pin(async move {
+            if self.interrupt {
+                // Give the Ctrl-C watcher time to install its handler
+                tokio::time::sleep(Duration::from_millis(200)).await;
+                let pid = std::process::id().to_string();
+                std::process::Command::new("kill")
+                    .args(["-INT", &pid])
+                    .status()
+                    .map_err(ModCliError::Io)?;
+            }
+            tokio::time::sleep(self.duration).await;
+            Ok(ExitCode::SUCCESS)
+        })
+    }
+}
+
+struct Sync;
+
+impl Command for Sync {
+    fn name(&self) -> &str {
+        "sync"
+    }
+    fn execute(&self, _args: &[String]) {}
+    fn run(&self, ctx: &Context) -> Result<ExitCode, ModCliError> {
+        Ok(ExitCode(ctx.args().raw().len() as u8))
+    }
+}
+
+fn cli() -> ModCli {
+    let mut cli = ModCli::new();
+    cli.registry.register_async(Box::new(Sleep {
+        name: "quick",
+        duration: Duration::from_millis(10),
+        timeout: None,
+        interrupt: false,
+    }));
+    cli.registry.register_async(Box::new(Sleep {
+        name: "slow",
+        duration: Duration::from_secs(30),
+        timeout: Some(Duration::from_millis(50)),
+        interrupt: false,
+    }));
+    cli.registry.register(Box::new(Sync));
+    cli
+}
+
+fn s(items: &[&str]) -> Vec<String> {
+    items.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn run_blocking_starts_a_runtime_for_async_and_sync_commands() {
+    let mut cli = cli();
+    assert_eq!(cli.run_blocking(s(&["quick"])), ExitCode::SUCCESS);
+    assert_eq!(cli.run_blocking(s(&["sync", "a", "b"])), ExitCode(2));
+    assert_eq!(cli.run_blocking(s(&["nope"])), ExitCode::NOT_FOUND);
+}
+
+#[test]
+fn timeout_stops_only_that_command() {
+    let mut cli = cli();
+    let started = Instant::now();
+    assert_eq!(cli.run_blocking(s(&["slow"])), ExitCode::TIMEOUT);
+    assert!(started.elapsed() < Duration::from_secs(5));
+    // The registry-wide token stays usable for the next command
+    assert!(!cli.registry.cancellation().is_cancelled());
+    assert_eq!(cli.run_blocking(s(&["quick"])), ExitCode::SUCCESS);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn run_async_dispatches_sync_commands() {
+    let mut cli = cli();
+    assert_eq!(cli.run_async(s(&["sync", "x"])).await, ExitCode(1));
+    // run_blocking also works from inside a multi-threaded runtime
+    assert_eq!(cli.run_blocking(s(&["quick"])), ExitCode::SUCCESS);
+}
+
+struct Busy;
+
+impl Command for Busy {
+    fn name(&self) -> &str {
+        "busy"
+    }
+    fn execute(&self, _args: &[String]) {
+        std::thread::sleep(Duration::from_millis(300));
+    }
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn sync_commands_leave_the_runtime_free() {
+    let mut cli = cli();
+    cli.registry.register(Box::new(Busy));
+    let ticks = Arc::new(AtomicUsize::new(0));
+    let counter = ticks.clone();
+    let ticker = tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            counter.fetch_add(1, Ordering::SeqCst);
+        }
+    });
+    assert_eq!(cli.run_async(s(&["busy"])).await, ExitCode::SUCCESS);
+    // The ticker kept running on this thread while `busy` slept elsewhere
+    assert!(ticks.load(Ordering::SeqCst) >= 5);
+    ticker.abort();
+    assert_eq!(cli.run_async(s(&["sync", "a"])).await, ExitCode(1));
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn run_blocking_refuses_a_current_thread_runtime() {
+    let mut cli = cli();
+    assert!(!cli.run_blocking(s(&["quick"])).is_success());
+}
+
+#[cfg(unix)]
+#[test]
+fn ctrl_c_interrupts_a_running_async_command() {
+    let mut cli = ModCli::new();
+    let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let results = seen.clone();
+    cli.registry
+        .use_middleware(modcli::middleware::after(move |_call, result| {
+            results.lock().unwrap().push(format!("{result:?}"));
+        }));
+    cli.registry.register_async(Box::new(Sleep {
+        name: "wait",
+        duration: Duration::from_secs(30),
+        timeout: None,
+        interrupt: true,
+    }));
+    let started = Instant::now();
+    assert_eq!(cli.run_blocking(s(&["wait"])), ExitCode::INTERRUPTED);
+    assert!(started.elapsed() < Duration::from_secs(10));
+    assert!(cli.registry.cancellation().is_cancelled());
+    // Middleware saw the interruption, so paired setup/teardown stays balanced
+    assert_eq!(
+        *seen.lock().unwrap(),
+        vec![r#"Err(Interrupted { command: "wait" })"#]
+    );
+}
+
+#[tokio::test]
+async fn cancelled_future_wakes_on_cancel() {
+    let token = modcli::cancel::CancellationToken::new();
+    let remote = token.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        remote.cancel();
+    });
+    tokio::time::timeout(Duration::from_secs(5), token.cancelled())
+        .await
+        .expect("woken by cancel");
+    assert!(token.is_cancelled());
+}
@@ -0,0 +1,56 @@
+use std::sync::{Mutex, OnceLock};
+
+use modcli::command::Command;
+use modcli::loader::CommandRegistry;
+use modcli::shell::editor::LineEditor;
+use modcli::shell::Shell;
+
+static CALLS: OnceLock<Mutex<Vec<Vec<String>>>> = OnceLock::new();
+
+struct Record;
+
+impl Command for Record {
+    fn name(&self) -> &str {
+        "record"
+    }
+    fn execute(&self, args: &[String]) {
+        CALLS
+            .get_or_init(|| Mutex::new(Vec::new()))
+            .lock()
+            .unwrap()
+            .push(args.to_vec());
+    }
+}
+
+#[test]
+fn execute_line_parses_and_dispatches() {
+    let mut reg = CommandRegistry::new();
+    reg.register(Box::new(Record));
+    let shell = Shell::new().history(false);
+
+    assert!(shell.execute_line(&reg, r#"record one "two words""#));
+    assert!(shell.execute_line(&reg, "   "));
+
+    let calls = CALLS.get().unwrap().lock().unwrap();
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0], vec!["one".to_string(), "two words".to_string()]);
+}
+
+#[test]
+fn exit_commands_end_the_session() {
+    let reg = CommandRegistry::new();
+    let shell = Shell::new().exit_commands(&["bye"]);
+    assert!(!shell.execute_line(&reg, " bye "));
+    assert!(shell.is_exit("bye"));
+    assert!(!shell.is_exit("exit"));
+}
+
+#[test]
+fn editor_history_skips_blanks_and_repeats() {
+    let mut ed = LineEditor::new(vec!["help".into()]);
+    assert!(!ed.push("help"));
+    assert!(!ed.push("  "));
+    assert!(ed.push("ping"));
+    assert!(ed.push("help"));
+    assert_eq!(ed.entries(), ["help", "ping", "help"]);
+}
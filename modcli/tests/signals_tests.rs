@@ -0,0 +1,112 @@
+#![cfg(unix)]
+
+use modcli::cancel::CancellationToken;
+use modcli::command::Command;
+use modcli::context::Context;
+use modcli::error::ModCliError;
+use modcli::exit::ExitCode;
+use modcli::signals;
+use modcli::ModCli;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// Signal state is process-wide; run these tests one at a time.
+static SERIAL: Mutex<()> = Mutex::new(());
+
+fn serial() -> std::sync::MutexGuard<'static, ()> {
+    SERIAL.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn raise(sig: &str) {
+    let pid = std::process::id().to_string();
+    std::process::Command::new("kill")
+        .args([sig, &pid])
+        .status()
+        .expect("kill");
+}
+
+/// Sends `signal` to the process, then waits (up to 10s) for cancellation.
+struct Worker {
+    signal: Option<&'static str>,
+}
+
+impl Command for Worker {
+    fn name(&self) -> &str {
+        "work"
+    }
+    fn execute(&self, _args: &[String]) {}
+    fn run(&self, ctx: &Context) -> Result<ExitCode, ModCliError> {
+        if let Some(sig) = self.signal {
+            raise(sig);
+        }
+        let started = Instant::now();
+        while !ctx.is_cancelled() && started.elapsed() < Duration::from_secs(10) {
+            std::thread::sleep(Duration::from_millis(10));
+            if self.signal.is_none() {
+                break;
+            }
+        }
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+fn cli(signal: Option<&'static str>) -> ModCli {
+    let mut cli = ModCli::new();
+    cli.registry.register(Box::new(Worker { signal }));
+    cli
+}
+
+#[test]
+fn sigint_cancels_the_command_and_exits_130() {
+    let _serial = serial();
+    signals::set_grace_period(Some(Duration::from_secs(30)));
+    let mut cli = cli(Some("-INT"));
+    let started = Instant::now();
+    assert_eq!(
+        cli.run_with_status(vec!["work".into()]),
+        ExitCode::INTERRUPTED
+    );
+    assert!(started.elapsed() < Duration::from_secs(5));
+    assert!(cli.registry.cancellation().is_cancelled());
+
+    // The next run starts clean
+    let mut cli = self::cli(None);
+    assert_eq!(cli.run_with_status(vec!["work".into()]), ExitCode::SUCCESS);
+}
+
+#[test]
+fn sigterm_exits_143() {
+    let _serial = serial();
+    signals::set_grace_period(Some(Duration::from_secs(30)));
+    let mut cli = cli(Some("-TERM"));
+    assert_eq!(cli.run_with_status(vec!["work".into()]), ExitCode(143));
+}
+
+#[test]
+fn nested_watch_keeps_interrupts_local() {
+    let _serial = serial();
+    signals::set_grace_period(Some(Duration::from_secs(30)));
+    let outer_token = CancellationToken::new();
+    let outer = signals::watch(&outer_token);
+    {
+        let inner_token = CancellationToken::new();
+        let inner = signals::watch(&inner_token);
+        signals::interrupt();
+        assert_eq!(inner.interrupted(), Some(ExitCode::INTERRUPTED));
+        assert!(inner_token.is_cancelled());
+    }
+    assert_eq!(outer.interrupted(), None);
+    assert!(!outer_token.is_cancelled());
+}
+
+#[test]
+fn interrupt_without_a_watch_leaves_the_process_alone() {
+    let _serial = serial();
+    // Reaching the asserts means the process wasn't exited
+    assert!(!signals::interrupt());
+    let token = CancellationToken::new();
+    let guard = signals::watch(&token);
+    assert_eq!(guard.interrupted(), None);
+    assert!(signals::interrupt());
+    assert!(token.is_cancelled());
+}
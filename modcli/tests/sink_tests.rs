@@ -0,0 +1,96 @@
+use modcli::exit::ExitCode;
+use modcli::loader::CommandRegistry;
+use modcli::output::handle::Output;
+use modcli::output::sink::{self, FileSink, MemorySink, OutputSink, SplitSink, Stream, TeeSink};
+use modcli::output::{hook, print, progress};
+use std::sync::{Arc, Mutex};
+
+// The global sink and message stream are process-wide.
+static GLOBAL: Mutex<()> = Mutex::new(());
+
+fn with_global_sink(f: impl FnOnce(&MemorySink)) {
+    let _guard = GLOBAL.lock().unwrap_or_else(|e| e.into_inner());
+    let memory = Arc::new(MemorySink::new());
+    sink::set_global(memory.clone());
+    f(&memory);
+    sink::set_message_stream(Stream::Out);
+    sink::reset_global();
+}
+
+#[test]
+fn global_sink_receives_all_output() {
+    with_global_sink(|memory| {
+        print::line("plain");
+        hook::status("busy");
+        progress::show_percent_progress("copy", 50);
+        Output::stdio().eline("oops");
+
+        let mut reg = CommandRegistry::new();
+        reg.command("greet").help("Say hi").run(|ctx| {
+            ctx.out().line("hi");
+            Ok(ExitCode::SUCCESS)
+        });
+        reg.execute("greet", &[]);
+        reg.execute("help", &["greet".into()]);
+
+        let out = modcli::testing::strip_ansi(&memory.contents());
+        assert!(out.starts_with("plain\nStatus: busy\n\rcopy:  50% completehi\n"));
+        assert!(out.contains("Say hi"));
+        assert_eq!(memory.err_contents(), "oops\n");
+    });
+}
+
+#[test]
+fn hook_messages_can_go_to_stderr() {
+    with_global_sink(|memory| {
+        sink::set_message_stream(Stream::Err);
+        print::line("result");
+        hook::warn("careful");
+        assert_eq!(memory.contents(), "result\n");
+        assert_eq!(
+            modcli::testing::strip_ansi(&memory.err_contents()),
+            "Warning: careful\n"
+        );
+    });
+}
+
+#[test]
+fn registry_output_can_use_its_own_sink() {
+    let own = Arc::new(MemorySink::new());
+    let mut reg = CommandRegistry::new();
+    reg.set_output(Output::to(own.clone()));
+    reg.command("emit").run(|ctx| {
+        ctx.out().line("mine");
+        ctx.out().eline("mine too");
+        Ok(ExitCode::SUCCESS)
+    });
+    reg.try_run("emit", &[]).unwrap();
+    assert_eq!(own.contents(), "mine\n");
+    assert_eq!(own.err_contents(), "mine too\n");
+    assert_eq!(reg.output().contents(), "");
+}
+
+#[test]
+fn file_tee_and_split_sinks() {
+    let path = std::env::temp_dir().join(format!("modcli-sink-{}.log", std::process::id()));
+    let file = Arc::new(FileSink::create(&path).unwrap());
+    let errors = Arc::new(MemorySink::new());
+    let copy = Arc::new(MemorySink::new());
+    let split = SplitSink::new(file.clone(), errors.clone());
+    let tee = TeeSink::new(vec![Arc::new(split), copy.clone()]);
+
+    tee.write(Stream::Out, "one\n");
+    tee.write(Stream::Err, "two\n");
+    tee.flush();
+
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "one\n");
+    assert_eq!(errors.err_contents(), "two\n");
+    assert_eq!(copy.contents(), "one\n");
+    assert_eq!(copy.err_contents(), "two\n");
+
+    let appended = FileSink::append(&path).unwrap();
+    appended.write(Stream::Err, "three\n");
+    appended.flush();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "one\nthree\n");
+    let _ = std::fs::remove_file(&path);
+}
@@ -0,0 +1,124 @@
+use std::sync::{Mutex, OnceLock};
+
+use modcli::command::Command;
+use modcli::error::ModCliError;
+use modcli::loader::CommandRegistry;
+use modcli::spec::{ArgSpec, CommandSpec, ParsedArgs, ValueType};
+
+fn argv(items: &[&str]) -> Vec<String> {
+    items.iter().map(|s| s.to_string()).collect()
+}
+
+fn deploy_spec() -> CommandSpec {
+    CommandSpec::new()
+        .arg(ArgSpec::positional("target").required())
+        .arg(
+            ArgSpec::option("port")
+                .short('p')
+                .value_type(ValueType::Int)
+                .default("8080"),
+        )
+        .arg(ArgSpec::option("env").choices(&["dev", "prod"]))
+        .arg(ArgSpec::option("tag").short('t').repeatable())
+        .arg(ArgSpec::flag("verbose").short('v').repeatable())
+        .arg(ArgSpec::flag("dry-run"))
+}
+
+#[test]
+fn parses_positionals_options_and_defaults() {
+    let parsed = deploy_spec()
+        .parse(&argv(&[
+            "web", "--env", "prod", "-t", "a", "--tag=b", "-vv",
+        ]))
+        .unwrap();
+    assert_eq!(parsed.get_str("target"), Some("web"));
+    assert_eq!(parsed.get::<u16>("port"), Some(8080));
+    assert_eq!(parsed.get_str("env"), Some("prod"));
+    assert_eq!(parsed.get_all("tag"), ["a".to_string(), "b".to_string()]);
+    assert_eq!(parsed.count("verbose"), 2);
+    assert!(!parsed.flag("dry-run"));
+}
+
+#[test]
+fn short_inline_values_and_double_dash() {
+    let parsed = deploy_spec()
+        .parse(&argv(&["-p9000", "--dry-run", "--", "--not-an-option"]))
+        .unwrap();
+    assert_eq!(parsed.get::<u16>("port"), Some(9000));
+    assert!(parsed.flag("dry-run"));
+    assert_eq!(parsed.get_str("target"), Some("--not-an-option"));
+}
+
+#[test]
+fn reports_usage_errors() {
+    let spec = deploy_spec();
+    let cases: &[(&[&str], &str)] = &[
+        (&[], "missing required argument: <target>"),
+        (
+            &["web", "--port", "abc"],
+            "expected integer value for --port",
+        ),
+        (&["web", "--env", "qa"], "invalid value 'qa' for --env"),
+        (&["web", "--bogus"], "unknown option: --bogus"),
+        (&["web", "--port"], "missing value for --port"),
+        (&["web", "--env", "dev", "--env", "prod"], "more than once"),
+        (&["web", "extra"], "unexpected argument: extra"),
+    ];
+    for (input, expected) in cases {
+        match spec.parse(&argv(input)) {
+            Err(ModCliError::InvalidUsage(msg)) => {
+                assert!(msg.contains(expected), "{input:?}: {msg}")
+            }
+            other => panic!("{input:?}: expected InvalidUsage, got {other:?}"),
+        }
+    }
+}
+
+#[test]
+fn usage_line_lists_arguments() {
+    let usage = deploy_spec().usage();
+    assert!(usage.starts_with("<target> [--port <PORT>]"));
+    assert!(usage.contains("[--verbose...]"));
+}
+
+static SEEN: OnceLock<Mutex<Vec<String>>> = OnceLock::new();
+
+struct Greet;
+
+impl Command for Greet {
+    fn name(&self) -> &str {
+        "greet"
+    }
+    fn spec(&self) -> Option<CommandSpec> {
+        Some(
+            CommandSpec::new()
+                .arg(ArgSpec::positional("name").default("world"))
+                .arg(ArgSpec::option("times").value_type(ValueType::Int)),
+        )
+    }
+    fn execute(&self, _args: &[String]) {
+        panic!("execute_parsed should be used when a spec is declared")
+    }
+    fn execute_parsed(&self, parsed: &ParsedArgs, _registry: &CommandRegistry) {
+        let name = parsed.get_str("name").unwrap_or_default().to_string();
+        SEEN.get_or_init(|| Mutex::new(Vec::new()))
+            .lock()
+            .unwrap()
+            .push(name);
+    }
+}
+
+#[test]
+fn registry_parses_spec_before_execute() {
+    let mut reg = CommandRegistry::new();
+    reg.register(Box::new(Greet));
+
+    reg.try_execute("greet", &argv(&["james"])).unwrap();
+    let err = reg
+        .try_execute("greet", &argv(&["--times", "x"]))
+        .unwrap_err();
+    assert!(matches!(err, ModCliError::InvalidUsage(_)));
+
+    let seen = SEEN.get().unwrap().lock().unwrap();
+    assert_eq!(seen.as_slice(), ["james".to_string()]);
+}
@@ -0,0 +1,118 @@
+use modcli::command::Command;
+use modcli::error::ModCliError;
+use modcli::loader::CommandRegistry;
+use modcli::suggest::{edit_distance, suggestions, unknown_hint};
+
+struct Named(&'static str, &'static [&'static str]);
+
+impl Command for Named {
+    fn name(&self) -> &str {
+        self.0
+    }
+    fn aliases(&self) -> &[&str] {
+        self.1
+    }
+    fn execute(&self, _args: &[String]) {}
+}
+
+struct Secret;
+
+impl Command for Secret {
+    fn name(&self) -> &str {
+        "status-secret"
+    }
+    fn hidden(&self) -> bool {
+        true
+    }
+    fn execute(&self, _args: &[String]) {}
+}
+
+struct Admin;
+
+impl Command for Admin {
+    fn name(&self) -> &str {
+        "stats"
+    }
+    fn required_caps(&self) -> &[&str] {
+        &["admin"]
+    }
+    fn execute(&self, _args: &[String]) {}
+}
+
+fn registry() -> CommandRegistry {
+    let mut reg = CommandRegistry::new();
+    reg.register(Box::new(Named("status", &["st"])));
+    reg.register(Box::new(Named("deploy", &["ship"])));
+    reg.register(Box::new(Named("node:drain", &[])));
+    reg.register(Box::new(Secret));
+    reg.register(Box::new(Admin));
+    reg
+}
+
+fn unknown(reg: &CommandRegistry, cmd: &str, args: &[&str]) -> (String, Vec<String>) {
+    let args: Vec<String> = args.iter().map(|s| s.to_string()).collect();
+    match reg.try_execute(cmd, &args) {
+        Err(ModCliError::UnknownCommand { name, suggestions }) => (name, suggestions),
+        other => panic!("expected UnknownCommand, got {other:?}"),
+    }
+}
+
+#[test]
+fn edit_distance_basics() {
+    assert_eq!(edit_distance("status", "status"), 0);
+    assert_eq!(edit_distance("stauts", "status"), 1);
+    assert_eq!(edit_distance("stash", "status"), 3);
+    assert_eq!(edit_distance("", "abc"), 3);
+    assert_eq!(edit_distance("naïve", "naive"), 1);
+}
+
+#[test]
+fn ranks_closest_and_prefix_matches() {
+    let names = ["status", "stash", "deploy", "start"];
+    assert_eq!(suggestions("statsu", names)[0], "status");
+    assert_eq!(suggestions("dep", names), vec!["deploy"]);
+    assert!(suggestions("zzzzzz", names).is_empty());
+}
+
+#[test]
+fn unknown_command_carries_suggestions() {
+    let reg = registry();
+    let (name, s) = unknown(&reg, "deplyo", &[]);
+    assert_eq!(name, "deplyo");
+    assert_eq!(s, vec!["deploy"]);
+
+    let (_, s) = unknown(&reg, "nod", &[]);
+    assert_eq!(s[0], "node");
+
+    let (name, s) = unknown(&reg, "node", &["drian"]);
+    assert_eq!(name, "node drian");
+    assert_eq!(s, vec!["node drain"]);
+
+    let (_, s) = unknown(&reg, "shipp", &[]);
+    assert_eq!(s, vec!["ship"]);
+}
+
+#[test]
+fn hidden_and_gated_commands_are_not_suggested() {
+    let mut reg = registry();
+    let (_, s) = unknown(&reg, "stat", &[]);
+    assert!(s.contains(&"status".to_string()));
+    assert!(
+        !s.iter().any(|c| c == "stats" || c == "status-secret"),
+        "{s:?}"
+    );
+
+    reg.grant_cap("admin");
+    let (_, s) = unknown(&reg, "stat", &[]);
+    assert!(s.contains(&"stats".to_string()));
+}
+
+#[test]
+fn hint_text() {
+    assert!(unknown_hint("x", &[]).contains("Type `help`"));
+    assert_eq!(unknown_hint("x", &["a".into()]), "[x]. Did you mean `a`?");
+    assert_eq!(
+        unknown_hint("x", &["a".into(), "b".into(), "c".into()]),
+        "[x]. Did you mean `a`, `b` or `c`?"
+    );
+}
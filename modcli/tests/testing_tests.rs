@@ -0,0 +1,141 @@
+use modcli::error::ModCliError;
+use modcli::exit::ExitCode;
+use modcli::globals;
+use modcli::input;
+use modcli::output::mode;
+use modcli::output::{hook, print};
+use modcli::testing::{strip_ansi, TestCli};
+use modcli::ModCli;
+
+fn cli() -> TestCli {
+    let mut t = TestCli::default();
+    t.cli().set_app("tool", "1.2.3");
+    t.registry().command("report").run(|ctx| {
+        print::line("direct");
+        ctx.out().line("\x1b[1mhandle\x1b[0m");
+        ctx.out().eline("to stderr");
+        hook::info("working");
+        hook::warn("careful");
+        Ok(ExitCode::SUCCESS)
+    });
+    t.registry()
+        .command("fail")
+        .run(|_| Err(ModCliError::Validation("bad input".into())));
+    t
+}
+
+#[test]
+fn captures_streams_messages_and_exit_code() {
+    let mut t = cli();
+    let out = t.run(&["report"]);
+    assert!(out.success());
+    assert_eq!(
+        out.stdout,
+        "direct\nhandle\nInfo: working\nWarning: careful\n"
+    );
+    assert_eq!(out.stderr, "to stderr\n");
+    assert_eq!(out.messages("info"), vec!["working"]);
+    assert_eq!(out.messages("warn"), vec!["careful"]);
+
+    let out = t.run(&["fail"]);
+    assert_eq!(out.code, ExitCode::DATAERR);
+    assert_eq!(out.messages("error"), vec!["validation failed: bad input"]);
+
+    let out = t.run(&["nope"]);
+    assert_eq!(out.code, ExitCode::NOT_FOUND);
+    assert_eq!(out.messages("unknown").len(), 1);
+
+    assert_eq!(t.run(&["--version"]).stdout, "tool 1.2.3\n");
+}
+
+#[test]
+fn ansi_is_stripped_unless_kept() {
+    let mut t = cli();
+    let out = t.keep_ansi(true).run(&["report"]);
+    assert!(out.stdout.contains("\x1b[1mhandle\x1b[0m"));
+    assert_eq!(
+        strip_ansi("\x1b[38;5;1mred\x1b[0m \x1b]8;;https://x\x07link\x1b]8;;\x1b\\"),
+        "red link"
+    );
+}
+
+#[test]
+fn prompts_read_scripted_answers() {
+    let mut t = TestCli::default();
+    t.registry().command("init").run(|ctx| {
+        let name = input::text("Name?").required().get().unwrap();
+        let lang = input::select("Language", ["rust", "go"]).get().unwrap();
+        let git = input::confirm("Git?").default_no().get();
+        ctx.out()
+            .line(&format!("name={name} lang={lang} git={git}"));
+        Ok(ExitCode::SUCCESS)
+    });
+
+    // an empty answer to a required prompt is retried with the next one
+    let out = t.answers(["", "demo", "2", "y"]).run(&["init"]);
+    assert_eq!(out.messages("warn"), vec!["Value required"]);
+    assert_eq!(
+        out.stdout,
+        "Name? \nWarning: Value required\nName? demo\n\
+         Language\n  1. rust\n  2. go\nEnter choice [1-2]: 2\n\
+         Git? [y/N] y\nname=demo lang=1 git=true\n"
+    );
+}
+
+#[test]
+fn running_out_of_answers_does_not_block() {
+    let mut t = TestCli::default();
+    t.registry().command("ask").run(|ctx| {
+        let first = input::prompt_text("First");
+        let sure = input::confirm("Sure?").default_yes().get();
+        let secret = input::prompt_password("Token");
+        ctx.out()
+            .line(&format!("first={first} sure={sure} secret={secret:?}"));
+        Ok(ExitCode::SUCCESS)
+    });
+    let out = t.answer("one").run(&["ask"]);
+    assert!(out.stdout.ends_with("first=one sure=true secret=\"\"\n"));
+
+    // answers left over from one run are used by the next
+    t.answers(["a", "n", "hunter2", "extra"]);
+    let out = t.run(&["ask"]);
+    assert!(out
+        .stdout
+        .ends_with("first=a sure=false secret=\"hunter2\"\n"));
+    assert!(!out.stdout.contains("hunter2\n"));
+    let out = t.run(&["ask"]);
+    assert!(out.stdout.starts_with("First: extra\n"));
+}
+
+#[test]
+fn run_settings_stay_in_the_session() {
+    let mut t = TestCli::new(ModCli::with_standard_globals());
+    let outside = (globals::verbosity(), mode::output_mode());
+    t.registry().command("show").run(move |ctx| {
+        // Another thread sees the process-wide settings, untouched by this run
+        let seen = std::thread::spawn(|| (globals::verbosity(), mode::output_mode()))
+            .join()
+            .unwrap();
+        assert_eq!(seen, outside);
+        ctx.out()
+            .line(&format!("{} {}", globals::verbosity(), mode::output_mode()));
+        Ok(ExitCode::SUCCESS)
+    });
+    let out = t.run(&["-q", "--output", "ndjson", "show"]);
+    assert_eq!(out.stdout, "-1 ndjson\n");
+    assert_eq!((globals::verbosity(), mode::output_mode()), outside);
+}
+
+#[cfg(feature = "internal-commands")]
+#[test]
+fn help_for_an_unknown_command_fails() {
+    let mut t = cli();
+    let out = t.run(&["help", "repot"]);
+    assert_eq!(out.code, ExitCode::NOT_FOUND);
+    assert_eq!(out.messages("unknown").len(), 1);
+    assert!(out.messages("unknown")[0].contains("report"));
+
+    let out = t.run(&["help", "report"]);
+    assert!(out.success());
+    assert!(out.stdout.starts_with("report\n"));
+}
@@ -0,0 +1,128 @@
+use std::cell::RefCell;
+
+use modcli::command::Command;
+use modcli::error::ModCliError;
+use modcli::loader::CommandRegistry;
+use modcli::tree::{alias_path, Group};
+
+// Per-thread so parallel tests don't see each other's calls
+thread_local! {
+    static LAST: RefCell<String> = const { RefCell::new(String::new()) };
+}
+
+struct Leaf(&'static str, &'static [&'static str]);
+
+impl Command for Leaf {
+    fn name(&self) -> &str {
+        self.0
+    }
+    fn aliases(&self) -> &[&str] {
+        self.1
+    }
+    fn execute(&self, args: &[String]) {
+        LAST.with(|l| *l.borrow_mut() = format!("{} {}", self.0, args.join(",")));
+    }
+}
+
+fn registry() -> CommandRegistry {
+    let mut reg = CommandRegistry::new();
+    reg.register(Box::new(Leaf("cluster:node:drain", &["dr"])));
+    reg.register(Box::new(Leaf("cluster:node:cordon", &[])));
+    reg.register(Box::new(Leaf("cluster:status", &[])));
+    reg.register(Box::new(Leaf("tree:a", &[])));
+    reg.register(Box::new(Leaf("tree:a:b:c:d", &[])));
+    reg.register_group(
+        Group::new("cluster")
+            .help("Manage clusters")
+            .aliases(&["cl"]),
+    );
+    reg
+}
+
+fn run(reg: &CommandRegistry, words: &[&str]) -> Result<(), ModCliError> {
+    let args: Vec<String> = words[1..].iter().map(|s| s.to_string()).collect();
+    reg.try_execute(words[0], &args)
+}
+
+fn last_call() -> String {
+    LAST.with(|l| l.borrow().clone())
+}
+
+#[test]
+fn dispatches_to_any_depth() {
+    let reg = registry();
+    run(&reg, &["cluster", "node", "drain", "web-1"]).unwrap();
+    assert_eq!(last_call(), "cluster:node:drain web-1");
+    run(&reg, &["tree", "a", "b", "c", "d", "x", "y"]).unwrap();
+    assert_eq!(last_call(), "tree:a:b:c:d x,y");
+    // Colon paths still dispatch directly
+    run(&reg, &["cluster:status"]).unwrap();
+    assert_eq!(last_call(), "cluster:status ");
+}
+
+#[test]
+fn longest_prefix_falls_back_to_deepest_command() {
+    let reg = registry();
+    // `tree a b` is a group under the `tree:a` command; `zzz` is not a child
+    run(&reg, &["tree", "a", "b", "zzz"]).unwrap();
+    assert_eq!(last_call(), "tree:a b,zzz");
+}
+
+#[test]
+fn aliases_resolve_at_each_level() {
+    let reg = registry();
+    run(&reg, &["cl", "node", "dr", "web-2"]).unwrap();
+    assert_eq!(last_call(), "cluster:node:drain web-2");
+    assert!(
+        run(&reg, &["dr"]).is_err(),
+        "nested alias leaked to top level"
+    );
+    assert_eq!(alias_path("cluster:node:drain", "dr"), "cluster:node:dr");
+    assert_eq!(alias_path("deploy", "d"), "d");
+}
+
+#[test]
+fn bare_group_succeeds_and_unknown_child_suggests() {
+    let reg = registry();
+    assert!(run(&reg, &["cluster", "node"]).is_ok());
+    match run(&reg, &["cluster", "node", "drian"]) {
+        Err(ModCliError::UnknownCommand { name, suggestions }) => {
+            assert_eq!(name, "cluster node drian");
+            assert_eq!(suggestions, vec!["cluster node drain"]);
+        }
+        other => panic!("expected UnknownCommand, got {other:?}"),
+    }
+}
+
+#[test]
+fn children_and_resolve() {
+    let reg = registry();
+    assert_eq!(
+        reg.children("cluster"),
+        vec!["cluster:node".to_string(), "cluster:status".to_string()]
+    );
+    assert!(reg.is_group("cluster:node"));
+    assert!(reg.is_group_visible("cluster"));
+    assert_eq!(reg.node_help("cluster"), Some("Manage clusters"));
+    let words: Vec<String> = ["cl", "node", "cordon", "x"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    assert_eq!(
+        reg.resolve(&words),
+        Some(("cluster:node:cordon".to_string(), 3))
+    );
+}
+
+#[test]
+fn a_command_with_children_still_routes_deeper_after_running_bare() {
+    let mut reg = registry();
+    reg.register(Box::new(Leaf("svc", &[])));
+    reg.register(Box::new(Leaf("svc:logs", &[])));
+    run(&reg, &["svc"]).unwrap();
+    assert_eq!(last_call(), "svc ");
+    run(&reg, &["svc", "logs", "api"]).unwrap();
+    assert_eq!(last_call(), "svc:logs api");
+    run(&reg, &["svc", "--x"]).unwrap();
+    assert_eq!(last_call(), "svc --x");
+}